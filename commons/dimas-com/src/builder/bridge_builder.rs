@@ -0,0 +1,266 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `bridge_builder` provides the `BridgeBuilder` for a `Bridge` between two sessions.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::communicator::DEFAULT_SESSION;
+use crate::error::Error;
+use crate::traits::Responder;
+use crate::zenoh::bridge::{Bridge, BridgeDirection, Remapping};
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	sync::Arc,
+};
+use core::time::Duration;
+use dimas_core::builder_states::{NoSelector, NoStorage, Selector, Storage};
+use dimas_core::{Result, enums::OperationState, traits::Context, utils::selector_from};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, sync::RwLock};
+// endregion:	--- modules
+
+// region:		--- BridgeBuilder
+/// The builder for a [`Bridge`]
+pub struct BridgeBuilder<P, K, S>
+where
+	P: Send + Sync + 'static,
+{
+	context: Context<P>,
+	activation_state: OperationState,
+	source: String,
+	target: String,
+	direction: BridgeDirection,
+	remapping: Option<Remapping>,
+	rate_limit: Option<Duration>,
+	selector: K,
+	storage: S,
+}
+
+impl<P> BridgeBuilder<P, NoSelector, NoStorage>
+where
+	P: Send + Sync + 'static,
+{
+	/// Construct a [`BridgeBuilder`] in initial state
+	#[must_use]
+	pub fn new(context: Context<P>) -> Self {
+		Self {
+			context,
+			activation_state: OperationState::Active,
+			source: DEFAULT_SESSION.to_string(),
+			target: DEFAULT_SESSION.to_string(),
+			direction: BridgeDirection::Forward,
+			remapping: None,
+			rate_limit: None,
+			selector: NoSelector,
+			storage: NoStorage,
+		}
+	}
+}
+
+impl<P, K, S> BridgeBuilder<P, K, S>
+where
+	P: Send + Sync + 'static,
+{
+	/// Set the activation state.
+	#[must_use]
+	pub const fn activation_state(mut self, state: OperationState) -> Self {
+		self.activation_state = state;
+		self
+	}
+
+	/// Set the id of the session the messages come from
+	#[must_use]
+	pub fn from(mut self, session_id: &str) -> Self {
+		self.source = session_id.into();
+		self
+	}
+
+	/// Set the id of the session the messages go to
+	#[must_use]
+	pub fn to(mut self, session_id: &str) -> Self {
+		self.target = session_id.into();
+		self
+	}
+
+	/// Set the direction, default is [`BridgeDirection::Forward`]
+	#[must_use]
+	pub const fn direction(mut self, direction: BridgeDirection) -> Self {
+		self.direction = direction;
+		self
+	}
+
+	/// Remap the key expressions by replacing the leading part `from`
+	/// in the source session with `to` in the target session
+	#[must_use]
+	pub fn remap(mut self, from: &str, to: &str) -> Self {
+		self.remapping = Some(Remapping {
+			from: from.into(),
+			to: to.into(),
+		});
+		self
+	}
+
+	/// Forward at most one message per key expression within `interval`
+	#[must_use]
+	pub const fn rate_limit(mut self, interval: Duration) -> Self {
+		self.rate_limit = Some(interval);
+		self
+	}
+}
+
+impl<P, K> BridgeBuilder<P, K, NoStorage>
+where
+	P: Send + Sync + 'static,
+{
+	/// Provide agents storage for the bridge
+	#[must_use]
+	pub fn storage(
+		self,
+		storage: Arc<RwLock<HashMap<String, Box<dyn Responder>>>>,
+	) -> BridgeBuilder<P, K, Storage<Box<dyn Responder>>> {
+		let Self {
+			context,
+			activation_state,
+			source,
+			target,
+			direction,
+			remapping,
+			rate_limit,
+			selector,
+			..
+		} = self;
+		BridgeBuilder {
+			context,
+			activation_state,
+			source,
+			target,
+			direction,
+			remapping,
+			rate_limit,
+			selector,
+			storage: Storage { storage },
+		}
+	}
+}
+
+impl<P, S> BridgeBuilder<P, NoSelector, S>
+where
+	P: Send + Sync + 'static,
+{
+	/// Set the full key expression of the messages to forward
+	#[must_use]
+	pub fn selector(self, selector: &str) -> BridgeBuilder<P, Selector, S> {
+		let Self {
+			context,
+			activation_state,
+			source,
+			target,
+			direction,
+			remapping,
+			rate_limit,
+			storage,
+			..
+		} = self;
+		BridgeBuilder {
+			context,
+			activation_state,
+			source,
+			target,
+			direction,
+			remapping,
+			rate_limit,
+			selector: Selector {
+				selector: selector.into(),
+			},
+			storage,
+		}
+	}
+
+	/// Set only the message qualifing part of the key expression.
+	/// Will be prefixed with `Agent`s prefix.
+	#[must_use]
+	pub fn topic(self, topic: &str) -> BridgeBuilder<P, Selector, S> {
+		let selector = selector_from(topic, self.context.prefix());
+		self.selector(&selector)
+	}
+}
+
+impl<P, S> BridgeBuilder<P, Selector, S>
+where
+	P: Send + Sync + 'static,
+{
+	/// Build the [`Bridge`]
+	///
+	/// # Errors
+	/// if a session does not exist or both sessions are the same
+	pub fn build(self) -> Result<Bridge> {
+		if self.source == self.target {
+			return Err(Error::InvalidBridge(self.source).into());
+		}
+		let source = self
+			.context
+			.session(&self.source)
+			.ok_or_else(|| Error::NoCommunicator(self.source.clone()))?;
+		let target = self
+			.context
+			.session(&self.target)
+			.ok_or_else(|| Error::NoCommunicator(self.target.clone()))?;
+		let key = format!(
+			"bridge/{}/{}/{}",
+			self.source, self.target, self.selector.selector
+		);
+		Ok(Bridge::new(
+			source,
+			target,
+			self.selector.selector,
+			key,
+			self.activation_state,
+			self.direction,
+			self.remapping,
+			self.rate_limit,
+		))
+	}
+}
+
+impl<P> BridgeBuilder<P, Selector, Storage<Box<dyn Responder>>>
+where
+	P: Send + Sync + 'static,
+{
+	/// Build and add the [`Bridge`] to the `Agent`
+	///
+	/// # Errors
+	/// if a session does not exist or both sessions are the same
+	pub fn add(self) -> Result<Option<Box<dyn Responder>>> {
+		let collection = self.storage.storage.clone();
+		let bridge = self.build()?;
+		let r = collection
+			.write()
+			.map_err(|_| Error::MutexPoison(String::from("BridgeBuilder")))?
+			.insert(bridge.selector().to_string(), Box::new(bridge));
+		Ok(r)
+	}
+}
+// endregion:	--- BridgeBuilder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug)]
+	struct Props {}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<BridgeBuilder<Props, NoSelector, NoStorage>>();
+	}
+}
@@ -5,6 +5,7 @@
 //!
 
 // region:    	--- modules
+mod bridge_builder;
 mod liveliness_subscriber_builder;
 mod observable_builder;
 mod observer_builder;
@@ -14,6 +15,7 @@ mod queryable_builder;
 mod subscriber_builder;
 
 // flatten
+pub use bridge_builder::BridgeBuilder;
 pub use liveliness_subscriber_builder::LivelinessSubscriberBuilder;
 pub use observable_builder::ObservableBuilder;
 pub use observer_builder::ObserverBuilder;
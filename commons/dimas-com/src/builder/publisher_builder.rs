@@ -42,9 +42,12 @@ where
 	congestion_control: CongestionControl,
 	encoding: String,
 	express: bool,
+	intra_process: bool,
 	priority: Priority,
 	#[cfg(feature = "unstable")]
 	reliability: Reliability,
+	#[cfg(feature = "shared-memory")]
+	shared_memory: bool,
 	selector: K,
 	storage: S,
 }
@@ -65,9 +68,12 @@ where
 			congestion_control: CongestionControl::Drop,
 			encoding: Encoding::default().to_string(),
 			express: false,
+			intra_process: false,
 			priority: Priority::Data,
 			#[cfg(feature = "unstable")]
 			reliability: Reliability::BestEffort,
+			#[cfg(feature = "shared-memory")]
+			shared_memory: false,
 			selector: NoSelector,
 			storage: NoStorage,
 		}
@@ -121,6 +127,18 @@ where
 		self
 	}
 
+	/// Enable or disable the intra-process delivery.
+	///
+	/// With intra-process delivery, subscribers in the same process and session get the messages
+	/// directly, a [`Message::share`](dimas_core::message_types::Message::share)d value
+	/// without encoding and copying.
+	/// Remote subscribers still get the messages via zenoh.
+	#[must_use]
+	pub const fn intra_process(mut self, intra_process: bool) -> Self {
+		self.intra_process = intra_process;
+		self
+	}
+
 	/// Set the publishers priority
 	#[must_use]
 	pub const fn set_priority(mut self, priority: Priority) -> Self {
@@ -128,6 +146,18 @@ where
 		self
 	}
 
+	/// Publish via shared memory.
+	///
+	/// The messages are written into buffers allocated from the sessions shared memory provider,
+	/// so subscribers on the same host get them without copying through the network stack.
+	/// Needs shared memory enabled in the configuration, see `Config::set_shared_memory`.
+	#[cfg(feature = "shared-memory")]
+	#[must_use]
+	pub const fn shared_memory(mut self, enabled: bool) -> Self {
+		self.shared_memory = enabled;
+		self
+	}
+
 	/// Set the publishers reliability
 	#[cfg(feature = "unstable")]
 	#[must_use]
@@ -156,9 +186,12 @@ where
 			congestion_control,
 			encoding,
 			express,
+			intra_process,
 			priority,
 			#[cfg(feature = "unstable")]
 			reliability,
+			#[cfg(feature = "shared-memory")]
+			shared_memory,
 			selector,
 			..
 		} = self;
@@ -171,9 +204,12 @@ where
 			congestion_control,
 			encoding,
 			express,
+			intra_process,
 			priority,
 			#[cfg(feature = "unstable")]
 			reliability,
+			#[cfg(feature = "shared-memory")]
+			shared_memory,
 			selector,
 			storage: Storage { storage },
 		}
@@ -196,9 +232,12 @@ where
 			congestion_control,
 			encoding,
 			express,
+			intra_process,
 			priority,
 			#[cfg(feature = "unstable")]
 			reliability,
+			#[cfg(feature = "shared-memory")]
+			shared_memory,
 			storage,
 			..
 		} = self;
@@ -211,9 +250,12 @@ where
 			congestion_control,
 			encoding,
 			express,
+			intra_process,
 			priority,
 			#[cfg(feature = "unstable")]
 			reliability,
+			#[cfg(feature = "shared-memory")]
+			shared_memory,
 			selector: Selector {
 				selector: selector.into(),
 			},
@@ -252,9 +294,12 @@ where
 			self.congestion_control,
 			self.encoding,
 			self.express,
+			self.intra_process,
 			self.priority,
 			#[cfg(feature = "unstable")]
 			self.reliability,
+			#[cfg(feature = "shared-memory")]
+			self.shared_memory,
 		))
 	}
 }
@@ -263,20 +308,38 @@ impl<P> PublisherBuilder<P, Selector, Storage<Box<dyn PublisherTrait>>>
 where
 	P: Send + Sync + 'static,
 {
-	/// Build and add the [Publisher] to the `Agent`s context
+	/// Build and add the [Publisher] to the `Agent`s context.
+	/// For an MQTT session an MQTT publisher is added, which ignores the zenoh specific settings.
 	///
 	/// # Errors
 	/// Currently none
 	pub fn add(self) -> Result<Option<Box<dyn PublisherTrait>>> {
 		let collection = self.storage.storage.clone();
+		#[cfg(feature = "mqtt")]
+		if self.context.session(&self.session_id).is_none() {
+			let p = crate::mqtt::publisher::Publisher::new(
+				self.session_id,
+				self.selector.selector,
+				self.activation_state,
+			);
+			return insert(&collection, Box::new(p));
+		}
 		let p = self.build()?;
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("PublisherBuilder")))?
-			.insert(p.selector().to_string(), Box::new(p));
-		Ok(r)
+		insert(&collection, Box::new(p))
 	}
 }
+
+/// Insert the publisher `p` into `collection`, returning a replaced one
+fn insert(
+	collection: &RwLock<HashMap<String, Box<dyn PublisherTrait>>>,
+	p: Box<dyn PublisherTrait>,
+) -> Result<Option<Box<dyn PublisherTrait>>> {
+	let r = collection
+		.write()
+		.map_err(|_| Error::MutexPoison(String::from("PublisherBuilder")))?
+		.insert(p.selector().to_string(), p);
+	Ok(r)
+}
 // endregion:	--- PublisherBuilder
 
 #[cfg(test)]
@@ -272,20 +272,40 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the [`Subscriber`] to the `Agent`.
+	/// For an MQTT session an MQTT subscriber is added.
 	///
 	/// # Errors
 	/// Currently none
 	pub fn add(self) -> Result<Option<Box<dyn SubscriberTrait>>> {
 		let c = self.storage.storage.clone();
+		#[cfg(feature = "mqtt")]
+		if self.context.session(&self.session_id).is_none() {
+			let s = crate::mqtt::subscriber::Subscriber::new(
+				self.session_id,
+				self.selector.selector,
+				self.context,
+				self.activation_state,
+				self.put_callback.callback,
+				self.delete_callback,
+			);
+			return insert(&c, Box::new(s));
+		}
 		let s = self.build()?;
-
-		let r = c
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("SubscriberBuilder")))?
-			.insert(s.selector().to_string(), Box::new(s));
-		Ok(r)
+		insert(&c, Box::new(s))
 	}
 }
+
+/// Insert the subscriber `s` into `collection`, returning a replaced one
+fn insert(
+	collection: &RwLock<HashMap<String, Box<dyn SubscriberTrait>>>,
+	s: Box<dyn SubscriberTrait>,
+) -> Result<Option<Box<dyn SubscriberTrait>>> {
+	let r = collection
+		.write()
+		.map_err(|_| Error::MutexPoison(String::from("SubscriberBuilder")))?
+		.insert(s.selector().to_string(), s);
+	Ok(r)
+}
 // endregion:	--- SubscriberBuilder
 
 #[cfg(test)]
@@ -0,0 +1,856 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Implementation of an in-memory loopback communicator for tests
+//!
+//! All [`LoopbackCommunicator`]s created by a [`Loopback`] share a zenoh session without
+//! any network access, so agents within a test binary only communicate with each other.
+//! The [`Loopback`] records the traffic going through the communicator methods
+//! and applies the [`Fault`]s injected for matching key expressions.
+//! Agents attached to the [`Loopback`] expose their subscribers and the number of messages
+//! these handled, so a test can await the handling of the messages sent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use super::DEFAULT_SESSION;
+use crate::traits::LivelinessSubscriber;
+use crate::{
+	enums::CommunicatorImplementation,
+	error::Error,
+	traits::{
+		Communicator, CommunicatorImplementationMethods, CommunicatorMethods, Observer, Publisher,
+		Querier, Responder,
+	},
+};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+	sync::{Arc, Weak},
+	vec::Vec,
+};
+use core::time::Duration;
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	message_types::{Message, QueryableMsg},
+	metrics::{CALLBACK_DURATION, MetricValue, Metrics},
+	traits::Capability,
+};
+use std::{
+	collections::HashMap,
+	sync::{Mutex, RwLock},
+};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use zenoh::{Session, config::ZenohId, key_expr::KeyExpr};
+// endregion:	--- modules
+
+// region:		--- types
+// the initial size of the HashMaps
+const INITIAL_SIZE: usize = 9;
+/// the mode reported by a [`LoopbackCommunicator`]
+const MODE: &str = "loopback";
+// endregion:	--- types
+
+// region:		--- Traffic
+/// The kind of a recorded [`Traffic`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrafficKind {
+	/// a put message
+	Put,
+	/// a delete message
+	Delete,
+	/// a query
+	Get,
+	/// a reply to a query handed to the given callback
+	Reply,
+	/// an observation request
+	Observe,
+	/// a stream request
+	Watch,
+}
+
+/// A message recorded by a [`Loopback`]
+#[derive(Debug, Clone)]
+pub struct Traffic {
+	/// the uuid of the sending communicator
+	pub uuid: String,
+	/// the kind of the message
+	pub kind: TrafficKind,
+	/// the selector of the message
+	pub selector: String,
+	/// the content of the message, if any
+	pub message: Option<Message>,
+	/// the fault applied to the message, if any
+	pub fault: Option<FaultKind>,
+}
+// endregion:	--- Traffic
+
+// region:		--- Fault
+/// The kind of an injected [`Fault`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FaultKind {
+	/// the message is delayed by the given duration
+	Delay(Duration),
+	/// the message is silently dropped
+	Drop,
+	/// sending the message fails with an error
+	Fail,
+}
+
+/// A fault injected into a [`Loopback`] for the messages matching a key expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fault {
+	selector: String,
+	kind: FaultKind,
+	times: Option<usize>,
+}
+
+impl Fault {
+	/// Delay the messages matching `selector`
+	#[must_use]
+	pub fn delay(selector: &str, delay: Duration) -> Self {
+		Self::new(selector, FaultKind::Delay(delay))
+	}
+
+	/// Drop the messages matching `selector`
+	#[must_use]
+	pub fn drop(selector: &str) -> Self {
+		Self::new(selector, FaultKind::Drop)
+	}
+
+	/// Fail sending the messages matching `selector`
+	#[must_use]
+	pub fn fail(selector: &str) -> Self {
+		Self::new(selector, FaultKind::Fail)
+	}
+
+	/// Apply the fault only to the next `times` matching messages, default is all of them
+	#[must_use]
+	pub const fn times(mut self, times: usize) -> Self {
+		self.times = Some(times);
+		self
+	}
+
+	/// Constructor
+	fn new(selector: &str, kind: FaultKind) -> Self {
+		Self {
+			selector: selector.into(),
+			kind,
+			times: None,
+		}
+	}
+}
+// endregion:	--- Fault
+
+// region:		--- Loopback
+/// The traffic recorded by a [`Loopback`]
+#[derive(Debug, Default)]
+struct Recording {
+	/// the number of messages forgotten by clearing
+	forgotten: usize,
+	/// the messages recorded since the last clearing
+	traffic: Vec<Traffic>,
+}
+
+/// An agent attached to a [`Loopback`]
+#[derive(Debug)]
+struct Attached {
+	/// the agents metrics, counting the messages handled by its subscribers
+	metrics: Metrics,
+	/// the agents responders
+	responders: Weak<RwLock<HashMap<String, Box<dyn Responder>>>>,
+}
+
+/// An in-memory network for [`LoopbackCommunicator`]s
+#[derive(Debug, Clone)]
+pub struct Loopback {
+	/// the communicator for the isolated session
+	communicator: Arc<CommunicatorImplementation>,
+	/// the recorded traffic
+	recording: Arc<Mutex<Recording>>,
+	/// the injected faults
+	faults: Arc<Mutex<Vec<(KeyExpr<'static>, Fault)>>>,
+	/// the attached agents
+	attached: Arc<Mutex<Vec<Attached>>>,
+}
+
+impl Loopback {
+	/// Create a [`Loopback`] with a zenoh session neither listening, connecting nor scouting
+	/// # Errors
+	/// if the session can not be opened
+	pub fn new() -> Result<Self> {
+		let mut config = zenoh::Config::default();
+		for (key, value) in [
+			("listen/endpoints", "[]"),
+			("connect/endpoints", "[]"),
+			("scouting/multicast/enabled", "false"),
+			("scouting/gossip/enabled", "false"),
+		] {
+			config
+				.insert_json5(key, value)
+				.map_err(|source| Error::CreateCommunicator { source })?;
+		}
+		let zenoh = crate::zenoh::Communicator::new(&config)?;
+		Ok(Self {
+			communicator: Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+			recording: Arc::new(Mutex::new(Recording::default())),
+			faults: Arc::new(Mutex::new(Vec::new())),
+			attached: Arc::new(Mutex::new(Vec::new())),
+		})
+	}
+
+	/// Create a [`LoopbackCommunicator`] with an own uuid in this [`Loopback`]
+	#[must_use]
+	pub fn communicator(&self) -> Arc<dyn Communicator> {
+		Arc::new(LoopbackCommunicator::new(ZenohId::default(), self.clone()))
+	}
+
+	/// Get the shared session
+	/// # Panics
+	/// never, the communicator always is a zenoh communicator
+	#[must_use]
+	pub fn session(&self) -> Arc<Session> {
+		self.communicator.session().expect("snh")
+	}
+
+	/// Get a copy of the traffic recorded so far
+	#[must_use]
+	pub fn traffic(&self) -> Vec<Traffic> {
+		self.recording
+			.lock()
+			.map(|recording| recording.traffic.clone())
+			.unwrap_or_default()
+	}
+
+	/// Get the number of messages recorded so far, including the forgotten ones.
+	/// It is the position of the next recorded message.
+	#[must_use]
+	pub fn recorded(&self) -> usize {
+		self.recording
+			.lock()
+			.map(|recording| recording.forgotten + recording.traffic.len())
+			.unwrap_or_default()
+	}
+
+	/// Get a copy of the traffic recorded from `position` on together with the positions,
+	/// forgotten messages are skipped
+	#[must_use]
+	pub fn traffic_since(&self, position: usize) -> Vec<(usize, Traffic)> {
+		self.recording
+			.lock()
+			.map(|recording| {
+				let start = position.saturating_sub(recording.forgotten);
+				recording
+					.traffic
+					.iter()
+					.enumerate()
+					.skip(start)
+					.map(|(index, traffic)| (recording.forgotten + index, traffic.clone()))
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Forget the traffic recorded so far, the positions of the following messages continue
+	pub fn clear_traffic(&self) {
+		if let Ok(mut recording) = self.recording.lock() {
+			recording.forgotten += recording.traffic.len();
+			recording.traffic.clear();
+		}
+	}
+
+	/// Inject a [`Fault`], the first matching fault applies to a message
+	/// # Errors
+	/// if the selector of the fault is not a valid key expression
+	pub fn inject(&self, fault: Fault) -> Result<()> {
+		let key_expr = KeyExpr::try_from(fault.selector.clone())
+			.map_err(|_| Error::InvalidSelector(fault.selector.clone()))?;
+		self.faults
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("Loopback")))?
+			.push((key_expr, fault));
+		Ok(())
+	}
+
+	/// Remove all injected [`Fault`]s
+	pub fn clear_faults(&self) {
+		if let Ok(mut faults) = self.faults.lock() {
+			faults.clear();
+		}
+	}
+
+	/// Attach an agent by its `metrics` and `responders`,
+	/// its subscribers are taken into account by [`Self::handled`] and [`Self::deliveries`]
+	pub fn attach(
+		&self,
+		metrics: Metrics,
+		responders: &Arc<RwLock<HashMap<String, Box<dyn Responder>>>>,
+	) {
+		if let Ok(mut attached) = self.attached.lock() {
+			attached.push(Attached {
+				metrics,
+				responders: Arc::downgrade(responders),
+			});
+		}
+	}
+
+	/// Get the number of messages handled by the subscriber callbacks of the attached agents
+	#[must_use]
+	pub fn handled(&self) -> u64 {
+		self.attached
+			.lock()
+			.map(|attached| {
+				attached
+					.iter()
+					.flat_map(|agent| agent.metrics.snapshot())
+					.filter(|metric| {
+						metric.name() == CALLBACK_DURATION
+							&& metric.label("kind") == Some("subscriber")
+					})
+					.map(|metric| match metric.value() {
+						MetricValue::Histogram { count, .. } => *count,
+						MetricValue::Counter(_) => 0,
+					})
+					.sum()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Get the number of running subscribers of the attached agents a put to `selector` is delivered to
+	#[must_use]
+	pub fn deliveries(&self, selector: &str) -> u64 {
+		let Ok(key_expr) = KeyExpr::try_from(selector) else {
+			return 0;
+		};
+		self.attached
+			.lock()
+			.map(|attached| {
+				attached
+					.iter()
+					.filter_map(|agent| agent.responders.upgrade())
+					.map(|responders| {
+						responders.read().map_or(0, |responders| {
+							responders
+								.values()
+								.filter(|responder| {
+									responder.is_subscribed()
+										&& KeyExpr::try_from(responder.selector()).is_ok_and(
+											|subscriber| subscriber.intersects(&key_expr),
+										)
+								})
+								.count() as u64
+						})
+					})
+					.sum()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Take the first fault matching `selector`, removing it when used up
+	fn take_fault(&self, selector: &str) -> Option<FaultKind> {
+		let key = selector
+			.split_once('?')
+			.map_or(selector, |(key, _)| key);
+		let key_expr = KeyExpr::try_from(key).ok()?;
+		let mut faults = self.faults.lock().ok()?;
+		let index = faults
+			.iter()
+			.position(|(pattern, _)| pattern.intersects(&key_expr))?;
+		let kind = faults[index].1.kind;
+		match &mut faults[index].1.times {
+			Some(1) => {
+				faults.remove(index);
+			}
+			Some(times) => *times -= 1,
+			None => {}
+		}
+		drop(faults);
+		Some(kind)
+	}
+
+	/// Record a message
+	fn record(&self, traffic: Traffic) {
+		if let Ok(mut recording) = self.recording.lock() {
+			recording.traffic.push(traffic);
+		}
+	}
+}
+// endregion:	--- Loopback
+
+// region:		--- LoopbackCommunicator
+/// a communicator within a [`Loopback`]
+#[derive(Debug)]
+pub struct LoopbackCommunicator {
+	/// a generated uuid
+	uuid: ZenohId,
+	/// the mode, always `loopback`
+	mode: String,
+	/// The [`Communicator`]s current operational state.
+	state: OperationState,
+	/// The [`Loopback`] the communicator belongs to
+	loopback: Loopback,
+	/// Registered [`LivelinessSubscriber`]
+	liveliness_subscribers: Arc<RwLock<HashMap<String, Box<dyn LivelinessSubscriber>>>>,
+	/// Registered [`Observer`]
+	observers: Arc<RwLock<HashMap<String, Box<dyn Observer>>>>,
+	/// Registered [`Publisher`]
+	publishers: Arc<RwLock<HashMap<String, Box<dyn Publisher>>>>,
+	/// Registered [`Query`]s
+	queriers: Arc<RwLock<HashMap<String, Box<dyn Querier>>>>,
+	/// Registered [`Observable`]s, [`Queryable`]s and [`Subscriber`]s
+	responders: Arc<RwLock<HashMap<String, Box<dyn Responder>>>>,
+}
+
+impl Capability for LoopbackCommunicator {
+	fn manage_operation_state(&self, new_state: &OperationState) -> Result<()> {
+		if new_state >= &self.state {
+			self.upgrade_capabilities(new_state)?;
+		} else if new_state < &self.state {
+			self.downgrade_capabilities(new_state)?;
+		}
+		Ok(())
+	}
+}
+
+impl Communicator for LoopbackCommunicator {
+	/// Get the liveliness subscribers
+	fn liveliness_subscribers(
+		&self,
+	) -> Arc<RwLock<HashMap<String, Box<dyn LivelinessSubscriber>>>> {
+		self.liveliness_subscribers.clone()
+	}
+
+	/// Get the observers
+	fn observers(&self) -> Arc<RwLock<HashMap<String, Box<dyn Observer>>>> {
+		self.observers.clone()
+	}
+
+	/// Get the publishers
+	fn publishers(&self) -> Arc<RwLock<HashMap<String, Box<dyn Publisher>>>> {
+		self.publishers.clone()
+	}
+
+	/// Get the queries
+	fn queriers(&self) -> Arc<RwLock<HashMap<String, Box<dyn Querier>>>> {
+		self.queriers.clone()
+	}
+
+	/// Get the responders
+	fn responders(&self) -> Arc<RwLock<HashMap<String, Box<dyn Responder>>>> {
+		self.responders.clone()
+	}
+
+	fn uuid(&self) -> String {
+		self.uuid.to_string()
+	}
+
+	fn mode(&self) -> &String {
+		&self.mode
+	}
+
+	fn default_session(&self) -> Arc<Session> {
+		self.loopback.session()
+	}
+
+	fn session(&self, id: &str) -> Option<Arc<Session>> {
+		if id == DEFAULT_SESSION {
+			Some(self.loopback.session())
+		} else {
+			None
+		}
+	}
+
+	fn sessions(&self) -> Vec<Arc<Session>> {
+		[self.loopback.session()].into()
+	}
+
+	fn share(&self) -> Result<Arc<dyn Communicator>> {
+		Ok(self.loopback.communicator())
+	}
+}
+
+impl CommunicatorMethods for LoopbackCommunicator {
+	fn put(&self, selector: &str, message: Message) -> Result<()> {
+		if !self.transmit(TrafficKind::Put, selector, Some(message.clone()))? {
+			return Ok(());
+		}
+		let publishers = self
+			.publishers
+			.read()
+			.map_err(|_| Error::ReadAccess("publishers".into()))?;
+
+		#[allow(clippy::single_match_else)]
+		match publishers.get(selector) {
+			Some(publisher) => publisher.put(message),
+			None => self.loopback.communicator.put(selector, message),
+		}
+	}
+
+	fn delete(&self, selector: &str) -> Result<()> {
+		if !self.transmit(TrafficKind::Delete, selector, None)? {
+			return Ok(());
+		}
+		let publishers = self
+			.publishers
+			.read()
+			.map_err(|_| Error::ReadAccess("publishers".into()))?;
+
+		#[allow(clippy::option_if_let_else)]
+		match publishers.get(selector) {
+			Some(publisher) => publisher.delete(),
+			None => self.loopback.communicator.delete(selector),
+		}
+	}
+
+	fn get(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		if !self.transmit(TrafficKind::Get, selector, message.clone())? {
+			return Ok(());
+		}
+		// record the replies handed to a given callback
+		let mut recording = callback.map(|callback| {
+			move |reply: QueryableMsg| -> Result<()> {
+				let mut message = Message::new(reply.to_vec());
+				if let Some(attachments) = reply.attachments() {
+					message = message.with_attachments(attachments.clone());
+				}
+				self.loopback.record(Traffic {
+					uuid: self.uuid(),
+					kind: TrafficKind::Reply,
+					selector: selector.into(),
+					message: Some(message),
+					fault: None,
+				});
+				callback(reply)
+			}
+		});
+		let callback = recording
+			.as_mut()
+			.map(|callback| callback as &mut dyn FnMut(QueryableMsg) -> Result<()>);
+
+		let queriers = self
+			.queriers
+			.read()
+			.map_err(|_| Error::ReadAccess("queriers".into()))?;
+
+		#[allow(clippy::single_match_else)]
+		blocking(|| match queriers.get(selector) {
+			Some(querier) => querier.get(message, callback),
+			None => self
+				.loopback
+				.communicator
+				.get(selector, message, callback),
+		})
+	}
+
+	fn observe(&self, selector: &str, message: Option<Message>) -> Result<()> {
+		if !self.transmit(TrafficKind::Observe, selector, message.clone())? {
+			return Ok(());
+		}
+		let observers = self
+			.observers
+			.read()
+			.map_err(|_| Error::ReadAccess("observers".into()))?;
+
+		#[allow(clippy::option_if_let_else)]
+		blocking(|| match observers.get(selector) {
+			Some(observer) => observer.request(message),
+			None => self
+				.loopback
+				.communicator
+				.observe(selector, message),
+		})
+	}
+
+	fn watch(&self, selector: &str, message: Message) -> Result<()> {
+		if !self.transmit(TrafficKind::Watch, selector, Some(message.clone()))? {
+			return Ok(());
+		}
+		let observers = self
+			.observers
+			.read()
+			.map_err(|_| Error::ReadAccess("observers".into()))?;
+
+		#[allow(clippy::option_if_let_else)]
+		blocking(|| match observers.get(selector) {
+			Some(observer) => observer.request(Some(message)),
+			None => self
+				.loopback
+				.communicator
+				.watch(selector, message),
+		})
+	}
+
+	fn put_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.put(selector, message)
+	}
+
+	fn delete_from(&self, session_id: &str, selector: &str) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.delete(selector)
+	}
+
+	fn get_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.get(selector, message, callback)
+	}
+
+	fn observe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.observe(selector, message)
+	}
+
+	fn watch_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.watch(selector, message)
+	}
+}
+
+impl LoopbackCommunicator {
+	/// Constructor
+	fn new(uuid: ZenohId, loopback: Loopback) -> Self {
+		Self {
+			uuid,
+			mode: MODE.to_string(),
+			state: OperationState::Created,
+			loopback,
+			liveliness_subscribers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			observers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			publishers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			queriers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			responders: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+		}
+	}
+
+	/// Check that `session_id` names the only session
+	fn check_session(session_id: &str) -> Result<()> {
+		if session_id == DEFAULT_SESSION {
+			Ok(())
+		} else {
+			Err(Error::NoCommunicator(session_id.into()).into())
+		}
+	}
+
+	/// Record a message and apply a matching fault.
+	/// Returns whether the message has to be sent.
+	fn transmit(
+		&self,
+		kind: TrafficKind,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<bool> {
+		let fault = self.loopback.take_fault(selector);
+		self.loopback.record(Traffic {
+			uuid: self.uuid(),
+			kind,
+			selector: selector.into(),
+			message,
+			fault,
+		});
+		match fault {
+			None => Ok(true),
+			Some(FaultKind::Delay(delay)) => {
+				blocking(|| std::thread::sleep(delay));
+				Ok(true)
+			}
+			Some(FaultKind::Drop) => Ok(false),
+			Some(FaultKind::Fail) => Err(Error::InjectedFault(selector.into()).into()),
+		}
+	}
+}
+
+/// Run the blocking `f` without stalling the tasks queued on the current tokio worker.
+/// Within a loopback the answering tasks are woken on the querying thread.
+fn blocking<R>(f: impl FnOnce() -> R) -> R {
+	match Handle::try_current() {
+		Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+			tokio::task::block_in_place(f)
+		}
+		_ => f(),
+	}
+}
+// endregion:	--- LoopbackCommunicator
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Instant;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Fault>();
+		is_normal::<Loopback>();
+		is_normal::<LoopbackCommunicator>();
+		is_normal::<Traffic>();
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn loopback_traffic() -> Result<()> {
+		let loopback = Loopback::new()?;
+		let sender = loopback.communicator();
+		let receiver = sender.share()?;
+		assert_ne!(sender.uuid(), receiver.uuid());
+		assert_eq!(
+			sender.default_session().zid(),
+			receiver.default_session().zid()
+		);
+		assert!(sender.session("other").is_none());
+
+		// a queryable of one communicator answers the query of the other one
+		let _queryable = receiver
+			.default_session()
+			.declare_queryable("loopback/test/query")
+			.callback(|query| {
+				let _ = zenoh::Wait::wait(query.reply(
+					query.key_expr().clone(),
+					Message::encode(&42_u32).value().clone(),
+				));
+			})
+			.await?;
+		sender.put("loopback/test/put", Message::encode(&1_u32))?;
+		sender.delete("loopback/test/put")?;
+		let mut answers = Vec::new();
+		let mut callback = |reply: QueryableMsg| -> Result<()> {
+			answers.push(reply.decode::<u32>()?);
+			Ok(())
+		};
+		sender.get("loopback/test/query", None, Some(&mut callback))?;
+		assert_eq!(answers, [42]);
+
+		let traffic = loopback.traffic();
+		let kinds: Vec<TrafficKind> = traffic
+			.iter()
+			.map(|traffic| traffic.kind)
+			.collect();
+		assert_eq!(
+			kinds,
+			[
+				TrafficKind::Put,
+				TrafficKind::Delete,
+				TrafficKind::Get,
+				TrafficKind::Reply
+			]
+		);
+		assert!(
+			traffic
+				.iter()
+				.all(|traffic| traffic.uuid == sender.uuid())
+		);
+		assert_eq!(
+			traffic[0]
+				.message
+				.clone()
+				.ok_or("no message")?
+				.decode::<u32>()?,
+			1
+		);
+		loopback.clear_traffic();
+		assert!(loopback.traffic().is_empty());
+		assert_eq!(loopback.recorded(), 4);
+
+		// positions continue after clearing
+		sender.delete("loopback/test/put")?;
+		let since = loopback.traffic_since(2);
+		assert_eq!(since.len(), 1);
+		assert_eq!(since[0].0, 4);
+		assert_eq!(since[0].1.kind, TrafficKind::Delete);
+		assert!(loopback.traffic_since(5).is_empty());
+		Ok(())
+	}
+
+	#[test]
+	fn loopback_attached() -> Result<()> {
+		let loopback = Loopback::new()?;
+		let metrics = Metrics::new();
+		let responders: Arc<RwLock<HashMap<String, Box<dyn Responder>>>> =
+			Arc::new(RwLock::new(HashMap::new()));
+		loopback.attach(metrics.clone(), &responders);
+		assert_eq!(loopback.handled(), 0);
+		metrics.observe(
+			CALLBACK_DURATION,
+			&[("kind", "subscriber"), ("topic", "loopback/test")],
+			Duration::from_millis(1),
+		);
+		metrics.observe(
+			CALLBACK_DURATION,
+			&[("kind", "timer"), ("topic", "loopback/test")],
+			Duration::from_millis(1),
+		);
+		assert_eq!(loopback.handled(), 1);
+		assert_eq!(loopback.deliveries("loopback/test"), 0);
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn loopback_faults() -> Result<()> {
+		let loopback = Loopback::new()?;
+		let com = loopback.communicator();
+		loopback.inject(Fault::fail("loopback/fail/**").times(1))?;
+		loopback.inject(Fault::drop("loopback/drop"))?;
+		loopback.inject(Fault::delay("loopback/delay", Duration::from_millis(50)))?;
+		assert!(
+			loopback
+				.inject(Fault::drop("loopback/**/**/"))
+				.is_err()
+		);
+
+		assert!(
+			com.put("loopback/fail/a", Message::encode(&1_u32))
+				.is_err()
+		);
+		// used up
+		com.put("loopback/fail/a", Message::encode(&1_u32))?;
+		com.put("loopback/drop", Message::encode(&1_u32))?;
+		let start = Instant::now();
+		com.put("loopback/delay", Message::encode(&1_u32))?;
+		assert!(start.elapsed() >= Duration::from_millis(50));
+
+		let faults: Vec<Option<FaultKind>> = loopback
+			.traffic()
+			.iter()
+			.map(|traffic| traffic.fault)
+			.collect();
+		assert_eq!(
+			faults,
+			[
+				Some(FaultKind::Fail),
+				None,
+				Some(FaultKind::Drop),
+				Some(FaultKind::Delay(Duration::from_millis(50)))
+			]
+		);
+
+		loopback.clear_faults();
+		com.put("loopback/drop", Message::encode(&1_u32))?;
+		assert!(
+			loopback
+				.traffic()
+				.last()
+				.is_some_and(|traffic| traffic.fault.is_none())
+		);
+		Ok(())
+	}
+}
@@ -3,12 +3,19 @@
 //! Enums for communication capabilities
 //!
 
+/// an in-memory loopback communicator for tests
+#[cfg(feature = "testing")]
+mod loopback_communicator;
 /// a multi session communicator
 mod multi_communicator;
 /// a single session communicator
 mod single_communicator;
 
 // flatten
+#[cfg(feature = "testing")]
+pub use loopback_communicator::{
+	Fault, FaultKind, Loopback, LoopbackCommunicator, Traffic, TrafficKind,
+};
 #[allow(clippy::module_name_repetitions)]
 pub use multi_communicator::MultiCommunicator;
 #[allow(clippy::module_name_repetitions)]
@@ -26,10 +33,13 @@ use alloc::sync::Arc;
 use dimas_config::Config;
 use dimas_core::Result;
 
+/// id of the default communication session
+pub const DEFAULT_SESSION: &str = "default";
+
 /// Create a [`Communicator`] from a [`Config`]
 /// # Errors
 pub fn from(config: &Config) -> Result<Arc<dyn Communicator>> {
-	if config.sessions().is_none() {
+	if config.sessions().is_none() && config.routes().is_none() {
 		Ok(Arc::new(SingleCommunicator::new(config)?))
 	} else {
 		Ok(Arc::new(MultiCommunicator::new(config)?))
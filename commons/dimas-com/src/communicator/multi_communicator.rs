@@ -29,14 +29,15 @@ use dimas_config::Config;
 use dimas_core::message_types::{Message, QueryableMsg};
 use dimas_core::{Result, enums::OperationState, traits::Capability};
 use std::{collections::HashMap, sync::RwLock};
-use zenoh::{Session, config::ZenohId};
+use tokio::sync::mpsc::Receiver;
+use zenoh::{Session, config::ZenohId, key_expr::KeyExpr};
+
+use super::DEFAULT_SESSION;
 // endregion:   --- modules
 
 // region:		--- types
 /// the initial size of the `HashMaps`
 const INITIAL_SIZE: usize = 9;
-/// id for default communication session
-const DEFAULT: &str = "default";
 // endregion:	--- types
 
 // region:      --- MultiCommunicator
@@ -51,6 +52,8 @@ pub struct MultiCommunicator {
 	state: OperationState,
 	/// Registered Communicators
 	communicators: Arc<RwLock<HashMap<String, Arc<CommunicatorImplementation>>>>,
+	/// Routes from key expressions to session ids
+	routes: Arc<Vec<(KeyExpr<'static>, String)>>,
 	/// Registered [`LivelinessSubscriber`]
 	liveliness_subscribers: Arc<RwLock<HashMap<String, Box<dyn LivelinessSubscriber>>>>,
 	/// Registered [`Observer`]
@@ -75,64 +78,50 @@ impl Capability for MultiCommunicator {
 }
 
 impl CommunicatorMethods for MultiCommunicator {
-	/// Send a put message [`Message`] to the given `selector`.
+	/// Send a put message [`Message`] to the given `selector`
+	/// using a registered publisher, its session or the routed session.
 	/// # Errors
-	/// - `NotImplemented`: there is no implementation within this communicator
+	/// - `NoCommunicator`: the routed session does not exist
 	fn put(&self, selector: &str, message: Message) -> Result<()> {
 		let publishers = self
 			.publishers
 			.read()
 			.map_err(|_| Error::ReadAccess("publishers".into()))?;
 
-		#[allow(clippy::single_match_else)]
 		match publishers.get(selector) {
-			Some(publisher) => publisher.put(message),
-			None => {
-				let comm = self
-					.communicators
-					.read()
-					.map_err(|_| Error::ReadAccess("publishers".into()))?
-					.get(DEFAULT)
-					.ok_or_else(|| Error::NoCommunicator(DEFAULT.into()))
-					.cloned()?;
-
-				comm.put(selector, message)
-			}
+			Some(publisher) => match publisher.session_id() {
+				Some(session_id) => self
+					.communicator(session_id)?
+					.put(selector, message),
+				None => publisher.put(message),
+			},
+			None => self.routed(selector)?.put(selector, message),
 		}
 	}
 
-	/// Send a delete message to the given `selector`.
+	/// Send a delete message to the given `selector`
+	/// using a registered publisher, its session or the routed session.
 	/// # Errors
-	/// - `NotImplemented`: there is no implementation within this communicator
+	/// - `NoCommunicator`: the routed session does not exist
 	fn delete(&self, selector: &str) -> Result<()> {
 		let publishers = self
 			.publishers
 			.read()
 			.map_err(|_| Error::ReadAccess("publishers".into()))?;
 
-		#[allow(clippy::single_match_else)]
 		match publishers.get(selector) {
-			Some(publisher) => publisher.delete(),
-			None => {
-				let comm = self
-					.communicators
-					.read()
-					.map_err(|_| Error::ReadAccess("publishers".into()))?
-					.get(DEFAULT)
-					.ok_or_else(|| Error::NoCommunicator(DEFAULT.into()))
-					.cloned()?;
-
-				#[allow(clippy::match_wildcard_for_single_variants)]
-				match comm.as_ref() {
-					CommunicatorImplementation::Zenoh(zenoh) => zenoh.delete(selector),
-				}
-			}
+			Some(publisher) => match publisher.session_id() {
+				Some(session_id) => self.communicator(session_id)?.delete(selector),
+				None => publisher.delete(),
+			},
+			None => self.routed(selector)?.delete(selector),
 		}
 	}
 
-	/// Send a query with an optional specification [`Message`] to the given `selector`.
+	/// Send a query with an optional specification [`Message`] to the given `selector`
+	/// using a registered querier or the routed session.
 	/// # Errors
-	/// - `NotImplemented`: there is no implementation within this communicator
+	/// - `NoCommunicator`: the routed session does not exist
 	fn get(
 		&self,
 		selector: &str,
@@ -147,27 +136,16 @@ impl CommunicatorMethods for MultiCommunicator {
 		#[allow(clippy::single_match_else)]
 		match queriers.get(selector) {
 			Some(querier) => querier.get(message, callback),
-			None => {
-				let comm = self
-					.communicators
-					.read()
-					.map_err(|_| Error::ReadAccess("queriers".into()))?
-					.get(DEFAULT)
-					.ok_or_else(|| Error::NoCommunicator(DEFAULT.into()))
-					.cloned()?;
-
-				match comm.as_ref() {
-					CommunicatorImplementation::Zenoh(zenoh) => {
-						zenoh.get(selector, message, callback)
-					}
-				}
-			}
+			None => self
+				.routed(selector)?
+				.get(selector, message, callback),
 		}
 	}
 
 	/// Request an observation for [`Message`] from the given `selector`
+	/// using a registered observer or the routed session.
 	/// # Errors
-	/// - `NotImplemented`: there is no implementation within this communicator
+	/// - `NoCommunicator`: the routed session does not exist
 	fn observe(&self, selector: &str, message: Option<Message>) -> Result<()> {
 		let observers = self
 			.observers
@@ -177,28 +155,90 @@ impl CommunicatorMethods for MultiCommunicator {
 		#[allow(clippy::single_match_else)]
 		match observers.get(selector) {
 			Some(observer) => observer.request(message),
-			None => {
-				let comm = self
-					.communicators
-					.read()
-					.map_err(|_| Error::ReadAccess("observers".into()))?
-					.get(DEFAULT)
-					.ok_or_else(|| Error::NoCommunicator(DEFAULT.into()))
-					.cloned()?;
-
-				#[allow(clippy::match_wildcard_for_single_variants)]
-				match comm.as_ref() {
-					CommunicatorImplementation::Zenoh(_zenoh) => Err(Error::NotImplemented.into()),
-				}
-			}
+			None => self.routed(selector)?.observe(selector, message),
 		}
 	}
 
 	/// Request a stream configured by [`Message`] from the given `selector`
+	/// using a registered observer or the routed session.
 	/// # Errors
-	/// - `NotImplemented`: there is no implementation within this communicator
-	fn watch(&self, _selector: &str, _message: Message) -> Result<()> {
-		Err(Error::NotImplemented.into())
+	/// - `NoCommunicator`: the routed session does not exist
+	fn watch(&self, selector: &str, message: Message) -> Result<()> {
+		let observers = self
+			.observers
+			.read()
+			.map_err(|_| Error::ReadAccess("observers".into()))?;
+
+		#[allow(clippy::single_match_else)]
+		match observers.get(selector) {
+			Some(observer) => observer.request(Some(message)),
+			None => self.routed(selector)?.watch(selector, message),
+		}
+	}
+
+	/// Send an ad-hoc put message [`Message`] via the session `session_id` to the given `selector`.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn put_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		self.communicator(session_id)?
+			.put(selector, message)
+	}
+
+	/// Send an ad-hoc delete message via the session `session_id` to the given `selector`.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn delete_from(&self, session_id: &str, selector: &str) -> Result<()> {
+		self.communicator(session_id)?.delete(selector)
+	}
+
+	/// Send an ad-hoc query with an optional specification [`Message`] via the session `session_id`
+	/// to the given `selector`.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn get_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		self.communicator(session_id)?
+			.get(selector, message, callback)
+	}
+
+	/// Request an ad-hoc observation for [`Message`] via the session `session_id`
+	/// from the given `selector`.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn observe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<()> {
+		self.communicator(session_id)?
+			.observe(selector, message)
+	}
+
+	/// Request an ad-hoc stream configured by [`Message`] via the session `session_id`
+	/// from the given `selector`.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn watch_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		self.communicator(session_id)?
+			.watch(selector, message)
+	}
+
+	/// Subscribe via the session `session_id` to the given `selector`,
+	/// used for sessions which are not a zenoh session.
+	/// # Errors
+	/// - `NoCommunicator`: the session does not exist
+	fn subscribe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+	) -> Result<Receiver<Option<Message>>> {
+		self.communicator(session_id)?.subscribe(selector)
 	}
 }
 
@@ -241,32 +281,13 @@ impl Communicator for MultiCommunicator {
 	}
 
 	fn default_session(&self) -> Arc<Session> {
-		let com = self
-			.communicators
-			.read()
-			.expect("snh")
-			.get(DEFAULT)
-			.cloned()
-			.expect("snh");
-		match com.as_ref() {
-			CommunicatorImplementation::Zenoh(communicator) => communicator.session(),
-		}
+		self.session(DEFAULT_SESSION).expect("snh")
 	}
 
 	fn session(&self, id: &str) -> Option<Arc<zenoh::Session>> {
-		let com = self
-			.communicators
-			.read()
-			.expect("snh")
-			.get(id)
-			.cloned()
-			.expect("snh");
-		match com.as_ref() {
-			CommunicatorImplementation::Zenoh(communicator) => {
-				let com = communicator.session();
-				Some(com)
-			}
-		}
+		self.communicator(id)
+			.ok()
+			.and_then(|com| com.session())
 	}
 
 	fn sessions(&self) -> Vec<Arc<Session>> {
@@ -274,13 +295,25 @@ impl Communicator for MultiCommunicator {
 			.communicators
 			.read()
 			.expect("snh")
-			.iter()
-			.map(|(_id, com)| match com.as_ref() {
-				CommunicatorImplementation::Zenoh(communicator) => communicator.session(),
-			})
+			.values()
+			.filter_map(|com| com.session())
 			.collect();
 		com
 	}
+
+	fn share(&self) -> Result<Arc<dyn Communicator>> {
+		let communicators = self
+			.communicators
+			.read()
+			.map_err(|_| Error::ReadAccess("communicators".into()))?
+			.clone();
+		Ok(Arc::new(Self::with_communicators(
+			ZenohId::default(),
+			self.mode.clone(),
+			communicators,
+			self.routes.clone(),
+		)))
+	}
 }
 
 impl MultiCommunicator {
@@ -290,38 +323,32 @@ impl MultiCommunicator {
 		let zenoh = crate::zenoh::Communicator::new(config.zenoh_config())?;
 		let uuid = zenoh.session().zid();
 		let mode = zenoh.mode().to_string();
-		let com = Self {
-			uuid,
-			mode,
-			state: OperationState::Created,
-			communicators: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-			liveliness_subscribers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-			observers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-			publishers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-			queriers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-			responders: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-		};
+		let mut communicators = HashMap::with_capacity(INITIAL_SIZE);
 		// add the default communicator
-		com.communicators
-			.write()
-			.map_err(|_| Error::ModifyStruct("commmunicators".into()))?
-			.insert(
-				"default".to_string(),
-				Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
-			);
+		communicators.insert(
+			DEFAULT_SESSION.to_string(),
+			Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+		);
 		// create the additional sessions
 		if let Some(sessions) = config.sessions() {
 			for session in sessions {
 				match session.protocol.as_str() {
 					"zenoh" => {
 						let zenoh = crate::zenoh::Communicator::new(&session.config)?;
-						com.communicators
-							.write()
-							.map_err(|_| Error::ModifyStruct("commmunicators".into()))?
-							.insert(
-								session.name.clone(),
-								Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
-							);
+						communicators.insert(
+							session.name.clone(),
+							Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+						);
+					}
+					#[cfg(feature = "mqtt")]
+					"mqtt" => {
+						let mqtt = crate::mqtt::Communicator::new(
+							&session.mqtt.clone().unwrap_or_default(),
+						)?;
+						communicators.insert(
+							session.name.clone(),
+							Arc::new(CommunicatorImplementation::Mqtt(mqtt)),
+						);
 					}
 					_ => {
 						return Err(Error::UnknownProtocol {
@@ -332,10 +359,84 @@ impl MultiCommunicator {
 				}
 			}
 		}
+		let routes = routes_from(config, &communicators)?;
+
+		Ok(Self::with_communicators(
+			uuid,
+			mode,
+			communicators,
+			Arc::new(routes),
+		))
+	}
 
-		Ok(com)
+	/// Get the communicator for the session `session_id`
+	fn communicator(&self, session_id: &str) -> Result<Arc<CommunicatorImplementation>> {
+		self.communicators
+			.read()
+			.map_err(|_| Error::ReadAccess("communicators".into()))?
+			.get(session_id)
+			.cloned()
+			.ok_or_else(|| Error::NoCommunicator(session_id.into()).into())
+	}
+
+	/// Get the id of the session `selector` is routed to
+	fn route(&self, selector: &str) -> &str {
+		let key = selector
+			.split_once('?')
+			.map_or(selector, |(key, _)| key);
+		KeyExpr::try_from(key)
+			.ok()
+			.and_then(|key_expr| {
+				self.routes
+					.iter()
+					.find(|(route, _)| route.includes(&key_expr))
+			})
+			.map_or(DEFAULT_SESSION, |(_, session_id)| session_id.as_str())
+	}
+
+	/// Get the communicator of the session `selector` is routed to
+	fn routed(&self, selector: &str) -> Result<Arc<CommunicatorImplementation>> {
+		self.communicator(self.route(selector))
+	}
+
+	/// Constructor using existing communicators
+	fn with_communicators(
+		uuid: ZenohId,
+		mode: String,
+		communicators: HashMap<String, Arc<CommunicatorImplementation>>,
+		routes: Arc<Vec<(KeyExpr<'static>, String)>>,
+	) -> Self {
+		Self {
+			uuid,
+			mode,
+			state: OperationState::Created,
+			communicators: Arc::new(RwLock::new(communicators)),
+			routes,
+			liveliness_subscribers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			observers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			publishers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			queriers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			responders: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+		}
 	}
 }
+
+/// Create the routes given in `config`, the routed sessions must be in `communicators`
+fn routes_from(
+	config: &Config,
+	communicators: &HashMap<String, Arc<CommunicatorImplementation>>,
+) -> Result<Vec<(KeyExpr<'static>, String)>> {
+	let mut routes = Vec::new();
+	for route in config.routes().iter().flatten() {
+		if !communicators.contains_key(&route.session) {
+			return Err(Error::NoCommunicator(route.session.clone()).into());
+		}
+		let key_expr = KeyExpr::try_from(route.selector.clone())
+			.map_err(|_| Error::InvalidSelector(route.selector.clone()))?;
+		routes.push((key_expr, route.session.clone()));
+	}
+	Ok(routes)
+}
 // endregion:   --- MultiCommunicator
 
 #[cfg(test)]
@@ -349,4 +450,131 @@ mod tests {
 	const fn normal_types() {
 		is_normal::<MultiCommunicator>();
 	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn routing() -> Result<()> {
+		let mut config = Config::default();
+		config.add_route("multi/test/field/**", "field");
+		config.add_route("multi/test/**", DEFAULT_SESSION);
+		let mut communicators = HashMap::new();
+		for id in [DEFAULT_SESSION, "field"] {
+			let zenoh = crate::zenoh::Communicator::new(config.zenoh_config())?;
+			communicators.insert(
+				id.to_string(),
+				Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+			);
+		}
+		let routes = routes_from(&config, &communicators)?;
+		let com = MultiCommunicator::with_communicators(
+			ZenohId::default(),
+			String::from("peer"),
+			communicators,
+			Arc::new(routes),
+		);
+
+		assert_eq!(com.route("multi/test/field/speed"), "field");
+		assert_eq!(com.route("multi/test/field/speed?param=1"), "field");
+		assert_eq!(com.route("multi/test/other"), DEFAULT_SESSION);
+		assert_eq!(com.route("other"), DEFAULT_SESSION);
+		assert_ne!(
+			com.session("field").map(|session| session.zid()),
+			Some(com.default_session().zid())
+		);
+		assert!(com.session("unknown").is_none());
+
+		com.put("multi/test/field/speed", Message::encode(&1_u32))?;
+		com.put_from("field", "multi/test/other", Message::encode(&1_u32))?;
+		assert!(
+			com.put_from("unknown", "multi/test/other", Message::encode(&1_u32))
+				.is_err()
+		);
+
+		config.add_route("multi/**", "unknown");
+		let communicators = com
+			.communicators
+			.read()
+			.map_err(|_| "poisoned")?
+			.clone();
+		assert!(routes_from(&config, &communicators).is_err());
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn share_sessions() -> Result<()> {
+		let com = MultiCommunicator::new(&Config::default())?;
+		let shared = com.share()?;
+		assert_ne!(com.uuid(), shared.uuid());
+		assert_eq!(com.default_session().zid(), shared.default_session().zid());
+
+		// a poisoned lock is reported instead of panicking
+		let communicators = com.communicators.clone();
+		let _ = std::thread::spawn(move || {
+			let _guard = communicators.write();
+			panic!("poisoning the communicators");
+		})
+		.join();
+		assert!(com.share().is_err());
+		Ok(())
+	}
+
+	#[cfg(feature = "mqtt")]
+	#[tokio::test(flavor = "multi_thread")]
+	async fn mqtt_publisher() -> Result<()> {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+		let mqtt = dimas_config::Mqtt {
+			host: "127.0.0.1".into(),
+			port: listener.local_addr()?.port(),
+			..dimas_config::Mqtt::default()
+		};
+		tokio::spawn(crate::mqtt::broker::broker(listener));
+		let config = Config::default();
+		let mut communicators = HashMap::new();
+		communicators.insert(
+			DEFAULT_SESSION.to_string(),
+			Arc::new(CommunicatorImplementation::Zenoh(
+				crate::zenoh::Communicator::new(config.zenoh_config())?,
+			)),
+		);
+		communicators.insert(
+			"iot".to_string(),
+			Arc::new(CommunicatorImplementation::Mqtt(
+				crate::mqtt::Communicator::new(&mqtt)?,
+			)),
+		);
+		let com = MultiCommunicator::with_communicators(
+			ZenohId::default(),
+			String::from("peer"),
+			communicators,
+			Arc::new(Vec::new()),
+		);
+		assert!(com.session("iot").is_none());
+
+		let selector = "multi/iot/setpoint";
+		com.publishers()
+			.write()
+			.map_err(|_| "poisoned")?
+			.insert(
+				selector.to_string(),
+				Box::new(crate::mqtt::publisher::Publisher::new(
+					"iot".into(),
+					selector.into(),
+					OperationState::Active,
+				)),
+			);
+		let mut receiver = com.subscribe_from("iot", "multi/iot/*")?;
+		// an inactive publisher does not send
+		assert!(
+			com.put(selector, Message::encode(&1_u32))
+				.is_err()
+		);
+
+		com.upgrade_capabilities(&OperationState::Active)?;
+		com.put(selector, Message::encode(&2_u32))?;
+		let message = tokio::time::timeout(core::time::Duration::from_secs(5), receiver.recv())
+			.await?
+			.flatten()
+			.ok_or("no message received")?;
+		assert_eq!(message.decode::<u32>()?, 2);
+		Ok(())
+	}
 }
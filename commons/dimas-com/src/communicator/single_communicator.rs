@@ -10,6 +10,7 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+use super::DEFAULT_SESSION;
 use crate::traits::LivelinessSubscriber;
 use crate::{
 	enums::CommunicatorImplementation,
@@ -26,7 +27,12 @@ use alloc::{
 	vec::Vec,
 };
 use dimas_config::Config;
-use dimas_core::{Result, enums::OperationState, message_types::Message, traits::Capability};
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	message_types::{Message, QueryableMsg},
+	traits::Capability,
+};
 use std::{collections::HashMap, sync::RwLock};
 use zenoh::{Session, config::ZenohId};
 // endregion:	--- modules
@@ -108,12 +114,12 @@ impl Communicator for SingleCommunicator {
 	}
 
 	fn default_session(&self) -> Arc<Session> {
-		self.communicator.session()
+		self.communicator.session().expect("snh")
 	}
 
 	fn session(&self, id: &str) -> Option<Arc<Session>> {
 		if id == "default" {
-			Some(self.communicator.session())
+			self.communicator.session()
 		} else {
 			None
 		}
@@ -122,9 +128,17 @@ impl Communicator for SingleCommunicator {
 	#[allow(clippy::vec_init_then_push)]
 	fn sessions(&self) -> Vec<Arc<Session>> {
 		let mut res = Vec::with_capacity(1);
-		res.push(self.communicator.session());
+		res.extend(self.communicator.session());
 		res
 	}
+
+	fn share(&self) -> Result<Arc<dyn Communicator>> {
+		Ok(Arc::new(Self::with_communicator(
+			ZenohId::default(),
+			self.mode.clone(),
+			self.communicator.clone(),
+		)))
+	}
 }
 
 impl CommunicatorMethods for SingleCommunicator {
@@ -137,9 +151,7 @@ impl CommunicatorMethods for SingleCommunicator {
 		#[allow(clippy::single_match_else)]
 		match publishers.get(selector) {
 			Some(publisher) => publisher.put(message),
-			None => match self.communicator.as_ref() {
-				CommunicatorImplementation::Zenoh(zenoh) => zenoh.put(selector, message),
-			},
+			None => self.communicator.put(selector, message),
 		}
 	}
 
@@ -152,9 +164,7 @@ impl CommunicatorMethods for SingleCommunicator {
 		#[allow(clippy::option_if_let_else)]
 		match publishers.get(selector) {
 			Some(publisher) => publisher.delete(),
-			None => match self.communicator.as_ref() {
-				CommunicatorImplementation::Zenoh(zenoh) => zenoh.delete(selector),
-			},
+			None => self.communicator.delete(selector),
 		}
 	}
 
@@ -172,15 +182,7 @@ impl CommunicatorMethods for SingleCommunicator {
 		#[allow(clippy::single_match_else)]
 		match queriers.get(selector) {
 			Some(querier) => querier.get(message, callback),
-			None =>
-			{
-				#[allow(clippy::match_wildcard_for_single_variants)]
-				match self.communicator.as_ref() {
-					CommunicatorImplementation::Zenoh(zenoh) => {
-						zenoh.get(selector, message, callback)
-					}
-				}
-			}
+			None => self.communicator.get(selector, message, callback),
 		}
 	}
 
@@ -197,12 +199,57 @@ impl CommunicatorMethods for SingleCommunicator {
 		#[allow(clippy::option_if_let_else)]
 		match observers.get(selector) {
 			Some(observer) => observer.request(message),
-			None => Err(crate::error::Error::NotImplemented.into()),
+			None => self.communicator.observe(selector, message),
 		}
 	}
 
-	fn watch(&self, _selector: &str, _message: dimas_core::message_types::Message) -> Result<()> {
-		Err(crate::error::Error::NotImplemented.into())
+	fn watch(&self, selector: &str, message: dimas_core::message_types::Message) -> Result<()> {
+		let observers = self
+			.observers
+			.read()
+			.map_err(|_| Error::ReadAccess("observers".into()))?;
+
+		#[allow(clippy::option_if_let_else)]
+		match observers.get(selector) {
+			Some(observer) => observer.request(Some(message)),
+			None => self.communicator.watch(selector, message),
+		}
+	}
+
+	fn put_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.put(selector, message)
+	}
+
+	fn delete_from(&self, session_id: &str, selector: &str) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.delete(selector)
+	}
+
+	fn get_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.get(selector, message, callback)
+	}
+
+	fn observe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.observe(selector, message)
+	}
+
+	fn watch_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		Self::check_session(session_id)?;
+		self.watch(selector, message)
 	}
 }
 
@@ -213,18 +260,39 @@ impl SingleCommunicator {
 		let zenoh = crate::zenoh::Communicator::new(config.zenoh_config())?;
 		let uuid = zenoh.session().zid();
 		let mode = zenoh.mode().to_string();
-		let com = Self {
+		Ok(Self::with_communicator(
 			uuid,
 			mode,
-			communicator: Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+			Arc::new(CommunicatorImplementation::Zenoh(zenoh)),
+		))
+	}
+
+	/// Check that `session_id` names the only session
+	fn check_session(session_id: &str) -> Result<()> {
+		if session_id == DEFAULT_SESSION {
+			Ok(())
+		} else {
+			Err(Error::NoCommunicator(session_id.into()).into())
+		}
+	}
+
+	/// Constructor using an existing communicator implementation
+	fn with_communicator(
+		uuid: ZenohId,
+		mode: String,
+		communicator: Arc<CommunicatorImplementation>,
+	) -> Self {
+		Self {
+			uuid,
+			mode,
+			communicator,
 			state: OperationState::Created,
 			liveliness_subscribers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
 			observers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
 			publishers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
 			queriers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
 			responders: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-		};
-		Ok(com)
+		}
 	}
 }
 // endregion:	--- SingleCommunicator
@@ -240,4 +308,25 @@ mod tests {
 	const fn normal_types() {
 		is_normal::<SingleCommunicator>();
 	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn share_session() -> Result<()> {
+		let com = SingleCommunicator::new(&Config::default())?;
+		let shared = com.share()?;
+		assert_ne!(com.uuid(), shared.uuid());
+		assert_eq!(com.default_session().zid(), shared.default_session().zid());
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn unknown_session() -> Result<()> {
+		let com = SingleCommunicator::new(&Config::default())?;
+		com.put_from(DEFAULT_SESSION, "single/test", Message::encode(&1_u32))?;
+		assert!(
+			com.put_from("other", "single/test", Message::encode(&1_u32))
+				.is_err()
+		);
+		assert!(com.delete_from("other", "single/test").is_err());
+		Ok(())
+	}
 }
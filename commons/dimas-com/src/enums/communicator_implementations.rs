@@ -4,6 +4,12 @@
 extern crate alloc;
 
 use alloc::sync::Arc;
+use dimas_core::{
+	Result,
+	message_types::{Message, QueryableMsg},
+};
+#[cfg(feature = "std")]
+use tokio::sync::mpsc::Receiver;
 use zenoh::Session;
 
 use crate::traits::CommunicatorImplementationMethods;
@@ -13,17 +19,75 @@ use crate::traits::CommunicatorImplementationMethods;
 pub enum CommunicatorImplementation {
 	/// zenoh
 	Zenoh(crate::zenoh::Communicator),
+	/// MQTT
+	#[cfg(feature = "mqtt")]
+	Mqtt(crate::mqtt::Communicator),
 }
 
-impl CommunicatorImplementationMethods for CommunicatorImplementation {}
+impl CommunicatorImplementationMethods for CommunicatorImplementation {
+	fn put(&self, selector: &str, message: Message) -> Result<()> {
+		match self {
+			Self::Zenoh(communicator) => communicator.put(selector, message),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.put(selector, message),
+		}
+	}
+
+	fn delete(&self, selector: &str) -> Result<()> {
+		match self {
+			Self::Zenoh(communicator) => communicator.delete(selector),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.delete(selector),
+		}
+	}
+
+	fn get(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		match self {
+			Self::Zenoh(communicator) => communicator.get(selector, message, callback),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.get(selector, message, callback),
+		}
+	}
+
+	fn observe(&self, selector: &str, message: Option<Message>) -> Result<()> {
+		match self {
+			Self::Zenoh(communicator) => communicator.observe(selector, message),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.observe(selector, message),
+		}
+	}
+
+	fn watch(&self, selector: &str, message: Message) -> Result<()> {
+		match self {
+			Self::Zenoh(communicator) => communicator.watch(selector, message),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.watch(selector, message),
+		}
+	}
+
+	#[cfg(feature = "std")]
+	fn subscribe(&self, selector: &str) -> Result<Receiver<Option<Message>>> {
+		match self {
+			Self::Zenoh(communicator) => communicator.subscribe(selector),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(communicator) => communicator.subscribe(selector),
+		}
+	}
+}
 
 impl CommunicatorImplementation {
-	/// extract session
+	/// extract session, only zenoh communicators have one
 	#[must_use]
-	#[allow(clippy::match_wildcard_for_single_variants)]
-	pub fn session(&self) -> Arc<Session> {
+	pub fn session(&self) -> Option<Arc<Session>> {
 		match self {
-			Self::Zenoh(communicator) => communicator.session(),
+			Self::Zenoh(communicator) => Some(communicator.session()),
+			#[cfg(feature = "mqtt")]
+			Self::Mqtt(_) => None,
 		}
 	}
 }
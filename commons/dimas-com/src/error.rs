@@ -32,6 +32,12 @@ pub enum Error {
 	/// No zenoh available/implemented
 	#[error("no zenoh session available")]
 	NoZenohSession,
+	/// A fault injected for tests
+	#[error("injected failure for '{0}'")]
+	InjectedFault(String),
+	/// Invalid bridge
+	#[error("a bridge needs two different sessions, got '{0}' twice")]
+	InvalidBridge(String),
 	/// Invalid selector
 	#[error("invalid selector for '{0}'")]
 	InvalidSelector(String),
@@ -53,6 +59,9 @@ pub enum Error {
 		/// the original zenoh error
 		source: Box<dyn core::error::Error + Send + Sync>,
 	},
+	/// Allocating a shared memory buffer failed
+	#[error("allocating shared memory failed with reason: {0}")]
+	SharedMemory(String),
 	/// Publishing a [`Message`] via `delete` failed
 	#[error("publishing a delete message failed with reason: {source}")]
 	PublishingDelete {
@@ -107,6 +116,12 @@ pub enum Error {
 		/// observables selector
 		selector: String,
 	},
+	/// No [`Observable`] accepted an observation request.
+	#[error("observation request for '{selector}' was not accepted")]
+	ObservationDeclined {
+		/// observables selector
+		selector: String,
+	},
 	/// Found unknown communication protocol
 	#[error("the protocol '{protocol}' is unknown")]
 	UnknownProtocol {
@@ -16,6 +16,9 @@ pub mod communicator;
 pub mod enums;
 /// Modules errors
 pub mod error;
+/// MQTT implementation
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
 /// `Communicator` trait
 pub mod traits;
 /// zenoh implementation
@@ -0,0 +1,115 @@
+// Copyright © 2025 Stephan Kunz
+
+//! A minimal MQTT broker for the tests of the MQTT communication.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bytes::BytesMut;
+use rumqttc::{
+	ConnAck, ConnectReturnCode, Packet, PubAck, Publish, QoS, SubAck, SubscribeReasonCode, UnsubAck,
+};
+use tokio::{
+	io::{AsyncReadExt, AsyncWriteExt},
+	net::{TcpListener, TcpStream},
+};
+// endregion:	--- modules
+
+/// the maximum size of a packet handled by the test broker
+const MAX_PACKET_SIZE: usize = 10 * 1024;
+
+/// A minimal MQTT 3.1.1 broker for a single connection,
+/// forwarding the publications to its subscriptions and keeping the retained messages
+pub async fn broker(listener: TcpListener) {
+	let mut retained: Vec<Publish> = Vec::new();
+	while let Ok((mut stream, _)) = listener.accept().await {
+		serve(&mut stream, &mut retained).await;
+	}
+}
+
+/// Serve the connection `stream` until it is closed
+async fn serve(stream: &mut TcpStream, retained: &mut Vec<Publish>) {
+	let mut filters: Vec<String> = Vec::new();
+	let mut incoming = BytesMut::new();
+	loop {
+		let packet = match Packet::read(&mut incoming, MAX_PACKET_SIZE) {
+			Ok(packet) => packet,
+			Err(rumqttc::Error::InsufficientBytes(_)) => {
+				match stream.read_buf(&mut incoming).await {
+					Ok(0) | Err(_) => return,
+					Ok(_) => continue,
+				}
+			}
+			Err(_) => return,
+		};
+		let mut answers = Vec::new();
+		match packet {
+			Packet::Connect(_) => answers.push(Packet::ConnAck(ConnAck::new(
+				ConnectReturnCode::Success,
+				false,
+			))),
+			Packet::Subscribe(subscribe) => {
+				let codes = subscribe
+					.filters
+					.iter()
+					.map(|_| SubscribeReasonCode::Success(QoS::AtMostOnce))
+					.collect();
+				answers.push(Packet::SubAck(SubAck::new(subscribe.pkid, codes)));
+				for filter in subscribe.filters {
+					for publish in retained
+						.iter()
+						.filter(|publish| rumqttc::matches(&publish.topic, &filter.path))
+					{
+						answers.push(Packet::Publish(publish.clone()));
+					}
+					if !filters.contains(&filter.path) {
+						filters.push(filter.path);
+					}
+				}
+			}
+			Packet::Unsubscribe(unsubscribe) => {
+				filters.retain(|filter| !unsubscribe.topics.contains(filter));
+				answers.push(Packet::UnsubAck(UnsubAck::new(unsubscribe.pkid)));
+			}
+			Packet::Publish(publish) => {
+				if publish.qos == QoS::AtLeastOnce {
+					answers.push(Packet::PubAck(PubAck::new(publish.pkid)));
+				}
+				let mut forward =
+					Publish::from_bytes(&publish.topic, QoS::AtMostOnce, publish.payload);
+				if publish.retain {
+					retained.retain(|message| message.topic != forward.topic);
+					if !forward.payload.is_empty() {
+						let mut message = forward.clone();
+						message.retain = true;
+						retained.push(message);
+					}
+				}
+				if filters
+					.iter()
+					.any(|filter| rumqttc::matches(&forward.topic, filter))
+				{
+					forward.retain = false;
+					answers.push(Packet::Publish(forward));
+				}
+			}
+			Packet::PingReq => answers.push(Packet::PingResp),
+			Packet::Disconnect => return,
+			_ => {}
+		}
+		let mut outgoing = BytesMut::new();
+		for answer in answers {
+			if answer
+				.write(&mut outgoing, MAX_PACKET_SIZE)
+				.is_err()
+			{
+				return;
+			}
+		}
+		if stream.write_all(&outgoing).await.is_err() {
+			return;
+		}
+	}
+}
@@ -0,0 +1,588 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Implements the MQTT communication capabilities using a broker.
+//!
+//! Key expressions are used as MQTT topics, the wildcards `*` and `**` of a selector
+//! are mapped to `+` and `#` of a topic filter.
+//! As MQTT has no queries, a `get` collects the messages retained by the broker
+//! and a `delete` clears the retained message of a topic.
+//! A subscription receives the messages published after subscribing,
+//! a message with empty payload, which clears a retained message, is passed as deletion.
+//! MQTT 3.1.1 has no user properties, so attachments of messages are not transmitted.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::{error::Error, traits::CommunicatorImplementationMethods};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
+use core::{
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
+use dimas_config::Mqtt;
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	message_types::{Message, QueryableMsg, SampleInfo},
+	traits::Capability,
+};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+#[cfg(feature = "std")]
+use std::sync::{
+	Mutex,
+	mpsc::{Receiver, Sender, channel},
+};
+use tokio::{
+	runtime::{Handle, RuntimeFlavor},
+	sync::mpsc,
+	task::JoinHandle,
+};
+use tracing::warn;
+use zenoh::{bytes::Encoding, config::ZenohId};
+// endregion:	--- modules
+
+// region:		--- types
+/// the capacity of the request channel to the event loop
+const CAPACITY: usize = 64;
+/// the time to wait for the first retained message of a `get`
+const GET_TIMEOUT: Duration = Duration::from_millis(1000);
+/// the time to wait for further retained messages of a `get`
+const GET_QUIET_TIME: Duration = Duration::from_millis(100);
+/// the time to wait before reconnecting to the broker
+const RECONNECT_DELAY: Duration = Duration::from_millis(1000);
+
+/// A running `get` collecting the retained messages for a topic filter
+struct Request {
+	id: u64,
+	filter: String,
+	sender: Sender<Vec<u8>>,
+}
+
+/// A subscription passing the published messages for a topic filter to a receiver
+struct Subscription {
+	filter: String,
+	sender: mpsc::Sender<Option<Message>>,
+}
+
+/// The running requests and subscriptions
+#[derive(Default)]
+struct Listeners {
+	requests: Vec<Request>,
+	subscriptions: Vec<Subscription>,
+}
+
+impl Listeners {
+	/// Whether a request or a subscription uses `filter`
+	fn uses(&self, filter: &str) -> bool {
+		self.requests
+			.iter()
+			.any(|request| request.filter == filter)
+			|| self
+				.subscriptions
+				.iter()
+				.any(|subscription| subscription.filter == filter)
+	}
+}
+
+/// Type definition for the shared listeners
+type SharedListeners = Arc<Mutex<Listeners>>;
+// endregion:	--- types
+
+// region:		--- Communicator
+/// [`Communicator`] handles the communication via an MQTT broker
+#[allow(clippy::module_name_repetitions)]
+pub struct Communicator {
+	/// The client connected to the broker
+	client: AsyncClient,
+	/// Whether put messages are retained by the broker
+	retain: bool,
+	/// The running requests and subscriptions
+	listeners: SharedListeners,
+	/// The next request id
+	next_id: AtomicU64,
+	/// The task polling the connection
+	handle: JoinHandle<()>,
+}
+
+impl core::fmt::Debug for Communicator {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Communicator")
+			.field("retain", &self.retain)
+			.finish_non_exhaustive()
+	}
+}
+
+impl Drop for Communicator {
+	fn drop(&mut self) {
+		self.handle.abort();
+	}
+}
+
+impl Capability for Communicator {
+	fn manage_operation_state(&self, _state: &OperationState) -> Result<()> {
+		Ok(())
+	}
+}
+
+impl CommunicatorImplementationMethods for Communicator {
+	/// Send a put message [`Message`] to the topic `selector`
+	/// # Errors
+	#[allow(clippy::needless_pass_by_value)]
+	fn put(&self, selector: &str, message: Message) -> Result<()> {
+		self.client
+			.try_publish(
+				topic(selector)?,
+				QoS::AtLeastOnce,
+				self.retain,
+				message.to_bytes().into_owned(),
+			)
+			.map_err(|source| {
+				Error::PublishingPut {
+					source: Box::new(source),
+				}
+				.into()
+			})
+	}
+
+	/// Clear the retained message of the topic `selector`
+	/// # Errors
+	fn delete(&self, selector: &str) -> Result<()> {
+		self.client
+			.try_publish(topic(selector)?, QoS::AtLeastOnce, true, Vec::new())
+			.map_err(|source| {
+				Error::PublishingDelete {
+					source: Box::new(source),
+				}
+				.into()
+			})
+	}
+
+	/// Collect the messages retained by the broker for `selector` via callback.
+	/// A query [`Message`] is not supported by MQTT.
+	/// # Errors
+	/// - `NotImplemented`: a query message or no callback is given
+	/// - `AccessingQueryable`: there is no retained message
+	fn get(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		if message.is_some() {
+			return Err(Error::NotImplemented.into());
+		}
+		let filter = topic_filter(selector)?;
+		let (sender, receiver) = channel();
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		self.listeners
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("MQTT listeners")))?
+			.requests
+			.push(Request {
+				id,
+				filter: filter.clone(),
+				sender,
+			});
+
+		let result = self
+			.client
+			.try_subscribe(filter.as_str(), QoS::AtLeastOnce)
+			.map_err(|source| {
+				Error::QueryCreation {
+					source: Box::new(source),
+				}
+				.into()
+			})
+			.and_then(|()| {
+				// the event loop may be queued on this worker, so hand its tasks over while waiting
+				match Handle::try_current() {
+					Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+						tokio::task::block_in_place(|| collect(selector, &receiver, callback))
+					}
+					_ => collect(selector, &receiver, callback),
+				}
+			});
+		self.finish(id, &filter)?;
+		result
+	}
+
+	/// Subscribe to the topic filter of `selector`.
+	/// The subscription ends with the first message after the receiver has been dropped.
+	/// # Errors
+	/// - `SubscriberCreation`: the subscription could not be requested
+	fn subscribe(&self, selector: &str) -> Result<mpsc::Receiver<Option<Message>>> {
+		let filter = topic_filter(selector)?;
+		let (sender, receiver) = mpsc::channel(CAPACITY);
+		self.listeners
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("MQTT listeners")))?
+			.subscriptions
+			.push(Subscription {
+				filter: filter.clone(),
+				sender,
+			});
+		self.client
+			.try_subscribe(filter, QoS::AtLeastOnce)
+			.map_err(|source| Error::SubscriberCreation {
+				source: Box::new(source),
+			})?;
+		Ok(receiver)
+	}
+}
+
+impl Communicator {
+	/// Constructor connecting to the broker given in `config`,
+	/// must be called within a tokio runtime
+	/// # Errors
+	pub fn new(config: &Mqtt) -> Result<Self> {
+		let runtime = Handle::try_current().map_err(|source| Error::CreateCommunicator {
+			source: Box::new(source),
+		})?;
+		let client_id = config.client_id.clone().unwrap_or_else(|| {
+			// MQTT 3.1.1 brokers have to accept client ids with up to 23 characters
+			let id: String = ZenohId::default()
+				.to_string()
+				.chars()
+				.take(16)
+				.collect();
+			["dimas-", &id].concat()
+		});
+		let mut options = MqttOptions::new(client_id, &config.host, config.port);
+		options.set_keep_alive(Duration::from_secs(config.keep_alive));
+		if let Some(username) = &config.username {
+			options.set_credentials(username, config.password.clone().unwrap_or_default());
+		}
+
+		let (client, mut eventloop) = AsyncClient::new(options, CAPACITY);
+		let listeners = SharedListeners::default();
+		let running = listeners.clone();
+		let connection = client.clone();
+		let handle = runtime.spawn(async move {
+			let mut reconnecting = false;
+			loop {
+				match eventloop.poll().await {
+					// the broker flags the messages it retained, which answer the requests
+					Ok(Event::Incoming(Packet::Publish(publish))) if publish.retain => {
+						dispatch(&running, &publish.topic, &publish.payload);
+					}
+					Ok(Event::Incoming(Packet::Publish(publish))) => {
+						deliver(&running, &connection, &publish.topic, &publish.payload);
+					}
+					// the broker does not keep the subscriptions of a clean session
+					Ok(Event::Incoming(Packet::ConnAck(_))) if reconnecting => {
+						reconnecting = false;
+						resubscribe(&running, &connection);
+					}
+					Ok(_) => {}
+					Err(error) => {
+						warn!("MQTT connection failed with {error}");
+						reconnecting = true;
+						// the next poll reconnects
+						tokio::time::sleep(RECONNECT_DELAY).await;
+					}
+				}
+			}
+		});
+
+		Ok(Self {
+			client,
+			retain: config.retain,
+			listeners,
+			next_id: AtomicU64::new(0),
+			handle,
+		})
+	}
+
+	/// Remove the request `id` and unsubscribe `filter` if no other request uses it
+	fn finish(&self, id: u64, filter: &str) -> Result<()> {
+		let mut listeners = self
+			.listeners
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("MQTT listeners")))?;
+		listeners
+			.requests
+			.retain(|request| request.id != id);
+		if !listeners.uses(filter) {
+			drop(listeners);
+			if let Err(error) = self.client.try_unsubscribe(filter) {
+				warn!("unsubscribing '{filter}' failed with {error}");
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Hand a retained message to all running requests matching `topic`
+fn dispatch(listeners: &SharedListeners, topic: &str, payload: &[u8]) {
+	if payload.is_empty() {
+		return;
+	}
+	if let Ok(listeners) = listeners.lock() {
+		for request in listeners
+			.requests
+			.iter()
+			.filter(|request| rumqttc::matches(topic, &request.filter))
+		{
+			// the request may have finished meanwhile
+			let _ = request.sender.send(payload.to_vec());
+		}
+	}
+}
+
+/// Hand a published message to all subscriptions matching `topic`,
+/// removing the subscriptions whose receiver has been dropped
+fn deliver(listeners: &SharedListeners, client: &AsyncClient, topic: &str, payload: &[u8]) {
+	let Ok(mut listeners) = listeners.lock() else {
+		return;
+	};
+	let sample = (!payload.is_empty()).then(|| {
+		Message::new(payload.to_vec()).with_info(SampleInfo {
+			key_expr: topic.to_string(),
+			timestamp: None,
+			source_id: None,
+			sequence_number: None,
+			encoding: Encoding::default().to_string(),
+			attachment: None,
+		})
+	});
+	let mut ended = Vec::new();
+	listeners.subscriptions.retain(|subscription| {
+		if !rumqttc::matches(topic, &subscription.filter) {
+			return true;
+		}
+		match subscription.sender.try_send(sample.clone()) {
+			Ok(()) => true,
+			Err(mpsc::error::TrySendError::Full(_)) => {
+				warn!(
+					"subscription for '{}' is lagging, dropped a message",
+					subscription.filter
+				);
+				true
+			}
+			Err(mpsc::error::TrySendError::Closed(_)) => {
+				ended.push(subscription.filter.clone());
+				false
+			}
+		}
+	});
+	for filter in ended {
+		if !listeners.uses(&filter) {
+			if let Err(error) = client.try_unsubscribe(filter.as_str()) {
+				warn!("unsubscribing '{filter}' failed with {error}");
+			}
+		}
+	}
+}
+
+/// Renew the subscriptions after a (re)connection
+fn resubscribe(listeners: &SharedListeners, client: &AsyncClient) {
+	if let Ok(listeners) = listeners.lock() {
+		for subscription in &listeners.subscriptions {
+			if let Err(error) = client.try_subscribe(subscription.filter.as_str(), QoS::AtLeastOnce)
+			{
+				warn!("subscribing '{}' failed with {error}", subscription.filter);
+			}
+		}
+	}
+}
+
+/// Pass the received retained messages to `callback` until no further message arrives
+fn collect(
+	selector: &str,
+	receiver: &Receiver<Vec<u8>>,
+	mut callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+) -> Result<()> {
+	let mut timeout = GET_TIMEOUT;
+	let mut answered = false;
+	while let Ok(content) = receiver.recv_timeout(timeout) {
+		answered = true;
+		timeout = GET_QUIET_TIME;
+		callback.as_deref_mut().map_or_else(
+			|| Err(Error::NotImplemented),
+			|callback| {
+				callback(QueryableMsg::new(content))
+					.map_err(|source| Error::QueryCallback { source })
+			},
+		)?;
+	}
+	if answered {
+		Ok(())
+	} else {
+		Err(Error::AccessingQueryable {
+			selector: selector.to_string(),
+		}
+		.into())
+	}
+}
+
+/// Check that `selector` is a topic without wildcards
+fn topic(selector: &str) -> Result<&str> {
+	if rumqttc::valid_topic(selector) && !selector.contains('*') {
+		Ok(selector)
+	} else {
+		Err(Error::InvalidSelector(selector.to_string()).into())
+	}
+}
+
+/// Convert the key expression of `selector` into an MQTT topic filter
+fn topic_filter(selector: &str) -> Result<String> {
+	let key = selector
+		.split_once('?')
+		.map_or(selector, |(key, _)| key);
+	let filter = key
+		.split('/')
+		.map(|chunk| match chunk {
+			"**" => "#",
+			"*" => "+",
+			chunk => chunk,
+		})
+		.collect::<Vec<_>>()
+		.join("/");
+	if rumqttc::valid_filter(&filter) {
+		Ok(filter)
+	} else {
+		Err(Error::InvalidSelector(selector.to_string()).into())
+	}
+}
+// endregion:	--- Communicator
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mqtt::broker::broker;
+	use tokio::{net::TcpListener, time::timeout};
+
+	/// the time to wait for a message at a subscription
+	const RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+	/// Receive the next message of a subscription
+	async fn receive(receiver: &mut mpsc::Receiver<Option<Message>>) -> Option<Message> {
+		timeout(RECEIVE_TIMEOUT, receiver.recv())
+			.await
+			.expect("no message received")
+			.expect("subscription ended")
+	}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Communicator>();
+	}
+
+	#[test]
+	fn topics() {
+		assert!(topic("robot/telemetry/speed").is_ok());
+		assert!(topic("robot/*/speed").is_err());
+		assert!(topic("robot/#").is_err());
+	}
+
+	#[test]
+	fn filters() -> Result<()> {
+		assert_eq!(
+			topic_filter("robot/telemetry/speed")?,
+			"robot/telemetry/speed"
+		);
+		assert_eq!(topic_filter("robot/*/speed")?, "robot/+/speed");
+		assert_eq!(topic_filter("robot/**")?, "robot/#");
+		assert_eq!(topic_filter("robot/**?param=1")?, "robot/#");
+		assert!(topic_filter("robot/**/speed").is_err());
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn without_broker() -> Result<()> {
+		let config = Mqtt {
+			// nothing is expected to listen at the discard port
+			port: 9,
+			..Mqtt::default()
+		};
+		let com = Communicator::new(&config)?;
+		// messages are queued until the broker is reachable
+		com.put("mqtt/test/speed", Message::encode(&1_u32))?;
+		com.delete("mqtt/test/speed")?;
+		assert!(
+			com.put("mqtt/test/*", Message::encode(&1_u32))
+				.is_err()
+		);
+		let mut callback = |_: QueryableMsg| -> Result<()> { Ok(()) };
+		assert!(
+			com.get("mqtt/test/*", None, Some(&mut callback))
+				.is_err()
+		);
+		assert!(
+			com.get(
+				"mqtt/test/*",
+				Some(Message::encode(&1_u32)),
+				Some(&mut callback)
+			)
+			.is_err()
+		);
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn with_broker() -> Result<()> {
+		let listener = TcpListener::bind("127.0.0.1:0").await?;
+		let config = Mqtt {
+			host: "127.0.0.1".into(),
+			port: listener.local_addr()?.port(),
+			retain: true,
+			..Mqtt::default()
+		};
+		tokio::spawn(broker(listener));
+		let com = Communicator::new(&config)?;
+
+		// the subscription is requested before the publication on the same connection
+		let mut receiver = com.subscribe("mqtt/*/speed")?;
+		com.put("mqtt/left/speed", Message::encode(&7_u32))?;
+		let message = receive(&mut receiver)
+			.await
+			.expect("put expected");
+		assert_eq!(
+			message.info().map(|info| info.key_expr.as_str()),
+			Some("mqtt/left/speed")
+		);
+		assert_eq!(message.decode::<u32>()?, 7);
+
+		// the broker retained the message
+		let mut values = Vec::new();
+		let mut callback = |response: QueryableMsg| -> Result<()> {
+			values.push(response.decode::<u32>()?);
+			Ok(())
+		};
+		com.get("mqtt/**", None, Some(&mut callback))?;
+		assert_eq!(values, [7]);
+
+		// a delete clears the retained message and is passed to the subscription
+		com.delete("mqtt/left/speed")?;
+		assert!(receive(&mut receiver).await.is_none());
+		let mut callback = |_: QueryableMsg| -> Result<()> { Ok(()) };
+		assert!(
+			com.get("mqtt/**", None, Some(&mut callback))
+				.is_err()
+		);
+
+		// a subscription with a dropped receiver ends with the next message
+		let mut right = com.subscribe("mqtt/right/#")?;
+		drop(receiver);
+		com.put("mqtt/right/speed", Message::encode(&3_u32))?;
+		let message = receive(&mut right).await.expect("put expected");
+		assert_eq!(message.decode::<u32>()?, 3);
+		let listeners = com.listeners.lock().expect("snh");
+		assert_eq!(listeners.subscriptions.len(), 1);
+		assert!(!listeners.uses("mqtt/+/speed"));
+		drop(listeners);
+		Ok(())
+	}
+}
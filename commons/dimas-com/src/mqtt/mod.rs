@@ -0,0 +1,20 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Implements the MQTT communication capabilities.
+
+// region:		--- modules
+/// test broker
+#[cfg(test)]
+pub(crate) mod broker;
+/// MQTT communicator implementation
+pub mod communicator;
+/// MQTT publisher implementation
+pub mod publisher;
+/// MQTT subscriber implementation
+#[cfg(feature = "std")]
+pub mod subscriber;
+
+// flatten
+#[allow(clippy::module_name_repetitions)]
+pub use communicator::Communicator;
+// endregion:	--- modules
@@ -0,0 +1,128 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `publisher` provides a message sender `Publisher` for MQTT sessions,
+//! which is created by the `PublisherBuilder` for sessions without a zenoh session.
+//!
+//! While active, the messages are sent by the communicator via the publishers session,
+//! the zenoh specific settings of the builder are not used.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::error::Error;
+use alloc::string::String;
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicBool, Ordering},
+};
+use dimas_core::{Result, enums::OperationState, message_types::Message, traits::Capability};
+// endregion:	--- modules
+
+// region:		--- Publisher
+/// Publisher for MQTT sessions
+pub struct Publisher {
+	/// the id of the session this publisher belongs to
+	session_id: String,
+	selector: String,
+	/// [`OperationState`] on which this publisher is active
+	activation_state: OperationState,
+	/// whether the publisher is active
+	active: AtomicBool,
+}
+
+impl Debug for Publisher {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Publisher")
+			.field("session_id", &self.session_id)
+			.field("selector", &self.selector)
+			.field("active", &self.active)
+			.finish_non_exhaustive()
+	}
+}
+
+impl crate::traits::Publisher for Publisher {
+	/// Get `selector`
+	fn selector(&self) -> &str {
+		&self.selector
+	}
+
+	/// Get the id of the session to send the messages via, `None` while inactive
+	fn session_id(&self) -> Option<&str> {
+		self.active
+			.load(Ordering::Relaxed)
+			.then_some(self.session_id.as_str())
+	}
+
+	/// The messages are sent via the session
+	/// # Errors
+	/// - `AccessPublisher`: the publisher is inactive
+	fn put(&self, _message: Message) -> Result<()> {
+		Err(Error::AccessPublisher.into())
+	}
+
+	/// The messages are sent via the session
+	/// # Errors
+	/// - `AccessPublisher`: the publisher is inactive
+	fn delete(&self) -> Result<()> {
+		Err(Error::AccessPublisher.into())
+	}
+}
+
+impl Capability for Publisher {
+	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
+		self.active
+			.store(state >= &self.activation_state, Ordering::Relaxed);
+		Ok(())
+	}
+}
+
+impl Publisher {
+	/// Constructor for a [`Publisher`]
+	#[must_use]
+	pub const fn new(
+		session_id: String,
+		selector: String,
+		activation_state: OperationState,
+	) -> Self {
+		Self {
+			session_id,
+			selector,
+			activation_state,
+			active: AtomicBool::new(false),
+		}
+	}
+}
+// endregion:	--- Publisher
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::traits::Publisher as _;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Publisher>();
+	}
+
+	#[test]
+	fn activation() -> Result<()> {
+		let publisher = Publisher::new(
+			"iot".into(),
+			"robot/iot/setpoint".into(),
+			OperationState::Active,
+		);
+		assert_eq!(publisher.session_id(), None);
+		assert!(publisher.put(Message::encode(&1_u32)).is_err());
+
+		publisher.manage_operation_state(&OperationState::Active)?;
+		assert_eq!(publisher.session_id(), Some("iot"));
+
+		publisher.manage_operation_state(&OperationState::Standby)?;
+		assert_eq!(publisher.session_id(), None);
+		Ok(())
+	}
+}
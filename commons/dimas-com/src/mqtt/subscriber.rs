@@ -0,0 +1,238 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `subscriber` provides a message `Subscriber` for MQTT sessions,
+//! which is created by the `SubscriberBuilder` for sessions without a zenoh session.
+//!
+//! The subscription is done via the session of the `Agent`s context.
+//! A message with empty payload, which clears a retained message, calls the delete callback.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::{
+	error::Error,
+	zenoh::subscriber::{ArcDeleteCallback, ArcPutCallback},
+};
+use alloc::{boxed::Box, string::String};
+use dimas_core::{
+	Result,
+	enums::{OperationState, TaskSignal},
+	message_types::Message,
+	metrics::{BYTES_RECEIVED, CALLBACK_DURATION, MESSAGES_RECEIVED},
+	traits::{Capability, Context},
+};
+#[cfg(feature = "std")]
+use std::time::Instant;
+#[cfg(feature = "std")]
+use tokio::{sync::mpsc::Receiver, task::JoinHandle};
+use tracing::{Instrument, Level, error, info, instrument, span};
+// endregion:	--- modules
+
+// region:		--- Subscriber
+/// Subscriber for MQTT sessions
+pub struct Subscriber<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// the id of the session this subscriber belongs to
+	session_id: String,
+	/// The subscribers key expression
+	selector: String,
+	/// Context for the Subscriber
+	context: Context<P>,
+	/// [`OperationState`] on which this subscriber is started
+	activation_state: OperationState,
+	put_callback: ArcPutCallback<P>,
+	delete_callback: Option<ArcDeleteCallback<P>>,
+	handle: std::sync::Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<P> core::fmt::Debug for Subscriber<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Subscriber")
+			.field("session_id", &self.session_id)
+			.field("selector", &self.selector)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> crate::traits::Responder for Subscriber<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Get `selector`
+	fn selector(&self) -> &str {
+		&self.selector
+	}
+
+	/// Whether the subscribers task is running
+	fn is_subscribed(&self) -> bool {
+		self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		})
+	}
+}
+
+impl<P> Capability for Subscriber<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
+		if state >= &self.activation_state {
+			self.start()
+		} else if state < &self.activation_state {
+			self.stop()
+		} else {
+			Ok(())
+		}
+	}
+}
+
+impl<P> Subscriber<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Constructor for a [`Subscriber`].
+	#[must_use]
+	pub fn new(
+		session_id: String,
+		selector: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		put_callback: ArcPutCallback<P>,
+		delete_callback: Option<ArcDeleteCallback<P>>,
+	) -> Self {
+		Self {
+			session_id,
+			selector,
+			context,
+			activation_state,
+			put_callback,
+			delete_callback,
+			handle: std::sync::Mutex::new(None),
+		}
+	}
+
+	/// Start or restart the subscriber.
+	/// An already running subscriber will be stopped.
+	#[instrument(level = Level::TRACE, skip_all)]
+	fn start(&self) -> Result<()> {
+		self.stop()?;
+
+		let receiver = self
+			.context
+			.subscribe_from(&self.session_id, &self.selector)?;
+		let selector = self.selector.clone();
+		let p_cb = self.put_callback.clone();
+		let d_cb = self.delete_callback.clone();
+		let ctx1 = self.context.clone();
+		let ctx2 = self.context.clone();
+
+		self.handle
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("Subscriber")))?
+			.replace(tokio::task::spawn(
+				async move {
+					let key = selector.clone();
+					std::panic::set_hook(Box::new(move |reason| {
+						error!("subscriber panic: {}", reason);
+						if let Err(reason) = ctx1
+							.sender()
+							.try_send(TaskSignal::RestartSubscriber(key.clone()))
+						{
+							error!("could not restart subscriber: {}", reason);
+						} else {
+							info!("restarting subscriber!");
+						};
+					}));
+					run_subscriber(receiver, selector, p_cb, d_cb, ctx2).await;
+				}
+				.in_current_span(),
+			));
+		Ok(())
+	}
+
+	/// Stop a running Subscriber, which ends its subscription
+	#[instrument(level = Level::TRACE, skip_all)]
+	fn stop(&self) -> Result<()> {
+		let handle = self
+			.handle
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("Subscriber")))?
+			.take();
+		if let Some(handle) = handle {
+			handle.abort();
+		}
+		Ok(())
+	}
+}
+
+#[instrument(name="subscriber", level = Level::ERROR, skip_all)]
+async fn run_subscriber<P>(
+	mut receiver: Receiver<Option<Message>>,
+	selector: String,
+	p_cb: ArcPutCallback<P>,
+	d_cb: Option<ArcDeleteCallback<P>>,
+	ctx: Context<P>,
+) where
+	P: Send + Sync + 'static,
+{
+	while let Some(sample) = receiver.recv().await {
+		match sample {
+			Some(msg) => {
+				let span = span!(Level::ERROR, "put", %selector);
+				let labels = [("topic", selector.as_str())];
+				ctx.metrics()
+					.increment(MESSAGES_RECEIVED, &labels, 1);
+				ctx.metrics()
+					.increment(BYTES_RECEIVED, &labels, msg.value().len() as u64);
+				let mut lock = p_cb.lock().await;
+				let start = Instant::now();
+				if let Err(error) = lock(ctx.clone(), msg).instrument(span).await {
+					error!("subscriber put callback failed with {error}");
+				}
+				drop(lock);
+				ctx.metrics().observe(
+					CALLBACK_DURATION,
+					&[("kind", "subscriber"), ("topic", &selector)],
+					start.elapsed(),
+				);
+			}
+			None => {
+				if let Some(cb) = d_cb.clone() {
+					let ctx = ctx.clone();
+					let mut lock = cb.lock().await;
+					if let Err(error) = lock(ctx).await {
+						error!("subscriber delete callback failed with {error}");
+					}
+				}
+			}
+		}
+	}
+}
+// endregion:	--- Subscriber
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug)]
+	struct Props {}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Subscriber<Props>>();
+	}
+}
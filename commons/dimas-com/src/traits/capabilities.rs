@@ -41,6 +41,13 @@ pub trait Publisher: Capability + Send + Sync {
 	#[must_use]
 	fn selector(&self) -> &str;
 
+	/// Get the id of the session the communicator sends the messages via,
+	/// `None` if the publisher sends them itself
+	#[must_use]
+	fn session_id(&self) -> Option<&str> {
+		None
+	}
+
 	/// Send a "put" message
 	/// # Errors
 	fn put(&self, message: Message) -> Result<()>;
@@ -70,5 +77,11 @@ pub trait Responder: Capability + Send + Sync {
 	/// Get `selector`
 	#[must_use]
 	fn selector(&self) -> &str;
+
+	/// Whether the responder is a running subscriber, receiving put and delete messages
+	#[must_use]
+	fn is_subscribed(&self) -> bool {
+		false
+	}
 }
 // endregion:	--- capabilities
@@ -207,4 +207,10 @@ pub trait Communicator: Capability + CommunicatorMethods + Send + Sync {
 
 	/// get all sessions
 	fn sessions(&self) -> Vec<Arc<Session>>;
+
+	/// Create a communicator with an own uuid and own registered capabilities,
+	/// which shares the sessions with this communicator
+	/// # Errors
+	/// if the sessions are not accessible
+	fn share(&self) -> Result<Arc<dyn Communicator>>;
 }
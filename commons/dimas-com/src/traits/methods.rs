@@ -9,6 +9,8 @@ use dimas_core::{
 	error::Result,
 	message_types::{Message, QueryableMsg},
 };
+#[cfg(feature = "std")]
+use tokio::sync::mpsc::Receiver;
 // endregion:   --- modules
 
 // region:		--- CommunicatorMethods
@@ -55,22 +57,21 @@ pub trait CommunicatorMethods {
 		Err(Error::NotImplemented.into())
 	}
 
-	/* not used currently
-	/// Send a put message [`Message`] from the given `session` to the given `selector`.
+	/// Send a put message [`Message`] via the session `session_id` to the given `selector`.
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
 	fn put_from(&self, _session_id: &str, _selector: &str, _message: Message) -> Result<()> {
 		Err(Error::NotImplemented.into())
 	}
 
-	/// Send a delete message from the given `session` to the given `selector`.
+	/// Send a delete message via the session `session_id` to the given `selector`.
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
 	fn delete_from(&self, _session_id: &str, _selector: &str) -> Result<()> {
 		Err(Error::NotImplemented.into())
 	}
 
-	/// Send a query with an optional specification [`Message`] from the given `session` to the given `selector`.
+	/// Send a query with an optional specification [`Message`] via the session `session_id` to the given `selector`.
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
 	fn get_from(
@@ -83,25 +84,37 @@ pub trait CommunicatorMethods {
 		Err(Error::NotImplemented.into())
 	}
 
-	/// Request an observation for [`Message`] from the given `session` from the given `selector`
+	/// Request an observation for [`Message`] via the session `session_id` from the given `selector`
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
 	fn observe_from(
 		&self,
-		_session: &str,
+		_session_id: &str,
 		_selector: &str,
 		_message: Option<Message>,
 	) -> Result<()> {
 		Err(Error::NotImplemented.into())
 	}
 
-	/// Request a stream configured by [`Message`] from the given `session` from the given `selector`
+	/// Request a stream configured by [`Message`] via the session `session_id` from the given `selector`
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
-	fn watch_from(&self, _session: &str, _selector: &str, _message: Message) -> Result<()> {
+	fn watch_from(&self, _session_id: &str, _selector: &str, _message: Message) -> Result<()> {
+		Err(Error::NotImplemented.into())
+	}
+
+	/// Subscribe via the session `session_id` to the given `selector`.
+	/// The received messages are passed to the returned receiver, a `None` stands for a deletion.
+	/// # Errors
+	/// - `NotImplemented`: there is no implementation within this communicator
+	#[cfg(feature = "std")]
+	fn subscribe_from(
+		&self,
+		_session_id: &str,
+		_selector: &str,
+	) -> Result<Receiver<Option<Message>>> {
 		Err(Error::NotImplemented.into())
 	}
-	*/
 }
 // endregion:	--- CommunicatorMethods
 
@@ -148,5 +161,14 @@ pub trait CommunicatorImplementationMethods {
 	fn watch(&self, _selector: &str, _message: Message) -> Result<()> {
 		Err(Error::NotImplemented.into())
 	}
+
+	/// Subscribe to the given `selector`.
+	/// The received messages are passed to the returned receiver, a `None` stands for a deletion.
+	/// # Errors
+	/// - `NotImplemented`: there is no implementation within this communicator
+	#[cfg(feature = "std")]
+	fn subscribe(&self, _selector: &str) -> Result<Receiver<Option<Message>>> {
+		Err(Error::NotImplemented.into())
+	}
 }
 // endregion:	---CommunicatorImplementation
@@ -0,0 +1,640 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `bridge` provides a `Bridge` forwarding messages between two sessions,
+//! which can be created using the `BridgeBuilder`.
+//!
+//! The key expressions can be remapped and the forwarding can be rate limited per key expression.
+//! Messages forwarded by a bridge carry the ids of the sessions they have visited
+//! in their [`Attachments`] under the key [`BRIDGE_KEY`],
+//! so neither a bridge working in both directions nor an opposing bridge sends them back.
+//! The other entries of the [`Attachments`] are forwarded unchanged.
+//! An attachment in another format is forwarded unchanged too, but can not carry the visited sessions,
+//! so bridges must not forward such messages in opposing directions.
+//!
+//! A bridge is registered for the intra-process delivery of its source session,
+//! so it also forwards the messages of publishers using intra-process delivery.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use super::intra_process::{self, LocalSample};
+use crate::error::Error;
+use alloc::{
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
+use core::time::Duration;
+use dimas_core::{Result, enums::OperationState, message_types::Attachments, traits::Capability};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, time::Instant};
+#[cfg(feature = "std")]
+use tokio::task::JoinHandle;
+use tracing::{Instrument, Level, error, instrument, warn};
+use zenoh::{
+	Session,
+	bytes::{Encoding, ZBytes},
+	config::ZenohId,
+	sample::{Sample, SampleKind},
+};
+// endregion:	--- modules
+
+// region:		--- types
+/// The key of the [`Attachments`] entry holding the comma separated ids of the sessions
+/// a forwarded message has visited
+pub const BRIDGE_KEY: &str = "dimas-bridge";
+// endregion:	--- types
+
+// region:		--- BridgeDirection
+/// The direction of a [`Bridge`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BridgeDirection {
+	/// Forward messages from the source to the target session
+	#[default]
+	Forward,
+	/// Forward messages from the target to the source session
+	Backward,
+	/// Forward messages in both directions
+	Both,
+}
+// endregion:	--- BridgeDirection
+
+// region:		--- Remapping
+/// Remapping of key expressions by replacing a leading part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remapping {
+	/// the leading part of key expressions in the source session
+	pub from: String,
+	/// the replacement in the target session
+	pub to: String,
+}
+
+impl Remapping {
+	/// Map a key expression from the source to the target session
+	#[must_use]
+	pub fn forward(&self, key_expr: &str) -> String {
+		replace_prefix(key_expr, &self.from, &self.to)
+	}
+
+	/// Map a key expression from the target to the source session
+	#[must_use]
+	pub fn backward(&self, key_expr: &str) -> String {
+		replace_prefix(key_expr, &self.to, &self.from)
+	}
+}
+
+/// Replace the leading chunks `prefix` of `key_expr` with `replacement`
+fn replace_prefix(key_expr: &str, prefix: &str, replacement: &str) -> String {
+	let rest = if prefix.is_empty() {
+		Some(key_expr)
+	} else if key_expr == prefix {
+		Some("")
+	} else {
+		key_expr
+			.strip_prefix(prefix)
+			.and_then(|rest| rest.strip_prefix('/'))
+	};
+	match rest {
+		Some("") => replacement.to_string(),
+		Some(rest) if replacement.is_empty() => rest.to_string(),
+		Some(rest) => [replacement, rest].join("/"),
+		None => key_expr.to_string(),
+	}
+}
+// endregion:	--- Remapping
+
+// region:		--- Bridge
+/// Bridge
+pub struct Bridge {
+	/// the session the messages come from
+	source: Arc<Session>,
+	/// the session the messages go to
+	target: Arc<Session>,
+	/// the key expression in the source session
+	key_expr: String,
+	/// the key used to store the bridge
+	key: String,
+	activation_state: OperationState,
+	direction: BridgeDirection,
+	remapping: Option<Remapping>,
+	rate_limit: Option<Duration>,
+	handles: std::sync::Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl core::fmt::Debug for Bridge {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Bridge")
+			.field("key", &self.key)
+			.field("direction", &self.direction)
+			.field("remapping", &self.remapping)
+			.field("rate_limit", &self.rate_limit)
+			.finish_non_exhaustive()
+	}
+}
+
+impl crate::traits::Responder for Bridge {
+	/// Get the key the bridge is stored with
+	fn selector(&self) -> &str {
+		&self.key
+	}
+}
+
+impl Capability for Bridge {
+	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
+		if state >= &self.activation_state {
+			self.start()
+		} else {
+			self.stop()
+		}
+	}
+}
+
+impl Bridge {
+	/// Constructor for a [`Bridge`]
+	#[allow(clippy::too_many_arguments)]
+	#[must_use]
+	pub const fn new(
+		source: Arc<Session>,
+		target: Arc<Session>,
+		key_expr: String,
+		key: String,
+		activation_state: OperationState,
+		direction: BridgeDirection,
+		remapping: Option<Remapping>,
+		rate_limit: Option<Duration>,
+	) -> Self {
+		Self {
+			source,
+			target,
+			key_expr,
+			key,
+			activation_state,
+			direction,
+			remapping,
+			rate_limit,
+			handles: std::sync::Mutex::new(Vec::new()),
+		}
+	}
+
+	/// Start or restart the bridge.
+	#[instrument(level = Level::TRACE, skip_all)]
+	fn start(&self) -> Result<()> {
+		self.stop()?;
+
+		let mut handles = self
+			.handles
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("Bridge")))?;
+		let target_selector = self.remapping.as_ref().map_or_else(
+			|| self.key_expr.clone(),
+			|remapping| remapping.forward(&self.key_expr),
+		);
+		if self.direction != BridgeDirection::Backward {
+			let remap = self
+				.remapping
+				.clone()
+				.map(|remapping| move |key: &str| remapping.forward(key));
+			handles.push(tokio::task::spawn(
+				run_bridge(
+					self.source.clone(),
+					self.target.clone(),
+					self.key_expr.clone(),
+					remap,
+					self.rate_limit,
+				)
+				.in_current_span(),
+			));
+		}
+		if self.direction != BridgeDirection::Forward {
+			let remap = self
+				.remapping
+				.clone()
+				.map(|remapping| move |key: &str| remapping.backward(key));
+			handles.push(tokio::task::spawn(
+				run_bridge(
+					self.target.clone(),
+					self.source.clone(),
+					target_selector,
+					remap,
+					self.rate_limit,
+				)
+				.in_current_span(),
+			));
+		}
+		drop(handles);
+		Ok(())
+	}
+
+	/// Stop a running bridge
+	#[instrument(level = Level::TRACE, skip_all)]
+	fn stop(&self) -> Result<()> {
+		self.handles
+			.lock()
+			.map_err(|_| Error::MutexPoison(String::from("Bridge")))?
+			.drain(..)
+			.for_each(|handle| handle.abort());
+		Ok(())
+	}
+}
+
+/// Forward the messages for `selector` from `source` to `target` until aborted
+async fn run_bridge(
+	source: Arc<Session>,
+	target: Arc<Session>,
+	selector: String,
+	remap: Option<impl Fn(&str) -> String + Send>,
+	rate_limit: Option<Duration>,
+) {
+	let subscriber = match source.declare_subscriber(&selector).await {
+		Ok(subscriber) => subscriber,
+		Err(error) => {
+			error!("bridging '{selector}' failed with {error}");
+			return;
+		}
+	};
+	// messages of publishers with intra-process delivery do not arrive via zenoh
+	let (_registration, mut local_receiver) = match intra_process::register(source.zid(), &selector)
+	{
+		Ok(registration) => registration,
+		Err(error) => {
+			error!("bridging '{selector}' failed with {error}");
+			return;
+		}
+	};
+	let mut forwarded: HashMap<String, Instant> = HashMap::new();
+
+	loop {
+		let sample = tokio::select! {
+			sample = subscriber.recv_async() => match sample {
+				Ok(sample) => Forwarding::from(sample),
+				Err(_) => break,
+			},
+			Some(sample) = local_receiver.recv() => Forwarding::from(sample),
+		};
+		// skip messages which have already visited the target
+		let Some(attachment) = mark(sample.attachment.as_ref(), source.zid(), target.zid()) else {
+			continue;
+		};
+		let key = sample.key_expr.as_str();
+		if let Some(interval) = rate_limit {
+			let now = Instant::now();
+			if forwarded
+				.get(key)
+				.is_some_and(|last| now.duration_since(*last) < interval)
+			{
+				continue;
+			}
+			forwarded.insert(key.to_string(), now);
+		}
+		let key = remap
+			.as_ref()
+			.map_or_else(|| key.to_string(), |remap| remap(key));
+		let result = match sample.payload {
+			Some(payload) => {
+				target
+					.put(&key, payload)
+					.encoding(sample.encoding)
+					.attachment(attachment)
+					.await
+			}
+			None => target.delete(&key).attachment(attachment).await,
+		};
+		if let Err(error) = result {
+			warn!("bridging '{key}' failed with {error}");
+		}
+	}
+}
+
+/// A message to forward, received via zenoh or the intra-process delivery
+struct Forwarding {
+	key_expr: String,
+	/// the payload of a "put" message, `None` for a "delete" message
+	payload: Option<ZBytes>,
+	encoding: Encoding,
+	attachment: Option<ZBytes>,
+}
+
+impl From<Sample> for Forwarding {
+	fn from(sample: Sample) -> Self {
+		Self {
+			key_expr: sample.key_expr().to_string(),
+			payload: (sample.kind() == SampleKind::Put).then(|| sample.payload().clone()),
+			encoding: sample.encoding().clone(),
+			attachment: sample.attachment().cloned(),
+		}
+	}
+}
+
+impl From<LocalSample> for Forwarding {
+	fn from(sample: LocalSample) -> Self {
+		match sample {
+			LocalSample::Put(message) => {
+				let info = message.info();
+				Self {
+					key_expr: info
+						.map(|info| info.key_expr.clone())
+						.unwrap_or_default(),
+					payload: Some(ZBytes::from(message.to_bytes().into_owned())),
+					encoding: info.map_or_else(Encoding::default, |info| {
+						Encoding::from(info.encoding.as_str())
+					}),
+					attachment: info
+						.and_then(|info| info.attachment.clone())
+						.map(ZBytes::from),
+				}
+			}
+			LocalSample::Delete(key_expr) => Self {
+				key_expr,
+				payload: None,
+				encoding: Encoding::default(),
+				attachment: None,
+			},
+		}
+	}
+}
+
+/// Add the `source` and `target` sessions to the sessions visited by a message with `attachment`.
+/// Returns `None` if the message has already visited `target`.
+/// An attachment in another format than [`Attachments`] is returned unchanged.
+fn mark(attachment: Option<&ZBytes>, source: ZenohId, target: ZenohId) -> Option<ZBytes> {
+	let mut attachments = match attachment {
+		None => Attachments::new(),
+		Some(raw) => match Attachments::from_bytes(&raw.to_bytes()) {
+			Ok(attachments) => attachments,
+			Err(_) => return Some(raw.clone()),
+		},
+	};
+	let (source, target) = (source.to_string(), target.to_string());
+	let mut visited: Vec<&str> = attachments
+		.get(BRIDGE_KEY)
+		.map(|visited| visited.split(',').collect())
+		.unwrap_or_default();
+	if visited.contains(&target.as_str()) {
+		return None;
+	}
+	if !visited.contains(&source.as_str()) {
+		visited.push(&source);
+	}
+	visited.push(&target);
+	let visited = visited.join(",");
+	attachments.insert(BRIDGE_KEY, visited);
+	Some(attachments.to_bytes().into())
+}
+// endregion:	--- Bridge
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Bridge>();
+		is_normal::<BridgeDirection>();
+		is_normal::<Remapping>();
+	}
+
+	#[test]
+	fn remapping() {
+		let remapping = Remapping {
+			from: String::from("robot"),
+			to: String::from("fleet/robot1"),
+		};
+		assert_eq!(
+			remapping.forward("robot/telemetry/**"),
+			"fleet/robot1/telemetry/**"
+		);
+		assert_eq!(remapping.forward("robot"), "fleet/robot1");
+		assert_eq!(remapping.forward("robots/telemetry"), "robots/telemetry");
+		assert_eq!(
+			remapping.backward("fleet/robot1/telemetry/speed"),
+			"robot/telemetry/speed"
+		);
+		assert_eq!(
+			remapping.backward("fleet/robot2/telemetry"),
+			"fleet/robot2/telemetry"
+		);
+
+		let remapping = Remapping {
+			from: String::new(),
+			to: String::from("fleet"),
+		};
+		assert_eq!(
+			remapping.forward("telemetry/speed"),
+			"fleet/telemetry/speed"
+		);
+		assert_eq!(
+			remapping.backward("fleet/telemetry/speed"),
+			"telemetry/speed"
+		);
+	}
+
+	#[test]
+	fn marking() {
+		let (a, b, c) = (ZenohId::default(), ZenohId::default(), ZenohId::default());
+		let attachment = mark(None, a, b).expect("not marked");
+		let attachments = Attachments::from_bytes(&attachment.to_bytes()).expect("not decodable");
+		assert_eq!(
+			attachments.get(BRIDGE_KEY),
+			Some(&alloc::format!("{a},{b}"))
+		);
+		// back to a visited session
+		assert!(mark(Some(&attachment), b, a).is_none());
+		assert!(mark(Some(&attachment), c, b).is_none());
+		// on to another session, keeping the other entries
+		let attachment: ZBytes = attachments
+			.with("origin", "sensor")
+			.to_bytes()
+			.into();
+		let attachment = mark(Some(&attachment), b, c).expect("not marked");
+		let attachments = Attachments::from_bytes(&attachment.to_bytes()).expect("not decodable");
+		assert_eq!(
+			attachments.get(BRIDGE_KEY),
+			Some(&alloc::format!("{a},{b},{c}"))
+		);
+		assert_eq!(attachments.get("origin"), Some(&String::from("sensor")));
+		// foreign attachments are kept unchanged
+		let foreign = ZBytes::from("foreign");
+		let attachment = mark(Some(&foreign), a, b).expect("not forwarded");
+		assert_eq!(attachment.to_bytes(), foreign.to_bytes());
+	}
+
+	/// Open a zenoh session neither listening, connecting nor scouting
+	async fn isolated_session() -> Result<Arc<Session>> {
+		let mut config = zenoh::Config::default();
+		for (key, value) in [
+			("listen/endpoints", "[]"),
+			("connect/endpoints", "[]"),
+			("scouting/multicast/enabled", "false"),
+			("scouting/gossip/enabled", "false"),
+		] {
+			config
+				.insert_json5(key, value)
+				.map_err(|source| Error::CreateCommunicator { source })?;
+		}
+		let session = zenoh::open(config)
+			.await
+			.map_err(|source| Error::CreateCommunicator { source })?;
+		Ok(Arc::new(session))
+	}
+
+	/// Wait until a subscriber for `key_expr` is declared in `session`
+	async fn matched(session: &Session, key_expr: &'static str) -> Result<()> {
+		let publisher = session
+			.declare_publisher(key_expr)
+			.await
+			.map_err(|source| Error::CreateCommunicator { source })?;
+		for _ in 0..100 {
+			let status = publisher
+				.matching_status()
+				.await
+				.map_err(|source| Error::CreateCommunicator { source })?;
+			if status.matching() {
+				return Ok(());
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		panic!("no subscriber for {key_expr}");
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn opposing_bridges() -> Result<()> {
+		let a = isolated_session().await?;
+		let b = isolated_session().await?;
+		let bridge = |direction| {
+			Bridge::new(
+				a.clone(),
+				b.clone(),
+				"bridge/**".into(),
+				"bridge".into(),
+				OperationState::Active,
+				direction,
+				None,
+				None,
+			)
+		};
+		let forward = bridge(BridgeDirection::Forward);
+		let backward = bridge(BridgeDirection::Backward);
+		forward.manage_operation_state(&OperationState::Active)?;
+		backward.manage_operation_state(&OperationState::Active)?;
+		matched(&a, "bridge/test").await?;
+		matched(&b, "bridge/test").await?;
+
+		let on_a = a
+			.declare_subscriber("bridge/**")
+			.await
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		let on_b = b
+			.declare_subscriber("bridge/**")
+			.await
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		a.put("bridge/test", "ping")
+			.await
+			.map_err(|source| Error::PublishingPut { source })?;
+		b.put("bridge/test", "pong")
+			.attachment(Attachments::new().with("origin", "b").to_bytes())
+			.await
+			.map_err(|source| Error::PublishingPut { source })?;
+		tokio::time::sleep(Duration::from_millis(200)).await;
+
+		let received = |subscriber: &zenoh::pubsub::Subscriber<
+			zenoh::handlers::FifoChannelHandler<zenoh::sample::Sample>,
+		>| {
+			let mut payloads = Vec::new();
+			while let Ok(Some(sample)) = subscriber.try_recv() {
+				let attachments = sample
+					.attachment()
+					.and_then(|raw| Attachments::from_bytes(&raw.to_bytes()).ok())
+					.unwrap_or_default();
+				payloads.push((
+					sample
+						.payload()
+						.try_to_string()
+						.map(|payload| payload.to_string())
+						.unwrap_or_default(),
+					attachments.get("origin").cloned(),
+				));
+			}
+			payloads.sort();
+			payloads
+		};
+		// each message arrives exactly once in each session
+		let expected = [
+			(String::from("ping"), None),
+			(String::from("pong"), Some(String::from("b"))),
+		];
+		assert_eq!(received(&on_a), expected);
+		assert_eq!(received(&on_b), expected);
+
+		forward.manage_operation_state(&OperationState::Created)?;
+		backward.manage_operation_state(&OperationState::Created)?;
+		Ok(())
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn intra_process_messages() -> Result<()> {
+		use crate::traits::Publisher as _;
+
+		let a = isolated_session().await?;
+		let b = isolated_session().await?;
+		let bridge = Bridge::new(
+			a.clone(),
+			b.clone(),
+			"bridge/**".into(),
+			"bridge".into(),
+			OperationState::Active,
+			BridgeDirection::Forward,
+			None,
+			None,
+		);
+		bridge.manage_operation_state(&OperationState::Active)?;
+		matched(&a, "bridge/local").await?;
+
+		let on_b = b
+			.declare_subscriber("bridge/**")
+			.await
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		let publisher = crate::zenoh::Publisher::new(
+			a.clone(),
+			"bridge/local".into(),
+			OperationState::Active,
+			#[cfg(feature = "unstable")]
+			zenoh::sample::Locality::Any,
+			zenoh::qos::CongestionControl::Block,
+			String::from("application/octet-stream"),
+			false,
+			true,
+			zenoh::qos::Priority::Data,
+			#[cfg(feature = "unstable")]
+			zenoh::qos::Reliability::Reliable,
+			#[cfg(feature = "shared-memory")]
+			false,
+		);
+		publisher.manage_operation_state(&OperationState::Active)?;
+		publisher.put(dimas_core::message_types::Message::share(Arc::new(7_u32)))?;
+		publisher.delete()?;
+
+		let sample = tokio::time::timeout(Duration::from_secs(1), on_b.recv_async())
+			.await
+			.expect("put not forwarded")
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		assert_eq!(sample.key_expr().as_str(), "bridge/local");
+		let message =
+			dimas_core::message_types::Message::new(sample.payload().to_bytes().into_owned());
+		assert_eq!(message.decode::<u32>()?, 7);
+		let sample = tokio::time::timeout(Duration::from_secs(1), on_b.recv_async())
+			.await
+			.expect("delete not forwarded")
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		assert_eq!(sample.kind(), SampleKind::Delete);
+
+		bridge.manage_operation_state(&OperationState::Created)?;
+		Ok(())
+	}
+}
@@ -11,6 +11,7 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+use super::subscriber::attachments_of;
 use crate::{error::Error, traits::CommunicatorImplementationMethods};
 use alloc::{
 	borrow::ToOwned,
@@ -19,19 +20,22 @@ use alloc::{
 	sync::Arc,
 	vec::Vec,
 };
+use bitcode::decode;
 use core::{fmt::Debug, time::Duration};
 use dimas_core::{
 	Result,
 	enums::OperationState,
-	message_types::{Message, QueryableMsg},
+	message_types::{Attachments, ControlResponse, Message, QueryableMsg},
 	traits::Capability,
+	utils::{inject_trace_context, request_selector_from, with_trace_context},
 };
 use zenoh::config::WhatAmI;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
 use zenoh::{
 	Session, Wait,
-	query::{ConsolidationMode, QueryTarget},
+	handlers::FifoChannelHandler,
+	query::{ConsolidationMode, QueryTarget, Reply},
 	sample::SampleKind,
 };
 // endregion:	--- modules
@@ -58,8 +62,11 @@ impl CommunicatorImplementationMethods for Communicator {
 	/// # Errors
 	#[allow(clippy::needless_pass_by_value)]
 	fn put(&self, selector: &str, message: Message) -> Result<()> {
+		let message = with_trace_context(message);
 		self.session
-			.put(selector, message.value())
+			.put(selector, message.to_bytes())
+			.attachment(message.attachments().map(Attachments::to_bytes))
+			.timestamp(self.session.new_timestamp())
 			.wait()
 			.map_err(|source| Error::PublishingPut { source }.into())
 	}
@@ -83,21 +90,7 @@ impl CommunicatorImplementationMethods for Communicator {
 		message: Option<Message>,
 		mut callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()> {
-		let builder = message
-			.map_or_else(
-				|| self.session.get(selector),
-				|msg| self.session.get(selector).payload(msg.value()),
-			)
-			.consolidation(ConsolidationMode::None)
-			.target(QueryTarget::All);
-
-		#[cfg(feature = "unstable")]
-		let builder = builder.allowed_destination(Locality::Any);
-
-		let query = builder
-			.timeout(Duration::from_millis(5000))
-			.wait()
-			.map_err(|source| Error::QueryCreation { source })?;
+		let query = self.query(selector, message)?;
 
 		let mut unreached = true;
 		let mut retry_count = 0u8;
@@ -113,8 +106,11 @@ impl CommunicatorImplementationMethods for Communicator {
 							callback.as_deref_mut().map_or_else(
 								|| Err(Error::NotImplemented),
 								|callback| {
-									callback(QueryableMsg(content))
-										.map_err(|source| Error::QueryCallback { source })
+									callback(
+										QueryableMsg::new(content)
+											.with_attachments(attachments_of(sample)),
+									)
+									.map_err(|source| Error::QueryCallback { source })
 								},
 							)?;
 						}
@@ -123,8 +119,12 @@ impl CommunicatorImplementationMethods for Communicator {
 						}
 					},
 					Err(err) => {
-						let content= err.payload().try_to_string()?;
-						std::println!(">> Zenoh Communicator received (ERROR: '{:?}' for {})", &content, &selector);
+						let content = err.payload().try_to_string()?;
+						std::println!(
+							">> Zenoh Communicator received (ERROR: '{:?}' for {})",
+							&content,
+							&selector
+						);
 					}
 				}
 				unreached = false;
@@ -142,6 +142,46 @@ impl CommunicatorImplementationMethods for Communicator {
 		}
 		Ok(())
 	}
+
+	/// Send an observation request with an optional [`Message`] to the `Observable`s at `selector`.
+	/// The feedback and the result are not received, they are published by the `Observable`.
+	/// # Errors
+	/// - `AccessingObservable`: there is no `Observable` answering
+	/// - `ObservationDeclined`: no `Observable` accepted the request
+	fn observe(&self, selector: &str, message: Option<Message>) -> Result<()> {
+		let query = self.query(&request_selector_from(selector), message)?;
+
+		let mut answered = false;
+		let mut accepted = false;
+		while let Ok(reply) = query.recv() {
+			if let Ok(sample) = reply.result() {
+				answered = true;
+				let content = sample.payload().to_bytes();
+				let response: ControlResponse = decode(&content)?;
+				accepted |= matches!(response, ControlResponse::Accepted);
+			}
+		}
+		if !answered {
+			return Err(Error::AccessingObservable {
+				selector: selector.to_string(),
+			}
+			.into());
+		}
+		if !accepted {
+			return Err(Error::ObservationDeclined {
+				selector: selector.to_string(),
+			}
+			.into());
+		}
+		Ok(())
+	}
+
+	/// Request an observation configured by [`Message`] from the `Observable`s at `selector`,
+	/// see [`Communicator::observe`](CommunicatorImplementationMethods::observe).
+	/// # Errors
+	fn watch(&self, selector: &str, message: Message) -> Result<()> {
+		self.observe(selector, Some(message))
+	}
 }
 
 impl Communicator {
@@ -180,6 +220,37 @@ impl Communicator {
 	pub const fn mode(&self) -> &String {
 		&self.mode
 	}
+
+	/// Send a query with an optional [`Message`] and the current trace context to all queryables at `selector`.
+	/// # Errors
+	/// - `QueryCreation`: the query could not be sent
+	#[allow(
+		clippy::significant_drop_tightening,
+		reason = "the query builder is consumed by `wait`, the lint misses the move into `allowed_destination`"
+	)]
+	fn query(&self, selector: &str, message: Option<Message>) -> Result<FifoChannelHandler<Reply>> {
+		let attachments = inject_trace_context(message.as_ref().and_then(Message::attachments))
+			.as_ref()
+			.map(Attachments::to_bytes);
+		let builder = self
+			.session
+			.get(selector)
+			.attachment(attachments)
+			.consolidation(ConsolidationMode::None)
+			.target(QueryTarget::All)
+			.timeout(Duration::from_millis(5000));
+		let builder = match message {
+			Some(msg) => builder.payload(msg.to_bytes()),
+			None => builder,
+		};
+
+		#[cfg(feature = "unstable")]
+		let builder = builder.allowed_destination(Locality::Any);
+
+		builder
+			.wait()
+			.map_err(|source| Error::QueryCreation { source }.into())
+	}
 }
 // endregion:	--- Communicator
 
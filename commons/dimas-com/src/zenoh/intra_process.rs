@@ -0,0 +1,150 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `intra_process` provides the delivery of messages to [`Subscriber`](crate::zenoh::Subscriber)s
+//! within the same process and session without going through zenoh.
+//!
+//! Every running subscriber and [`Bridge`](crate::zenoh::Bridge) is registered here.
+//! A [`Publisher`](crate::zenoh::Publisher) with intra-process delivery hands its messages
+//! directly to the matching registered subscribers and publishes to remote subscribers only.
+//! Shared values of a [`Message`] are thus passed on without encoding and copying.
+//! Plain zenoh subscribers of the same session do not receive these messages.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use core::sync::atomic::{AtomicU64, Ordering};
+use dimas_core::message_types::Message;
+#[cfg(feature = "std")]
+use std::sync::RwLock;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use zenoh::{config::ZenohId, key_expr::KeyExpr};
+// endregion:	--- modules
+
+// region:		--- LocalSample
+/// A sample delivered within the process
+#[derive(Debug, Clone)]
+pub enum LocalSample {
+	/// A "put" message
+	Put(Message),
+	/// A "delete" message for the contained key expression
+	Delete(String),
+}
+// endregion:	--- LocalSample
+
+// region:		--- registry
+/// A registered subscriber
+struct LocalSubscriber {
+	id: u64,
+	session: ZenohId,
+	key_expr: KeyExpr<'static>,
+	sender: UnboundedSender<LocalSample>,
+}
+
+/// The registered subscribers of the process
+static SUBSCRIBERS: RwLock<Vec<LocalSubscriber>> = RwLock::new(Vec::new());
+/// The next registration id
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The registration of a subscriber, it is removed from the registry when dropped
+#[derive(Debug)]
+pub struct Registration {
+	id: u64,
+}
+
+impl Drop for Registration {
+	fn drop(&mut self) {
+		if let Ok(mut subscribers) = SUBSCRIBERS.write() {
+			subscribers.retain(|subscriber| subscriber.id != self.id);
+		}
+	}
+}
+
+/// Register a subscriber for `selector` in the session with id `session`.
+/// Returns the [`Registration`] and the receiver for the delivered samples.
+///
+/// # Errors
+/// if the selector is not a valid key expression
+pub fn register(
+	session: ZenohId,
+	selector: &str,
+) -> dimas_core::Result<(Registration, UnboundedReceiver<LocalSample>)> {
+	let key_expr = KeyExpr::try_from(String::from(selector))?;
+	let (sender, receiver) = unbounded_channel();
+	let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+	SUBSCRIBERS
+		.write()
+		.map_err(|_| crate::error::Error::MutexPoison(String::from("intra-process registry")))?
+		.push(LocalSubscriber {
+			id,
+			session,
+			key_expr,
+			sender,
+		});
+	Ok((Registration { id }, receiver))
+}
+
+/// Deliver `sample` to all subscribers in the session with id `session` matching `key_expr`.
+/// Returns the number of subscribers the sample was delivered to.
+pub fn deliver(session: ZenohId, key_expr: &KeyExpr<'_>, sample: &LocalSample) -> usize {
+	SUBSCRIBERS.read().map_or(0, |subscribers| {
+		subscribers
+			.iter()
+			.filter(|subscriber| {
+				subscriber.session == session && subscriber.key_expr.intersects(key_expr)
+			})
+			.filter(|subscriber| subscriber.sender.send(sample.clone()).is_ok())
+			.count()
+	})
+}
+// endregion:	--- registry
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::sync::Arc;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<LocalSample>();
+		is_normal::<Registration>();
+	}
+
+	#[test]
+	fn deliver_to_matching() -> dimas_core::Result<()> {
+		let session = ZenohId::default();
+		let (registration, mut receiver) = register(session, "intra/test/*")?;
+		let (_other, mut other_receiver) = register(ZenohId::default(), "intra/test/*")?;
+
+		let value = Arc::new(42_u64);
+		let sample = LocalSample::Put(Message::share(value.clone()));
+		assert_eq!(
+			deliver(session, &KeyExpr::try_from("intra/test/a")?, &sample),
+			1
+		);
+		assert_eq!(
+			deliver(session, &KeyExpr::try_from("intra/other")?, &sample),
+			0
+		);
+		match receiver.try_recv()? {
+			LocalSample::Put(message) => assert!(Arc::ptr_eq(&message.shared::<u64>()?, &value)),
+			LocalSample::Delete(_) => panic!("unexpected delete"),
+		}
+		// a subscriber of another session gets nothing
+		assert!(other_receiver.try_recv().is_err());
+
+		drop(registration);
+		assert_eq!(
+			deliver(session, &KeyExpr::try_from("intra/test/a")?, &sample),
+			0
+		);
+		Ok(())
+	}
+}
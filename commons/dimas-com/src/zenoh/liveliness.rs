@@ -26,7 +26,7 @@ use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
 use tracing::info;
-use tracing::{Level, error, instrument, warn};
+use tracing::{Instrument, Level, error, instrument, warn};
 use zenoh::Session;
 use zenoh::sample::SampleKind;
 // endregion:	--- modules
@@ -138,26 +138,29 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("liveliness subscriber panic: {}", reason);
-						if let Err(reason) = ctx
-							.sender()
-							.blocking_send(TaskSignal::RestartLiveliness(key.clone()))
+				handle.replace(tokio::task::spawn(
+					async move {
+						std::panic::set_hook(Box::new(move |reason| {
+							error!("liveliness subscriber panic: {}", reason);
+							if let Err(reason) = ctx
+								.sender()
+								.try_send(TaskSignal::RestartLiveliness(key.clone()))
+							{
+								error!("could not restart liveliness subscriber: {}", reason);
+							} else {
+								info!("restarting liveliness subscriber!");
+							};
+						}));
+
+						// the liveliness subscriber with history
+						if let Err(error) =
+							run_liveliness(session2, token2, p_cb2, d_cb, ctx2, known_agents).await
 						{
-							error!("could not restart liveliness subscriber: {}", reason);
-						} else {
-							info!("restarting liveliness subscriber!");
+							error!("running liveliness subscriber failed with {error}");
 						};
-					}));
-
-					// the liveliness subscriber with history
-					if let Err(error) =
-						run_liveliness(session2, token2, p_cb2, d_cb, ctx2, known_agents).await
-					{
-						error!("running liveliness subscriber failed with {error}");
-					};
-				}));
+					}
+					.in_current_span(),
+				));
 				Ok(())
 			},
 		)
@@ -169,7 +172,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -3,8 +3,12 @@
 //! Implements the zenoh communication capabilities.
 
 // region:		--- modules
+/// the bridge between sessions
+pub mod bridge;
 /// zenoh communicator implementation
 pub mod communicator;
+/// the intra-process delivery
+pub mod intra_process;
 /// the liveliness subscriber
 pub mod liveliness;
 /// the observable
@@ -21,6 +25,7 @@ pub mod queryable;
 pub mod subscriber;
 
 // flatten
+pub use bridge::{Bridge, BridgeDirection};
 #[allow(clippy::module_name_repetitions)]
 pub use communicator::Communicator;
 pub use liveliness::LivelinessSubscriber;
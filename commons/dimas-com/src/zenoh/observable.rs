@@ -17,14 +17,15 @@ use core::time::Duration;
 use dimas_core::{
 	Result,
 	enums::{OperationState, TaskSignal},
-	message_types::{ControlResponse, Message, ObservableResponse},
+	message_types::{Attachments, ControlResponse, Message, ObservableResponse},
+	metrics::OBSERVABLE_EXECUTIONS,
 	traits::{Capability, Context},
-	utils::feedback_selector_from,
+	utils::{feedback_selector_from, link_trace_context},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tracing::{Instrument, Level, error, info, instrument, span, warn};
 use zenoh::Wait;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -165,26 +166,30 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("observable panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartObservable(key.clone()))
+				handle.replace(tokio::task::spawn(
+					async move {
+						let key = selector.clone();
+						std::panic::set_hook(Box::new(move |reason| {
+							error!("observable panic: {}", reason);
+							if let Err(reason) = ctx1
+								.sender()
+								.try_send(TaskSignal::RestartObservable(key.clone()))
+							{
+								error!("could not restart observable: {}", reason);
+							} else {
+								info!("restarting observable!");
+							};
+						}));
+						if let Err(error) = run_observable(
+							session, selector, interval, ccb, fcb, fcbp, efc, efch, ctx2,
+						)
+						.await
 						{
-							error!("could not restart observable: {}", reason);
-						} else {
-							info!("restarting observable!");
+							error!("observable failed with {error}");
 						};
-					}));
-					if let Err(error) =
-						run_observable(session, selector, interval, ccb, fcb, fcbp, efc, efch, ctx2)
-							.await
-					{
-						error!("observable failed with {error}");
-					};
-				}));
+					}
+					.in_current_span(),
+				));
 
 				Ok(())
 			},
@@ -203,27 +208,33 @@ where
 					let feedback_callback = self.feedback_callback.clone();
 					let execution_handle = self.execution_handle.clone();
 					let ctx = self.context.clone();
-					tokio::spawn(async move {
-						// stop execution if running
-						if let Some(execution_handle) = execution_handle.lock().await.take() {
-							execution_handle.abort();
-							// send back cancelation message
-							if let Some(publisher) = feedback_publisher.lock().await.take() {
-								let Ok(msg) = feedback_callback.lock().await(ctx).await else {
-									todo!()
+					tokio::spawn(
+						async move {
+							// stop execution if running
+							if let Some(execution_handle) = execution_handle.lock().await.take() {
+								execution_handle.abort();
+								// send back cancelation message
+								if let Some(publisher) = feedback_publisher.lock().await.take() {
+									let Ok(msg) = feedback_callback.lock().await(ctx).await else {
+										todo!()
+									};
+									let response =
+										ObservableResponse::Canceled(msg.to_bytes().into_owned());
+									match publisher
+										.put(Message::encode(&response).value().clone())
+										.wait()
+									{
+										Ok(()) => {}
+										Err(err) => {
+											error!("could not send cancel state due to {err}");
+										}
+									}
 								};
-								let response = ObservableResponse::Canceled(msg.value().clone());
-								match publisher
-									.put(Message::encode(&response).value().clone())
-									.wait()
-								{
-									Ok(()) => {}
-									Err(err) => error!("could not send cancel state due to {err}"),
-								}
 							};
-						};
-						handle.abort();
-					});
+							handle.abort();
+						}
+						.in_current_span(),
+					);
 				}
 				Ok(())
 			},
@@ -235,6 +246,10 @@ where
 // region:		--- functions
 #[allow(clippy::significant_drop_tightening)]
 #[allow(clippy::too_many_arguments)]
+#[allow(
+	clippy::too_many_lines,
+	reason = "the request, cancel and feedback handling share the state of one observation loop"
+)]
 #[instrument(name="observable", level = Level::ERROR, skip_all)]
 async fn run_observable<P>(
 	session: Arc<Session>,
@@ -307,12 +322,25 @@ where
 								content
 							},
 						);
-						let msg = Message::new(content);
+						let attachments = query
+							.attachment()
+							.and_then(|attachment| Attachments::from_bytes(&attachment.to_bytes()).ok());
+						let span = span!(Level::ERROR, "observation", %selector);
+						link_trace_context(&span, attachments.as_ref());
+						let msg = match attachments {
+							Some(attachments) => Message::new(content).with_attachments(attachments),
+							None => Message::new(content),
+						};
 						let ctx_clone = ctx.clone();
-						let res = control_callback.lock().await(ctx_clone, msg).await;
+						let res = control_callback
+							.lock()
+							.await(ctx_clone, msg)
+							.instrument(span.clone())
+							.await;
 						match res {
 							Ok(response) => {
 								if matches!(response, ControlResponse::Accepted ) {
+									ctx.metrics().increment(OBSERVABLE_EXECUTIONS, &[("topic", &selector)], 1);
 									// create feedback publisher
 									let mut fp = feedback_publisher.lock().await;
 									session
@@ -333,7 +361,7 @@ where
 									execution_handle.lock().await.replace(tokio::spawn( async move {
 										let res = execution_function_clone.lock().await(ctx_clone).await.unwrap_or_else(|_| { todo!() });
 										if !matches!(tx_clone.send(res).await, Ok(())) { error!("failed to send back execution result") }
-									}));
+									}.instrument(span).in_current_span()));
 
 									// start feedback timer
 									feedback_timer.set(tokio::time::sleep(feedback_interval));
@@ -361,7 +389,7 @@ where
 							let _ = h.await;
 							let Ok(msg) = feedback_callback.lock().await(ctx).await else { todo!() };
 							let response =
-								ObservableResponse::Canceled(msg.value().clone());
+								ObservableResponse::Canceled(msg.to_bytes().into_owned());
 							if let Some(p) = publisher {
 								match p.put(Message::encode(&response).value().clone()).wait() {
 									Ok(()) => {},
@@ -390,7 +418,7 @@ where
 				if is_running {
 					is_running = false;
 					execution_handle.lock().await.take();
-					let response = ObservableResponse::Finished(result.value().clone());
+					let response = ObservableResponse::Finished(result.to_bytes().into_owned());
 					feedback_publisher.lock().await.take().map_or_else(
 						|| error!("could not publish result"),
 						|p| {
@@ -407,7 +435,7 @@ where
 			() = &mut feedback_timer, if is_running => {
 				let Ok(msg) = feedback_callback.lock().await(ctx).await else { todo!() };
 				let response =
-					ObservableResponse::Feedback(msg.value().clone());
+					ObservableResponse::Feedback(msg.to_bytes().into_owned());
 
 				let lock = feedback_publisher.lock().await;
 				let publisher = lock.as_ref().map_or_else(
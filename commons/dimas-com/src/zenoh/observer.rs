@@ -9,7 +9,6 @@ extern crate std;
 // region:		--- modules
 use alloc::sync::Arc;
 use alloc::{
-	borrow::ToOwned,
 	boxed::Box,
 	string::{String, ToString},
 	vec::Vec,
@@ -19,14 +18,16 @@ use core::time::Duration;
 use dimas_core::{
 	Result,
 	enums::OperationState,
-	message_types::{ControlResponse, Message, ObservableResponse},
+	message_types::{Attachments, ControlResponse, Message, ObservableResponse},
 	traits::{Capability, Context},
-	utils::{cancel_selector_from, feedback_selector_from, request_selector_from},
+	utils::{
+		cancel_selector_from, feedback_selector_from, inject_trace_context, request_selector_from,
+	},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, instrument, warn};
+use tracing::{Instrument, Level, error, instrument, warn};
 use zenoh::Session;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -93,6 +94,14 @@ where
 	}
 
 	/// Cancel a running observation
+	#[allow(
+		clippy::cognitive_complexity,
+		reason = "the expanded `tracing` macros inflate the score of this otherwise linear function"
+	)]
+	#[allow(
+		clippy::significant_drop_tightening,
+		reason = "the query builder is consumed by `wait`, the lint misses the move into `allowed_destination`"
+	)]
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn cancel(&self) -> Result<()> {
 		// TODO: make a proper "key: value" implementation
@@ -126,12 +135,15 @@ where
 							let response: ControlResponse = decode(&content)?;
 							if matches!(response, ControlResponse::Canceled) {
 								// without spawning possible deadlock when called inside an control response
-								tokio::spawn(async move {
-									let mut lock = ccb.lock().await;
-									if let Err(error) = lock(ctx.clone(), response).await {
-										error!("callback failed with {error}");
+								tokio::spawn(
+									async move {
+										let mut lock = ccb.lock().await;
+										if let Err(error) = lock(ctx.clone(), response).await {
+											error!("callback failed with {error}");
+										}
 									}
-								});
+									.in_current_span(),
+								);
 							} else {
 								error!("unexpected response on cancelation");
 							};
@@ -159,19 +171,31 @@ where
 	}
 
 	/// Request an observation with an optional [`Message`].
+	#[allow(
+		clippy::cognitive_complexity,
+		reason = "the expanded `tracing` macros inflate the score of this otherwise linear function"
+	)]
+	#[allow(
+		clippy::significant_drop_tightening,
+		reason = "the query builder is consumed by `wait`, the lint misses the move into `allowed_destination`"
+	)]
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn request(&self, message: Option<Message>) -> Result<()> {
 		let session = self.session.clone();
 		// TODO: make a proper "key: value" implementation
 		let selector = request_selector_from(&self.selector);
+		let attachments = inject_trace_context(message.as_ref().and_then(Message::attachments))
+			.as_ref()
+			.map(Attachments::to_bytes);
 		let mut query = session
 			.get(&selector)
+			.attachment(attachments)
 			.target(QueryTarget::All)
 			.consolidation(ConsolidationMode::None)
 			.timeout(self.timeout);
 
 		if let Some(message) = message {
-			let value = message.value().to_owned();
+			let value = message.to_bytes().into_owned();
 			query = query.payload(value);
 		};
 
@@ -203,46 +227,41 @@ where
 										#[cfg(not(feature = "unstable"))]
 										let source_id = "*".to_string();
 										#[cfg(feature = "unstable")]
-										let source_id = reply.result().map_or_else(
-											|_| {
-												reply.replier_id().map_or_else(
-													|| "*".to_string(),
-													|id| id.to_string(),
-												)
-											},
-											|sample| {
-												sample.source_info().source_id().map_or_else(
-													|| {
-														reply.replier_id().map_or_else(
-															|| "*".to_string(),
-															|id| id.to_string(),
-														)
-													},
-													|id| id.zid().to_string(),
-												)
-											},
-										);
+										let source_id = reply
+											.result()
+											.ok()
+											.and_then(|sample| sample.source_info())
+											.map(|info| info.source_id().zid())
+											.or_else(|| reply.replier_id().map(|id| id.zid()))
+											.map_or_else(|| "*".to_string(), |zid| zid.to_string());
 										let selector =
 											feedback_selector_from(&self.selector, &source_id);
 
 										let rcb = self.response_callback.clone();
-										tokio::task::spawn(async move {
-											if let Err(error) =
-												run_observation(session, selector, ctx, rcb).await
-											{
-												error!("observation failed with {error}");
-											};
-										});
+										tokio::task::spawn(
+											async move {
+												if let Err(error) =
+													run_observation(session, selector, ctx, rcb)
+														.await
+												{
+													error!("observation failed with {error}");
+												};
+											}
+											.in_current_span(),
+										);
 									};
 									// call control callback
 									let ctx = self.context.clone();
 									let ccb = self.control_callback.clone();
-									tokio::task::spawn(async move {
-										let mut lock = ccb.lock().await;
-										if let Err(error) = lock(ctx, response).await {
-											error!("control callback failed with {error}");
+									tokio::task::spawn(
+										async move {
+											let mut lock = ccb.lock().await;
+											if let Err(error) = lock(ctx, response).await {
+												error!("control callback failed with {error}");
+											}
 										}
-									});
+										.in_current_span(),
+									);
 								},
 							);
 						}
@@ -328,7 +347,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
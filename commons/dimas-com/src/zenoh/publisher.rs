@@ -1,6 +1,11 @@
 // Copyright © 2023 Stephan Kunz
 
 //! Module `publisher` provides a message sender `Publisher` which can be created using the `PublisherBuilder`.
+//!
+//! The messages are stamped by the zenoh session with the wall time, not with the agents clock,
+//! so the timestamps of agents with different clocks stay comparable.
+//! Without feature `unstable` the sequence number of a message is sent as [`Attachments`] entry
+//! [`SEQUENCE_KEY`] instead of zenoh's source information.
 
 #[doc(hidden)]
 extern crate alloc;
@@ -9,19 +14,43 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+use super::intra_process::{self, LocalSample};
 use crate::error::Error;
-use alloc::{string::String, sync::Arc};
-use core::fmt::Debug;
-use dimas_core::{Result, enums::OperationState, message_types::Message, traits::Capability};
+use alloc::{
+	string::{String, ToString},
+	sync::Arc,
+};
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicU32, Ordering},
+};
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	message_types::{Attachments, Message, SampleInfo},
+	traits::Capability,
+	utils::with_trace_context,
+};
 use tracing::{Level, instrument};
+#[cfg(feature = "shared-memory")]
+use zenoh::shm::{BlockOn, GarbageCollect};
 use zenoh::{
 	Session, Wait,
+	bytes::ZBytes,
 	qos::{CongestionControl, Priority},
+	sample::Locality,
+	time::Timestamp,
 };
 #[cfg(feature = "unstable")]
-use zenoh::{qos::Reliability, sample::Locality};
+use zenoh::{qos::Reliability, sample::SourceInfo};
 // endregion:	--- modules
 
+// region:		--- types
+/// The key of the [`Attachments`] entry holding the sequence number of a message
+/// published without feature `unstable`
+pub const SEQUENCE_KEY: &str = "dimas-sequence";
+// endregion:	--- types
+
 // region:		--- Publisher
 /// Publisher
 pub struct Publisher {
@@ -34,9 +63,14 @@ pub struct Publisher {
 	congestion_control: CongestionControl,
 	encoding: String,
 	express: bool,
+	intra_process: bool,
 	priority: Priority,
 	#[cfg(feature = "unstable")]
 	reliability: Reliability,
+	#[cfg(feature = "shared-memory")]
+	shared_memory: bool,
+	/// the sequence number of the next message
+	sequence_number: AtomicU32,
 	publisher: std::sync::Mutex<Option<zenoh::pubsub::Publisher<'static>>>,
 }
 
@@ -44,6 +78,7 @@ impl Debug for Publisher {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		f.debug_struct("Publisher")
 			.field("selector", &self.selector)
+			.field("intra_process", &self.intra_process)
 			.field("initialized", &self.publisher)
 			.finish_non_exhaustive()
 	}
@@ -62,14 +97,46 @@ impl crate::traits::Publisher for Publisher {
 	fn put(&self, message: Message) -> Result<()> {
 		self.publisher.lock().map_or_else(
 			|_| todo!(),
-			|publisher| match publisher
-				.as_ref()
-				.ok_or(Error::AccessPublisher)?
-				.put(message.value())
-				.wait()
-			{
-				Ok(()) => Ok(()),
-				Err(source) => Err(Error::PublishingPut { source }.into()),
+			|publisher| {
+				let publisher = publisher.as_ref().ok_or(Error::AccessPublisher)?;
+				let message = with_trace_context(message);
+				let timestamp = self.session.new_timestamp();
+				let sequence_number = self
+					.sequence_number
+					.fetch_add(1, Ordering::Relaxed);
+				if self.intra_process {
+					let info = SampleInfo {
+						key_expr: publisher.key_expr().to_string(),
+						timestamp: Some(nanos(&timestamp)),
+						source_id: Some(self.session.zid().to_string()),
+						sequence_number: Some(sequence_number),
+						encoding: self.encoding.clone(),
+						attachment: message.attachments().map(Attachments::to_bytes),
+					};
+					intra_process::deliver(
+						self.session.zid(),
+						publisher.key_expr(),
+						&LocalSample::Put(message.clone().with_info(info)),
+					);
+					// avoid encoding a shared value nobody else is interested in
+					if message.is_shared() && !publisher.matching_status().wait()?.matching() {
+						return Ok(());
+					}
+				}
+				#[cfg(feature = "shared-memory")]
+				if self.shared_memory {
+					return self.put_shared_memory(publisher, &message, timestamp, sequence_number);
+				}
+				match publish(
+					publisher,
+					message.to_bytes(),
+					message.attachments(),
+					timestamp,
+					sequence_number,
+				) {
+					Ok(()) => Ok(()),
+					Err(source) => Err(Error::PublishingPut { source }.into()),
+				}
 			},
 		)
 	}
@@ -81,14 +148,19 @@ impl crate::traits::Publisher for Publisher {
 	fn delete(&self) -> Result<()> {
 		self.publisher.lock().map_or_else(
 			|_| todo!(),
-			|publisher| match publisher
-				.as_ref()
-				.ok_or(Error::AccessPublisher)?
-				.delete()
-				.wait()
-			{
-				Ok(()) => Ok(()),
-				Err(source) => Err(Error::PublishingDelete { source }.into()),
+			|publisher| {
+				let publisher = publisher.as_ref().ok_or(Error::AccessPublisher)?;
+				if self.intra_process {
+					intra_process::deliver(
+						self.session.zid(),
+						publisher.key_expr(),
+						&LocalSample::Delete(publisher.key_expr().to_string()),
+					);
+				}
+				match publisher.delete().wait() {
+					Ok(()) => Ok(()),
+					Err(source) => Err(Error::PublishingDelete { source }.into()),
+				}
 			},
 		)
 	}
@@ -117,8 +189,10 @@ impl Publisher {
 		congestion_control: CongestionControl,
 		encoding: String,
 		express: bool,
+		intra_process: bool,
 		priority: Priority,
 		#[cfg(feature = "unstable")] reliability: Reliability,
+		#[cfg(feature = "shared-memory")] shared_memory: bool,
 	) -> Self {
 		Self {
 			session,
@@ -129,13 +203,56 @@ impl Publisher {
 			congestion_control,
 			encoding,
 			express,
+			intra_process,
 			priority,
 			#[cfg(feature = "unstable")]
 			reliability,
+			#[cfg(feature = "shared-memory")]
+			shared_memory,
+			sequence_number: AtomicU32::new(0),
 			publisher: std::sync::Mutex::new(None),
 		}
 	}
 
+	/// Publish `message` in a buffer allocated from the sessions shared memory provider.
+	/// As long as the provider is not ready, the message is published as usual.
+	#[cfg(feature = "shared-memory")]
+	fn put_shared_memory(
+		&self,
+		publisher: &zenoh::pubsub::Publisher<'static>,
+		message: &Message,
+		timestamp: Timestamp,
+		sequence_number: u32,
+	) -> Result<()> {
+		let content = message.to_bytes();
+		// waiting for a provider still initializing would take it from the session for good
+		let result = match self.session.get_shm_provider().into_option() {
+			Some(provider) => {
+				let mut buffer = provider
+					.alloc(content.len())
+					.with_policy::<BlockOn<GarbageCollect>>()
+					.wait()
+					.map_err(|error| Error::SharedMemory(alloc::format!("{error:?}")))?;
+				buffer.copy_from_slice(&content);
+				publish(
+					publisher,
+					buffer,
+					message.attachments(),
+					timestamp,
+					sequence_number,
+				)
+			}
+			None => publish(
+				publisher,
+				content,
+				message.attachments(),
+				timestamp,
+				sequence_number,
+			),
+		};
+		result.map_err(|source| Error::PublishingPut { source }.into())
+	}
+
 	/// Initialize
 	/// # Errors
 	///
@@ -155,7 +272,20 @@ impl Publisher {
 			.allowed_destination(self.allowed_destination)
 			.reliability(self.reliability);
 
+		// local subscribers and bridges are served by the intra-process delivery
+		let builder = if self.intra_process {
+			builder.allowed_destination(Locality::Remote)
+		} else {
+			builder
+		};
+
 		let new_publisher = builder.wait()?;
+
+		// trigger the initialization of the shared memory provider ahead of the first message
+		#[cfg(feature = "shared-memory")]
+		if self.shared_memory {
+			let _ = self.session.get_shm_provider();
+		}
 		//.map_err(|_| DimasError::Put.into())?;
 		self.publisher.lock().map_or_else(
 			|_| todo!(),
@@ -180,6 +310,36 @@ impl Publisher {
 		)
 	}
 }
+
+/// Publish `payload` with optional `attachments`, a timestamp and the sequence number,
+/// with feature `unstable` as part of the source information
+fn publish(
+	publisher: &zenoh::pubsub::Publisher<'static>,
+	payload: impl Into<ZBytes>,
+	attachments: Option<&Attachments>,
+	timestamp: Timestamp,
+	sequence_number: u32,
+) -> zenoh::Result<()> {
+	#[cfg(feature = "unstable")]
+	let builder = publisher
+		.put(payload)
+		.attachment(attachments.map(Attachments::to_bytes))
+		.source_info(SourceInfo::new(publisher.id(), sequence_number));
+	#[cfg(not(feature = "unstable"))]
+	let builder = publisher.put(payload).attachment(
+		attachments
+			.cloned()
+			.unwrap_or_default()
+			.with(SEQUENCE_KEY, sequence_number.to_string())
+			.to_bytes(),
+	);
+	builder.timestamp(timestamp).wait()
+}
+
+/// Convert a `timestamp` into nanoseconds since the UNIX epoch
+pub(crate) fn nanos(timestamp: &Timestamp) -> i64 {
+	i64::try_from(timestamp.get_time().to_duration().as_nanos()).unwrap_or(i64::MAX)
+}
 // endregion:	--- Publisher
 
 #[cfg(test)]
@@ -193,4 +353,78 @@ mod tests {
 	const fn normal_types() {
 		is_normal::<Publisher>();
 	}
+
+	/// Opens a session with shared memory enabled, which listens and connects only as given
+	#[cfg(feature = "shared-memory")]
+	async fn shared_memory_session(listen: &str, connect: &str) -> Result<Arc<Session>> {
+		let mut config = zenoh::Config::default();
+		for (key, value) in [
+			("listen/endpoints", listen),
+			("connect/endpoints", connect),
+			("scouting/multicast/enabled", "false"),
+			("scouting/gossip/enabled", "false"),
+			("transport/shared_memory/enabled", "true"),
+		] {
+			config
+				.insert_json5(key, value)
+				.map_err(|source| Error::CreateCommunicator { source })?;
+		}
+		let session = zenoh::open(config)
+			.await
+			.map_err(|source| Error::CreateCommunicator { source })?;
+		Ok(Arc::new(session))
+	}
+
+	#[cfg(feature = "shared-memory")]
+	#[tokio::test(flavor = "multi_thread")]
+	async fn shared_memory_put() -> Result<()> {
+		use crate::traits::Publisher as _;
+
+		let port = std::net::TcpListener::bind("127.0.0.1:0")?
+			.local_addr()?
+			.port();
+		let endpoint = alloc::format!("[\"tcp/127.0.0.1:{port}\"]");
+		let receiving = shared_memory_session(&endpoint, "[]").await?;
+		let sending = shared_memory_session("[]", &endpoint).await?;
+		let subscriber = receiving
+			.declare_subscriber("publisher/shm")
+			.await
+			.map_err(|source| Error::SubscriberCreation { source })?;
+		let publisher = Publisher::new(
+			sending,
+			"publisher/shm".into(),
+			OperationState::Active,
+			Locality::Any,
+			CongestionControl::Block,
+			String::from("application/octet-stream"),
+			false,
+			false,
+			Priority::Data,
+			Reliability::Reliable,
+			true,
+		);
+		publisher.manage_operation_state(&OperationState::Active)?;
+
+		// the subscription and the shared memory provider need some time to get ready
+		let sample = tokio::time::timeout(core::time::Duration::from_secs(5), async {
+			loop {
+				publisher.put(Message::encode(&42_u32))?;
+				if let Ok(Ok(sample)) = tokio::time::timeout(
+					core::time::Duration::from_millis(50),
+					subscriber.recv_async(),
+				)
+				.await
+				{
+					if sample.payload().as_shm().is_some() {
+						return Result::<zenoh::sample::Sample>::Ok(sample);
+					}
+				}
+			}
+		})
+		.await
+		.map_err(|_| "no sample via shared memory")??;
+		let message = Message::new(sample.payload().to_bytes().into_owned());
+		assert_eq!(message.decode::<u32>()?, 42);
+		Ok(())
+	}
 }
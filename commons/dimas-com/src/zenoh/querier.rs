@@ -9,14 +9,16 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+use super::subscriber::attachments_of;
 use crate::error::Error;
 use alloc::sync::Arc;
 use core::{fmt::Debug, time::Duration};
 use dimas_core::{
 	Result,
 	enums::OperationState,
-	message_types::{Message, QueryableMsg},
+	message_types::{Attachments, Message, QueryableMsg},
 	traits::{Capability, Context},
+	utils::inject_trace_context,
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
@@ -27,7 +29,7 @@ use std::{
 };
 #[cfg(feature = "std")]
 use tokio::sync::Mutex;
-use tracing::{Level, error, instrument, warn};
+use tracing::{Instrument, Level, error, instrument, warn};
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
 use zenoh::{
@@ -99,6 +101,10 @@ where
 	}
 
 	/// Run a Querier with an optional [`Message`].
+	#[allow(
+		clippy::significant_drop_tightening,
+		reason = "the query builder is consumed by `wait`, the lint misses the move into `allowed_destination`"
+	)]
 	#[instrument(name="Querier", level = Level::ERROR, skip_all)]
 	fn get(
 		&self,
@@ -113,15 +119,20 @@ where
 					.clone()
 					.ok_or_else(|| Error::InvalidSelector("querier".into()))?;
 
+				let attachments =
+					inject_trace_context(message.as_ref().and_then(Message::attachments))
+						.as_ref()
+						.map(Attachments::to_bytes);
 				let builder = message
 					.map_or_else(
 						|| self.session.get(&key_expr),
 						|msg| {
 							self.session
 								.get(&self.selector)
-								.payload(msg.value())
+								.payload(msg.to_bytes())
 						},
 					)
+					.attachment(attachments)
 					.encoding(self.encoding.as_str())
 					.target(self.target)
 					.consolidation(self.mode)
@@ -144,16 +155,20 @@ where
 							Ok(sample) => match sample.kind() {
 								SampleKind::Put => {
 									let content: Vec<u8> = sample.payload().to_bytes().into_owned();
-									let msg = QueryableMsg(content);
+									let msg = QueryableMsg::new(content)
+										.with_attachments(attachments_of(sample));
 									if callback.is_none() {
 										let cb = cb.clone();
 										let ctx = self.context.clone();
-										tokio::task::spawn(async move {
-											let mut lock = cb.lock().await;
-											if let Err(error) = lock(ctx, msg).await {
-												error!("querier callback failed with {error}");
+										tokio::task::spawn(
+											async move {
+												let mut lock = cb.lock().await;
+												if let Err(error) = lock(ctx, msg).await {
+													error!("querier callback failed with {error}");
+												}
 											}
-										});
+											.in_current_span(),
+										);
 									} else {
 										let callback = callback.as_mut().ok_or_else(|| {
 											Error::AccessingQuerier {
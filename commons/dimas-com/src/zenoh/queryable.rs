@@ -16,12 +16,16 @@ use dimas_core::{
 	Result,
 	enums::{OperationState, TaskSignal},
 	message_types::QueryMsg,
+	metrics::CALLBACK_DURATION,
 	traits::{Capability, Context},
+	utils::link_trace_context,
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
+use std::time::Instant;
+#[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tracing::{Instrument, Level, error, info, instrument, span, warn};
 use zenoh::Session;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -137,33 +141,36 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("queryable panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartQueryable(key.clone()))
+				handle.replace(tokio::task::spawn(
+					async move {
+						let key = selector.clone();
+						std::panic::set_hook(Box::new(move |reason| {
+							error!("queryable panic: {}", reason);
+							if let Err(reason) = ctx1
+								.sender()
+								.try_send(TaskSignal::RestartQueryable(key.clone()))
+							{
+								error!("could not restart queryable: {}", reason);
+							} else {
+								info!("restarting queryable!");
+							};
+						}));
+						if let Err(error) = run_queryable(
+							session,
+							selector,
+							cb,
+							completeness,
+							#[cfg(feature = "unstable")]
+							allowed_origin,
+							ctx2,
+						)
+						.await
 						{
-							error!("could not restart queryable: {}", reason);
-						} else {
-							info!("restarting queryable!");
+							error!("queryable failed with {error}");
 						};
-					}));
-					if let Err(error) = run_queryable(
-						session,
-						selector,
-						cb,
-						completeness,
-						#[cfg(feature = "unstable")]
-						allowed_origin,
-						ctx2,
-					)
-					.await
-					{
-						error!("queryable failed with {error}");
-					};
-				}));
+					}
+					.in_current_span(),
+				));
 				Ok(())
 			},
 		)
@@ -175,7 +182,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -205,12 +214,20 @@ where
 	loop {
 		let query = queryable.recv_async().await?;
 		let request = QueryMsg(query);
+		let span = span!(Level::ERROR, "query", %selector);
+		link_trace_context(&span, request.attachments().as_ref());
 
-		let ctx = ctx.clone();
 		let mut lock = callback.lock().await;
-		if let Err(error) = lock(ctx, request).await {
+		let start = Instant::now();
+		if let Err(error) = lock(ctx.clone(), request).instrument(span).await {
 			error!("queryable callback failed with {error}");
 		}
+		drop(lock);
+		ctx.metrics().observe(
+			CALLBACK_DURATION,
+			&[("kind", "queryable"), ("topic", &selector)],
+			start.elapsed(),
+		);
 	}
 }
 // endregion:	--- Queryable
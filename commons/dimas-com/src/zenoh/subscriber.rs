@@ -2,6 +2,10 @@
 
 //! Module `subscriber` provides a message `Subscriber` which can be created using the `SubscriberBuilder`.
 //! A `Subscriber` can optional subscribe on a delete message.
+//!
+//! Messages published via shared memory are received like any other message.
+//! The metadata of a received message, like its concrete key expression,
+//! is available via `Message::info`.
 
 #[doc(hidden)]
 extern crate alloc;
@@ -10,23 +14,37 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+#[cfg(not(feature = "unstable"))]
+use super::publisher::SEQUENCE_KEY;
+use super::{
+	intra_process::{self, LocalSample},
+	publisher::nanos,
+};
 use crate::error::Error;
 use alloc::sync::Arc;
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+	vec::Vec,
+};
 use dimas_core::{
 	Result,
 	enums::{OperationState, TaskSignal},
-	message_types::Message,
+	message_types::{Attachments, Message, SampleInfo},
+	metrics::{BYTES_RECEIVED, CALLBACK_DURATION, MESSAGES_RECEIVED},
 	traits::{Capability, Context},
+	utils::link_trace_context,
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
+use std::time::Instant;
+#[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tracing::{Instrument, Level, error, info, instrument, span, warn};
 use zenoh::Session;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
-use zenoh::sample::SampleKind;
+use zenoh::sample::{Sample, SampleKind};
 // endregion:	--- modules
 
 // region:    	--- types
@@ -82,6 +100,15 @@ where
 	fn selector(&self) -> &str {
 		&self.selector
 	}
+
+	/// Whether the subscribers task is running
+	fn is_subscribed(&self) -> bool {
+		self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		})
+	}
 }
 
 impl<P> Capability for Subscriber<P>
@@ -144,33 +171,36 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("subscriber panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartSubscriber(key.clone()))
+				handle.replace(tokio::task::spawn(
+					async move {
+						let key = selector.clone();
+						std::panic::set_hook(Box::new(move |reason| {
+							error!("subscriber panic: {}", reason);
+							if let Err(reason) = ctx1
+								.sender()
+								.try_send(TaskSignal::RestartSubscriber(key.clone()))
+							{
+								error!("could not restart subscriber: {}", reason);
+							} else {
+								info!("restarting subscriber!");
+							};
+						}));
+						if let Err(error) = run_subscriber(
+							session,
+							selector,
+							#[cfg(feature = "unstable")]
+							allowed_origin,
+							p_cb,
+							d_cb,
+							ctx2.clone(),
+						)
+						.await
 						{
-							error!("could not restart subscriber: {}", reason);
-						} else {
-							info!("restarting subscriber!");
+							error!("spawning subscriber failed with {error}");
 						};
-					}));
-					if let Err(error) = run_subscriber(
-						session,
-						selector,
-						#[cfg(feature = "unstable")]
-						allowed_origin,
-						p_cb,
-						d_cb,
-						ctx2.clone(),
-					)
-					.await
-					{
-						error!("spawning subscriber failed with {error}");
-					};
-				}));
+					}
+					.in_current_span(),
+				));
 				Ok(())
 			},
 		)
@@ -182,7 +212,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -208,23 +240,52 @@ where
 
 	let subscriber = builder.await?;
 
+	// messages of publishers with intra-process delivery arrive via the registry
+	#[cfg(feature = "unstable")]
+	let local = allowed_origin != Locality::Remote;
+	#[cfg(not(feature = "unstable"))]
+	let local = true;
+	let (_registration, mut local_receiver) = intra_process::register(session.zid(), &selector)?;
+	if !local {
+		local_receiver.close();
+	}
+
 	loop {
-		let sample = subscriber
-			.recv_async()
-			.await
-			.map_err(|source| Error::SubscriberCreation { source })?;
-
-		match sample.kind() {
-			SampleKind::Put => {
-				let content: Vec<u8> = sample.payload().to_bytes().into_owned();
-				let msg = Message::new(content);
+		let sample = tokio::select! {
+			sample = subscriber.recv_async() => {
+				let sample = sample.map_err(|source| Error::SubscriberCreation { source })?;
+				match sample.kind() {
+					SampleKind::Put => LocalSample::Put(received(&sample)),
+					SampleKind::Delete => LocalSample::Delete(sample.key_expr().to_string()),
+				}
+			}
+			Some(sample) = local_receiver.recv() => sample,
+		};
+
+		match sample {
+			LocalSample::Put(msg) => {
+				let span = span!(Level::ERROR, "put", %selector);
+				link_trace_context(&span, msg.attachments());
+				let labels = [("topic", selector.as_str())];
+				ctx.metrics()
+					.increment(MESSAGES_RECEIVED, &labels, 1);
+				if !msg.is_shared() {
+					ctx.metrics()
+						.increment(BYTES_RECEIVED, &labels, msg.value().len() as u64);
+				}
 				let mut lock = p_cb.lock().await;
-				let ctx = ctx.clone();
-				if let Err(error) = lock(ctx, msg).await {
+				let start = Instant::now();
+				if let Err(error) = lock(ctx.clone(), msg).instrument(span).await {
 					error!("subscriber put callback failed with {error}");
 				}
+				drop(lock);
+				ctx.metrics().observe(
+					CALLBACK_DURATION,
+					&[("kind", "subscriber"), ("topic", &selector)],
+					start.elapsed(),
+				);
 			}
-			SampleKind::Delete => {
+			LocalSample::Delete(_) => {
 				if let Some(cb) = d_cb.clone() {
 					let ctx = ctx.clone();
 					let mut lock = cb.lock().await;
@@ -236,6 +297,64 @@ where
 		}
 	}
 }
+
+/// Decode the [`Attachments`] of a received `sample`, if there are some in that format
+pub(crate) fn attachments_of(sample: &Sample) -> Option<Attachments> {
+	sample
+		.attachment()
+		.and_then(|attachment| Attachments::from_bytes(&attachment.to_bytes()).ok())
+}
+
+/// Create the [`Message`] of a received put `sample` with its [`Attachments`] and [`SampleInfo`].
+/// Without feature `unstable` the sequence number is taken from the
+/// [`SEQUENCE_KEY`](super::publisher::SEQUENCE_KEY) entry, which is removed from the attachments.
+fn received(sample: &Sample) -> Message {
+	let content: Vec<u8> = sample.payload().to_bytes().into_owned();
+	#[cfg_attr(feature = "unstable", allow(unused_mut))]
+	let mut attachments = attachments_of(sample);
+	#[cfg_attr(feature = "unstable", allow(unused_mut))]
+	let mut info = sample_info(sample);
+	#[cfg(not(feature = "unstable"))]
+	if let Some(sequence_number) = attachments
+		.as_mut()
+		.and_then(|attachments| attachments.remove(SEQUENCE_KEY))
+	{
+		info.sequence_number = sequence_number.parse().ok();
+		attachments = attachments.filter(|attachments| !attachments.is_empty());
+		info.attachment = attachments.as_ref().map(Attachments::to_bytes);
+	}
+	let message = Message::new(content).with_info(info);
+	match attachments {
+		Some(attachments) => message.with_attachments(attachments),
+		None => message,
+	}
+}
+
+/// Collect the metadata of a received `sample`.
+/// Without feature `unstable` the source id is taken from the timestamp.
+fn sample_info(sample: &Sample) -> SampleInfo {
+	#[cfg(feature = "unstable")]
+	let source = sample
+		.source_info()
+		.map(|info| (info.source_id().zid().to_string(), info.source_sn()));
+	#[cfg(not(feature = "unstable"))]
+	let source: Option<(String, u32)> = None;
+
+	let timestamp = sample.timestamp();
+	SampleInfo {
+		key_expr: sample.key_expr().to_string(),
+		timestamp: timestamp.map(nanos),
+		source_id: source
+			.as_ref()
+			.map(|(id, _)| id.clone())
+			.or_else(|| timestamp.map(|timestamp| timestamp.get_id().to_string())),
+		sequence_number: source.map(|(_, sequence_number)| sequence_number),
+		encoding: sample.encoding().to_string(),
+		attachment: sample
+			.attachment()
+			.map(|attachment| attachment.to_bytes().into_owned()),
+	}
+}
 // endregion:	--- Subscriber
 
 #[cfg(test)]
@@ -9,7 +9,7 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::messages::AboutEntity;
+use crate::messages::{AboutEntity, LogLevelEntity};
 use alloc::{
 	string::{String, ToString},
 	vec::Vec,
@@ -17,7 +17,7 @@ use alloc::{
 use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
 use dimas_core::{
 	Result,
-	enums::{OperationState, Signal},
+	enums::{LogLevel, OperationState, Signal},
 	message_types::Message,
 	utils::selector_from,
 };
@@ -56,6 +56,38 @@ pub fn set_state(
 }
 // endregion:	--- set_state
 
+// region:		--- set_log_level
+/// Set the [`LogLevel`] for streaming log records of `DiMAS` entities.
+/// Without a level the current levels are returned.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn set_log_level(
+	com: &Communicator,
+	base_selector: &String,
+	level: Option<LogLevel>,
+) -> Result<Vec<LogLevelEntity>> {
+	let mut map: HashMap<String, LogLevelEntity> = HashMap::new();
+
+	let selector = selector_from("signal", Some(base_selector));
+	let message = Message::encode(&Signal::Log { level });
+	// set level for entities matching the selector
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: LogLevelEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<LogLevelEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+// endregion:	--- set_log_level
+
 // region:		--- shutdown
 /// Shutdown of `DiMAS` entities
 /// # Errors
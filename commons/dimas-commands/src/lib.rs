@@ -5,9 +5,17 @@
 
 mod control;
 mod lists;
+mod logs;
 /// the command messages
 pub mod messages;
+mod metrics;
+mod parameters;
+mod statistics;
 
 // flatten
 pub use control::*;
 pub use lists::*;
+pub use logs::*;
+pub use metrics::*;
+pub use parameters::*;
+pub use statistics::*;
@@ -10,18 +10,18 @@ extern crate std;
 
 // region:		--- modules
 use crate::messages::{AboutEntity, PingEntity, ScoutingEntity};
-use alloc::vec::Vec;
 use alloc::{
 	borrow::ToOwned,
 	string::{String, ToString},
 };
-use chrono::Local;
+use alloc::{vec, vec::Vec};
 use core::time::Duration;
 use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
 use dimas_config::Config;
 use dimas_core::{Result, enums::Signal, message_types::Message, utils::selector_from};
+use dimas_time::Clock;
 #[cfg(feature = "std")]
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 use zenoh::{
 	Wait,
 	config::{Locator, WhatAmI},
@@ -56,39 +56,37 @@ pub fn about_list(com: &Communicator, base_selector: &String) -> Result<Vec<Abou
 // endregion:	--- about_list
 
 // region:		--- ping_list
-/// Ping all reachable `DiMAS` entities
+/// Ping all reachable `DiMAS` entities once with a payload of `payload_size` bytes.
+///
+/// Returns the replying entities together with the measured roundtrip time.
 /// # Errors
 #[cfg(feature = "std")]
-pub fn ping_list(com: &Communicator, base_selector: &String) -> Result<Vec<(PingEntity, i64)>> {
-	let mut map: HashMap<String, (PingEntity, i64)> = HashMap::new();
+pub fn ping_list(
+	com: &Communicator,
+	base_selector: &String,
+	payload_size: usize,
+) -> Result<Vec<(PingEntity, Duration)>> {
+	let mut map: HashMap<String, (PingEntity, Duration)> = HashMap::new();
 
 	let selector = selector_from("signal", Some(base_selector));
-	let sent = Local::now()
-		.naive_utc()
-		.and_utc()
-		.timestamp_nanos_opt()
-		.unwrap_or(0);
-	let message = Message::encode(&Signal::Ping { sent });
-	// set state for entities matching the selector
+	let sent = Clock::wall().now_nanos();
+	let payload = vec![0u8; payload_size];
+	let message = Message::encode(&Signal::Ping { sent, payload });
+	// use a monotonic clock for the roundtrip measurement
+	let start = Instant::now();
 	com.get(
 		&selector,
 		Some(message),
 		Some(&mut |response| -> Result<()> {
-			let received = Local::now()
-				.naive_utc()
-				.and_utc()
-				.timestamp_nanos_opt()
-				.unwrap_or(0);
-
+			let roundtrip = start.elapsed();
 			let response: PingEntity = response.decode()?;
-			let roundtrip = received - sent;
 			map.entry(response.zid().to_string())
 				.or_insert((response, roundtrip));
 			Ok(())
 		}),
 	)?;
 
-	let result: Vec<(PingEntity, i64)> = map.values().cloned().collect();
+	let result: Vec<(PingEntity, Duration)> = map.values().cloned().collect();
 
 	Ok(result)
 }
@@ -0,0 +1,47 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Log streaming for `DiMAS` control & monitoring programs
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::messages::LogEntity;
+use alloc::string::String;
+use dimas_com::zenoh::Communicator;
+use dimas_core::{Result, message_types::Message, utils::selector_from};
+use zenoh::{Wait, pubsub::Subscriber};
+// endregion:	--- modules
+
+// region:		--- log_subscriber
+/// Subscribe to the log records streamed by `DiMAS` entities matching the `base_selector`.
+///
+/// The `callback` is called for every received record.
+/// The subscription ends, when the returned [`Subscriber`] is dropped.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn log_subscriber<F>(
+	com: &Communicator,
+	base_selector: &String,
+	callback: F,
+) -> Result<Subscriber<()>>
+where
+	F: Fn(LogEntity) + Send + Sync + 'static,
+{
+	let selector = selector_from("log", Some(base_selector));
+	let subscriber = com
+		.session()
+		.declare_subscriber(selector)
+		.callback(move |sample| {
+			let message = Message::new(sample.payload().to_bytes().into_owned());
+			if let Ok(entity) = message.decode::<LogEntity>() {
+				callback(entity);
+			}
+		})
+		.wait()?;
+	Ok(subscriber)
+}
+// endregion:	--- log_subscriber
@@ -10,7 +10,7 @@ extern crate alloc;
 use alloc::string::String;
 use bitcode::{Decode, Encode};
 use core::fmt::Display;
-use dimas_core::enums::OperationState;
+use dimas_core::{enums::OperationState, health::Health};
 // endregion:	--- modules
 
 // region:		--- AboutEntity
@@ -22,14 +22,15 @@ pub struct AboutEntity {
 	kind: String,
 	zid: String,
 	state: OperationState,
+	health: Health,
 }
 
 impl Display for AboutEntity {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		write!(
 			f,
-			"name: {} kind: {} state: {} zid: {}",
-			&self.name, &self.kind, &self.state, &self.zid
+			"name: {} kind: {} state: {} health: {} zid: {}",
+			&self.name, &self.kind, &self.state, &self.health, &self.zid
 		)
 	}
 }
@@ -37,12 +38,19 @@ impl Display for AboutEntity {
 impl AboutEntity {
 	/// Constructor
 	#[must_use]
-	pub const fn new(name: String, kind: String, zid: String, state: OperationState) -> Self {
+	pub const fn new(
+		name: String,
+		kind: String,
+		zid: String,
+		state: OperationState,
+		health: Health,
+	) -> Self {
 		Self {
 			name,
 			kind,
 			zid,
 			state,
+			health,
 		}
 	}
 
@@ -69,5 +77,11 @@ impl AboutEntity {
 	pub const fn state(&self) -> &OperationState {
 		&self.state
 	}
+
+	/// Get the health
+	#[must_use]
+	pub const fn health(&self) -> &Health {
+		&self.health
+	}
 }
 // endregion:	--- AboutEntity
@@ -0,0 +1,103 @@
+// Copyright © 2025 Stephan Kunz
+
+//! A streamed log record of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::enums::LogLevel;
+#[cfg(feature = "std")]
+use dimas_core::utils::LogRecord;
+// endregion:	--- modules
+
+// region:		--- LogEntity
+/// A log record of a `DiMAS` entity
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct LogEntity {
+	name: String,
+	zid: String,
+	timestamp: i64,
+	level: LogLevel,
+	target: String,
+	message: String,
+	fields: Vec<(String, String)>,
+}
+
+impl Display for LogEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"{:5} {} {}: {}",
+			&self.level, &self.name, &self.target, &self.message
+		)?;
+		for (name, value) in &self.fields {
+			write!(f, " {name}={value}")?;
+		}
+		Ok(())
+	}
+}
+
+impl LogEntity {
+	/// Constructor
+	#[cfg(feature = "std")]
+	#[must_use]
+	pub fn new(name: String, zid: String, record: LogRecord) -> Self {
+		Self {
+			name,
+			zid,
+			timestamp: record.timestamp,
+			level: record.level,
+			target: record.target,
+			message: record.message,
+			fields: record.fields,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the utc time in nanoseconds since the unix epoch
+	#[must_use]
+	pub const fn timestamp(&self) -> i64 {
+		self.timestamp
+	}
+
+	/// Get the level
+	#[must_use]
+	pub const fn level(&self) -> LogLevel {
+		self.level
+	}
+
+	/// Get the target
+	#[must_use]
+	pub fn target(&self) -> &str {
+		&self.target
+	}
+
+	/// Get the message
+	#[must_use]
+	pub fn message(&self) -> &str {
+		&self.message
+	}
+
+	/// Get the further fields
+	#[must_use]
+	pub const fn fields(&self) -> &Vec<(String, String)> {
+		&self.fields
+	}
+}
+// endregion:	--- LogEntity
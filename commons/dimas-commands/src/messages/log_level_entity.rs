@@ -0,0 +1,60 @@
+// Copyright © 2025 Stephan Kunz
+
+//! The log streaming level of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::string::String;
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::enums::LogLevel;
+// endregion:	--- modules
+
+// region:		--- LogLevelEntity
+/// The log streaming level of a `DiMAS` entity
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct LogLevelEntity {
+	name: String,
+	zid: String,
+	level: LogLevel,
+}
+
+impl Display for LogLevelEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"name: {} zid: {} level: {}",
+			&self.name, &self.zid, &self.level
+		)
+	}
+}
+
+impl LogLevelEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, zid: String, level: LogLevel) -> Self {
+		Self { name, zid, level }
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the level
+	#[must_use]
+	pub const fn level(&self) -> LogLevel {
+		self.level
+	}
+}
+// endregion:	--- LogLevelEntity
@@ -0,0 +1,62 @@
+// Copyright © 2025 Stephan Kunz
+
+//! The metrics of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::metrics::Metric;
+// endregion:	--- modules
+
+// region:		--- MetricsEntity
+/// A snapshot of the metrics of a `DiMAS` entity
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct MetricsEntity {
+	name: String,
+	zid: String,
+	metrics: Vec<Metric>,
+}
+
+impl Display for MetricsEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"name: {} zid: {} metrics: {}",
+			&self.name,
+			&self.zid,
+			self.metrics.len()
+		)
+	}
+}
+
+impl MetricsEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, zid: String, metrics: Vec<Metric>) -> Self {
+		Self { name, zid, metrics }
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the metrics
+	#[must_use]
+	pub const fn metrics(&self) -> &Vec<Metric> {
+		&self.metrics
+	}
+}
+// endregion:	--- MetricsEntity
@@ -4,10 +4,18 @@
 //! Module `Messages` provides the different messages used with DiMAS.
 
 mod about_entity;
+mod log_entity;
+mod log_level_entity;
+mod metrics_entity;
+mod parameter_entity;
 mod ping_entity;
 mod scouting_entity;
 
 // flatten
 pub use about_entity::*;
+pub use log_entity::*;
+pub use log_level_entity::*;
+pub use metrics_entity::*;
+pub use parameter_entity::*;
 pub use ping_entity::*;
 pub use scouting_entity::*;
@@ -0,0 +1,81 @@
+// Copyright © 2025 Stephan Kunz
+
+//! The parameters of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::parameter::Parameter;
+// endregion:	--- modules
+
+// region:		--- ParameterEntity
+/// The parameters of a `DiMAS` entity,
+/// together with an error message if the request could not be fulfilled
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct ParameterEntity {
+	name: String,
+	zid: String,
+	parameters: Vec<Parameter>,
+	error: Option<String>,
+}
+
+impl Display for ParameterEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "name: {} zid: {}", &self.name, &self.zid)?;
+		for parameter in &self.parameters {
+			write!(f, " {parameter}")?;
+		}
+		if let Some(error) = &self.error {
+			write!(f, " error: {error}")?;
+		}
+		Ok(())
+	}
+}
+
+impl ParameterEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(
+		name: String,
+		zid: String,
+		parameters: Vec<Parameter>,
+		error: Option<String>,
+	) -> Self {
+		Self {
+			name,
+			zid,
+			parameters,
+			error,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the parameters
+	#[must_use]
+	pub const fn parameters(&self) -> &Vec<Parameter> {
+		&self.parameters
+	}
+
+	/// Get the error message
+	#[must_use]
+	pub const fn error(&self) -> Option<&String> {
+		self.error.as_ref()
+	}
+}
+// endregion:	--- ParameterEntity
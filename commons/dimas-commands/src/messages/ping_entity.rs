@@ -7,7 +7,7 @@
 extern crate alloc;
 
 // region:		--- modules
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 use bitcode::{Decode, Encode};
 use core::fmt::Display;
 // endregion:	--- modules
@@ -20,6 +20,7 @@ pub struct PingEntity {
 	name: String,
 	zid: String,
 	oneway: i64,
+	payload: Vec<u8>,
 }
 
 impl Display for PingEntity {
@@ -35,8 +36,13 @@ impl Display for PingEntity {
 impl PingEntity {
 	/// Constructor
 	#[must_use]
-	pub const fn new(name: String, zid: String, oneway: i64) -> Self {
-		Self { name, zid, oneway }
+	pub const fn new(name: String, zid: String, oneway: i64, payload: Vec<u8>) -> Self {
+		Self {
+			name,
+			zid,
+			oneway,
+			payload,
+		}
 	}
 
 	/// Get the Name
@@ -56,5 +62,11 @@ impl PingEntity {
 	pub const fn oneway(&self) -> i64 {
 		self.oneway
 	}
+
+	/// Get the size of the echoed payload
+	#[must_use]
+	pub fn payload_size(&self) -> usize {
+		self.payload.len()
+	}
 }
 // endregion:	--- PingEntity
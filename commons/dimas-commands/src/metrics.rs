@@ -0,0 +1,47 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Metrics commands for `DiMAS` control & monitoring programs
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::messages::MetricsEntity;
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
+use dimas_core::{Result, enums::Signal, message_types::Message, utils::selector_from};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+// endregion:	--- modules
+
+// region:		--- metrics
+/// Collect the metrics of `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn metrics_list(com: &Communicator, base_selector: &String) -> Result<Vec<MetricsEntity>> {
+	let mut map: HashMap<String, MetricsEntity> = HashMap::new();
+
+	let selector = selector_from("signal", Some(base_selector));
+	let message = Message::encode(&Signal::Metrics);
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: MetricsEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<MetricsEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+// endregion:	--- metrics
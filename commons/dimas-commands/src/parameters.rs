@@ -0,0 +1,88 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Parameter commands for `DiMAS` control & monitoring programs
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::messages::ParameterEntity;
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
+use dimas_core::{Result, enums::Signal, message_types::Message, utils::selector_from};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+// endregion:	--- modules
+
+// region:		--- parameters
+/// Send a parameter `signal` to `DiMAS` entities and collect their answers
+#[cfg(feature = "std")]
+fn parameter_request(
+	com: &Communicator,
+	base_selector: &String,
+	signal: &Signal,
+) -> Result<Vec<ParameterEntity>> {
+	let mut map: HashMap<String, ParameterEntity> = HashMap::new();
+
+	let selector = selector_from("signal", Some(base_selector));
+	let message = Message::encode(signal);
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: ParameterEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<ParameterEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+
+/// List the parameters of `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn parameter_list(com: &Communicator, base_selector: &String) -> Result<Vec<ParameterEntity>> {
+	parameter_request(com, base_selector, &Signal::ParameterList)
+}
+
+/// Get a parameter of `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn get_parameter(
+	com: &Communicator,
+	base_selector: &String,
+	name: &str,
+) -> Result<Vec<ParameterEntity>> {
+	let signal = Signal::ParameterGet {
+		name: name.to_string(),
+	};
+	parameter_request(com, base_selector, &signal)
+}
+
+/// Set a parameter of `DiMAS` entities.
+/// The `value` is parsed by the entities according to the parameters type.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn set_parameter(
+	com: &Communicator,
+	base_selector: &String,
+	name: &str,
+	value: &str,
+) -> Result<Vec<ParameterEntity>> {
+	let signal = Signal::ParameterSet {
+		name: name.to_string(),
+		value: value.to_string(),
+	};
+	parameter_request(com, base_selector, &signal)
+}
+// endregion:	--- parameters
@@ -0,0 +1,298 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Statistics for `DiMAS` control & monitoring programs
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::messages::PingEntity;
+use alloc::{
+	collections::BTreeMap,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::time::Duration;
+// endregion:	--- modules
+
+// region:		--- PingStatistics
+/// Latency statistics of repeated pings to a single `DiMAS` entity with a certain payload size
+#[derive(Debug, Clone)]
+pub struct PingStatistics {
+	name: String,
+	zid: String,
+	payload_size: usize,
+	sent: usize,
+	roundtrips: Vec<Duration>,
+}
+
+impl PingStatistics {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, zid: String, payload_size: usize) -> Self {
+		Self {
+			name,
+			zid,
+			payload_size,
+			sent: 0,
+			roundtrips: Vec::new(),
+		}
+	}
+
+	/// Count a sent request
+	pub const fn add_request(&mut self) {
+		self.sent += 1;
+	}
+
+	/// Add the roundtrip time of a received reply
+	pub fn add_reply(&mut self, roundtrip: Duration) {
+		self.roundtrips.push(roundtrip);
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the payload size in bytes
+	#[must_use]
+	pub const fn payload_size(&self) -> usize {
+		self.payload_size
+	}
+
+	/// Get the number of sent requests
+	#[must_use]
+	pub const fn sent(&self) -> usize {
+		self.sent
+	}
+
+	/// Get the number of received replies
+	#[must_use]
+	pub fn received(&self) -> usize {
+		self.roundtrips.len()
+	}
+
+	/// Get the loss in percent
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn loss(&self) -> f64 {
+		if self.sent == 0 {
+			return 0.0;
+		}
+		let lost = self.sent.saturating_sub(self.received());
+		lost as f64 * 100.0 / self.sent as f64
+	}
+
+	/// Get the minimal roundtrip time
+	#[must_use]
+	pub fn min(&self) -> Option<Duration> {
+		self.roundtrips.iter().min().copied()
+	}
+
+	/// Get the maximal roundtrip time
+	#[must_use]
+	pub fn max(&self) -> Option<Duration> {
+		self.roundtrips.iter().max().copied()
+	}
+
+	/// Get the average roundtrip time
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn avg(&self) -> Option<Duration> {
+		if self.roundtrips.is_empty() {
+			return None;
+		}
+		let sum: f64 = self
+			.roundtrips
+			.iter()
+			.map(Duration::as_secs_f64)
+			.sum();
+		Some(Duration::from_secs_f64(sum / self.roundtrips.len() as f64))
+	}
+
+	/// Get the roundtrip time below which `percent` of the replies are, using the nearest rank method
+	#[must_use]
+	pub fn percentile(&self, percent: u8) -> Option<Duration> {
+		if self.roundtrips.is_empty() || percent > 100 {
+			return None;
+		}
+		let mut sorted = self.roundtrips.clone();
+		sorted.sort_unstable();
+		let rank = (usize::from(percent) * sorted.len()).div_ceil(100);
+		sorted.get(rank.saturating_sub(1)).copied()
+	}
+
+	/// Get the standard deviation of the roundtrip times
+	#[cfg(feature = "std")]
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn stddev(&self) -> Option<Duration> {
+		let avg = self.avg()?.as_secs_f64();
+		let variance: f64 = self
+			.roundtrips
+			.iter()
+			.map(|roundtrip| {
+				let diff = roundtrip.as_secs_f64() - avg;
+				diff * diff
+			})
+			.sum::<f64>()
+			/ self.roundtrips.len() as f64;
+		Some(Duration::from_secs_f64(variance.sqrt()))
+	}
+}
+// endregion:	--- PingStatistics
+
+// region:		--- PingSeries
+/// Collects the results of a series of ping rounds per `DiMAS` entity and payload size
+#[derive(Debug, Clone, Default)]
+pub struct PingSeries {
+	/// number of rounds per payload size
+	rounds: BTreeMap<usize, usize>,
+	/// statistics per zid and payload size
+	statistics: BTreeMap<(String, usize), PingStatistics>,
+}
+
+impl PingSeries {
+	/// Constructor
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			rounds: BTreeMap::new(),
+			statistics: BTreeMap::new(),
+		}
+	}
+
+	/// Record the result of a ping round with the given payload size.
+	///
+	/// Every known entity is counted as being pinged, so entities that did not reply are counted as loss.
+	/// Entities replying for the first time are credited with all previous rounds of that payload size.
+	pub fn record(&mut self, payload_size: usize, replies: &[(PingEntity, Duration)]) {
+		let rounds = self.rounds.entry(payload_size).or_insert(0);
+		*rounds += 1;
+		let rounds = *rounds;
+
+		for ((_, size), statistics) in &mut self.statistics {
+			if *size == payload_size {
+				statistics.add_request();
+			}
+		}
+
+		for (entity, roundtrip) in replies {
+			let statistics = self
+				.statistics
+				.entry((entity.zid().to_string(), payload_size))
+				.or_insert_with(|| {
+					let mut statistics = PingStatistics::new(
+						entity.name().to_string(),
+						entity.zid().to_string(),
+						payload_size,
+					);
+					statistics.sent = rounds;
+					statistics
+				});
+			statistics.add_reply(*roundtrip);
+		}
+	}
+
+	/// Get the number of rounds done with the given payload size
+	#[must_use]
+	pub fn rounds(&self, payload_size: usize) -> usize {
+		self.rounds
+			.get(&payload_size)
+			.copied()
+			.unwrap_or(0)
+	}
+
+	/// Get the statistics, sorted by zid and payload size
+	#[must_use]
+	pub fn statistics(&self) -> Vec<PingStatistics> {
+		self.statistics.values().cloned().collect()
+	}
+}
+// endregion:	--- PingSeries
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<PingStatistics>();
+		is_normal::<PingSeries>();
+	}
+
+	fn entity(zid: &str) -> PingEntity {
+		PingEntity::new(zid.to_string(), zid.to_string(), 0, Vec::new())
+	}
+
+	#[test]
+	fn statistics() {
+		let mut statistics = PingStatistics::new("name".into(), "zid".into(), 0);
+		assert!(statistics.avg().is_none());
+		assert!(statistics.percentile(95).is_none());
+		for millis in 1..=10 {
+			statistics.add_request();
+			statistics.add_reply(Duration::from_millis(millis));
+		}
+		statistics.add_request();
+		statistics.add_request();
+
+		assert_eq!(statistics.sent(), 12);
+		assert_eq!(statistics.received(), 10);
+		assert!((statistics.loss() - 16.666).abs() < 0.01);
+		assert_eq!(statistics.min(), Some(Duration::from_millis(1)));
+		assert_eq!(statistics.max(), Some(Duration::from_millis(10)));
+		assert_eq!(statistics.avg().map(|avg| avg.as_micros()), Some(5500));
+		assert_eq!(statistics.percentile(50), Some(Duration::from_millis(5)));
+		assert_eq!(statistics.percentile(95), Some(Duration::from_millis(10)));
+		assert_eq!(statistics.percentile(0), Some(Duration::from_millis(1)));
+		assert_eq!(
+			statistics
+				.stddev()
+				.map(|stddev| stddev.as_micros()),
+			Some(2872)
+		);
+	}
+
+	#[test]
+	fn series() {
+		let mut series = PingSeries::new();
+		series.record(0, &[(entity("a"), Duration::from_millis(1))]);
+		series.record(
+			0,
+			&[
+				(entity("a"), Duration::from_millis(2)),
+				(entity("b"), Duration::from_millis(3)),
+			],
+		);
+		series.record(0, &[]);
+		series.record(64, &[(entity("a"), Duration::from_millis(4))]);
+
+		assert_eq!(series.rounds(0), 3);
+		assert_eq!(series.rounds(64), 1);
+		let statistics = series.statistics();
+		assert_eq!(statistics.len(), 3);
+		assert_eq!(statistics[0].zid(), "a");
+		assert_eq!(statistics[0].payload_size(), 0);
+		assert_eq!(statistics[0].sent(), 3);
+		assert_eq!(statistics[0].received(), 2);
+		assert_eq!(statistics[1].payload_size(), 64);
+		assert_eq!(statistics[1].sent(), 1);
+		assert_eq!(statistics[2].zid(), "b");
+		assert_eq!(statistics[2].sent(), 3);
+		assert_eq!(statistics[2].received(), 1);
+	}
+}
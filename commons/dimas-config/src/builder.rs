@@ -0,0 +1,218 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Builder for a [`Config`] with programmatic overrides.
+//!
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::{Config, Result};
+use alloc::{string::String, vec::Vec};
+// endregion:	--- modules
+
+// region:		--- ConfigBuilder
+/// A builder for a [`Config`].
+///
+/// The configuration is read from the given file or created like [`Config::default`],
+/// then the environment overrides and finally the overrides given to the builder are applied.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigBuilder {
+	#[cfg(feature = "std")]
+	file: Option<String>,
+	mode: Option<String>,
+	connect: Vec<String>,
+	listen: Vec<String>,
+	shared_memory: Option<bool>,
+	shared_memory_pool_size: Option<usize>,
+	values: Vec<(String, String)>,
+	routes: Vec<(String, String)>,
+}
+
+impl ConfigBuilder {
+	/// Use the configuration file named `filename`
+	#[cfg(feature = "std")]
+	#[must_use]
+	pub fn file(mut self, filename: impl Into<String>) -> Self {
+		self.file = Some(filename.into());
+		self
+	}
+
+	/// Set the zenoh mode (`peer`, `client` or `router`)
+	#[must_use]
+	pub fn mode(mut self, mode: impl Into<String>) -> Self {
+		self.mode = Some(mode.into());
+		self
+	}
+
+	/// Add an endpoint to connect to, replacing the configured endpoints
+	#[must_use]
+	pub fn connect(mut self, endpoint: impl Into<String>) -> Self {
+		self.connect.push(endpoint.into());
+		self
+	}
+
+	/// Add an endpoint to listen on, replacing the configured endpoints
+	#[must_use]
+	pub fn listen(mut self, endpoint: impl Into<String>) -> Self {
+		self.listen.push(endpoint.into());
+		self
+	}
+
+	/// Enable or disable the shared memory support, see [`Config::set_shared_memory`]
+	#[must_use]
+	pub const fn shared_memory(mut self, enabled: bool) -> Self {
+		self.shared_memory = Some(enabled);
+		self
+	}
+
+	/// Set the size in bytes of the shared memory pool
+	#[must_use]
+	pub const fn shared_memory_pool_size(mut self, size: usize) -> Self {
+		self.shared_memory_pool_size = Some(size);
+		self
+	}
+
+	/// Set a single value given by a `/` separated `key`, see [`Config::set`]
+	#[must_use]
+	pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+		self.values.push((key.into(), value.into()));
+		self
+	}
+
+	/// Add a route for key expressions matching `selector` to the session named `session`,
+	/// see [`Config::routes`]
+	#[must_use]
+	pub fn route(mut self, selector: impl Into<String>, session: impl Into<String>) -> Self {
+		self.routes
+			.push((selector.into(), session.into()));
+		self
+	}
+
+	/// Set the value of a parameter
+	#[must_use]
+	pub fn parameter(self, name: &str, value: impl Into<String>) -> Self {
+		let key = ["parameters", name].join("/");
+		self.set(key, value)
+	}
+
+	/// Build the [`Config`]
+	///
+	/// # Errors
+	/// Returns a [`std::io::Error`], if the file does not exist or is not accessible.
+	/// Returns an [`Error`](crate::Error) for an invalid override.
+	pub fn build(self) -> Result<Config> {
+		let mut config = self.base()?;
+		if let Some(mode) = self.mode {
+			config.set_mode(&mode)?;
+		}
+		if !self.connect.is_empty() {
+			config.set_connect(&self.connect)?;
+		}
+		if !self.listen.is_empty() {
+			config.set_listen(&self.listen)?;
+		}
+		if let Some(enabled) = self.shared_memory {
+			config.set_shared_memory(enabled)?;
+		}
+		if let Some(size) = self.shared_memory_pool_size {
+			config.set_shared_memory_pool_size(size)?;
+		}
+		for (key, value) in &self.values {
+			config.set(key, value)?;
+		}
+		for (selector, session) in self.routes {
+			config.add_route(selector, session);
+		}
+		Ok(config)
+	}
+
+	/// Create the configuration the overrides are applied to
+	#[cfg(feature = "std")]
+	fn base(&self) -> Result<Config> {
+		self.file
+			.as_ref()
+			.map_or_else(|| Ok(Config::default()), |file| Config::from_file(file))
+	}
+
+	/// Create the configuration the overrides are applied to
+	#[cfg(not(feature = "std"))]
+	#[allow(clippy::unnecessary_wraps)]
+	fn base(&self) -> Result<Config> {
+		Ok(Config::default())
+	}
+}
+// endregion:	--- ConfigBuilder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<ConfigBuilder>();
+	}
+
+	#[test]
+	fn build_with_overrides() -> Result<()> {
+		let config = Config::builder()
+			.mode("client")
+			.connect("tcp/127.0.0.1:7447")
+			.connect("udp/127.0.0.1:7447")
+			.set("zenoh/scouting/multicast/enabled", "false")
+			.parameter("speed", "2.5")
+			.build()?;
+		let zenoh = config.zenoh_config();
+		assert_eq!(zenoh.get_json("mode")?, r#""client""#);
+		assert_eq!(
+			zenoh.get_json("connect/endpoints")?,
+			r#"["tcp/127.0.0.1:7447","udp/127.0.0.1:7447"]"#
+		);
+		assert_eq!(zenoh.get_json("scouting/multicast/enabled")?, "false");
+		assert_eq!(config.parameter("speed").map(String::as_str), Some("2.5"));
+
+		let config = Config::builder()
+			.route("robot/telemetry/**", "field")
+			.build()?;
+		let routes = config.routes().as_ref().ok_or("missing routes")?;
+		assert_eq!(routes.len(), 1);
+		assert_eq!(routes[0].selector, "robot/telemetry/**");
+		assert_eq!(routes[0].session, "field");
+		assert!(
+			Config::builder()
+				.set("unknown/key", "1")
+				.build()
+				.is_err()
+		);
+		assert!(Config::builder().mode("none").build().is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn build_with_shared_memory() -> Result<()> {
+		let config = Config::builder()
+			.shared_memory(true)
+			.shared_memory_pool_size(32 * 1024 * 1024)
+			.build()?;
+		let zenoh = config.zenoh_config();
+		assert_eq!(zenoh.get_json("transport/shared_memory/enabled")?, "true");
+		assert_eq!(
+			zenoh.get_json("transport/shared_memory/transport_optimization/enabled")?,
+			"true"
+		);
+		assert_eq!(
+			zenoh.get_json("transport/shared_memory/transport_optimization/pool_size")?,
+			"33554432"
+		);
+		assert!(
+			Config::builder()
+				.shared_memory_pool_size(0)
+				.build()
+				.is_err()
+		);
+		Ok(())
+	}
+}
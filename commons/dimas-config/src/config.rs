@@ -40,6 +40,51 @@
 //!  - local config directory (`Linux`: `$XDG_CONFIG_HOME/dimas` or `$HOME/.config/dimas` | `Windows`: `{FOLDERID_LocalAppData}/dimas` | `MacOS`: `$HOME/Library/Application Support/dimas`)
 //!  - config directory (`Linux`: `$XDG_CONFIG_HOME/dimas` or `$HOME/.config/dimas` | `Windows`: `{FOLDERID_RoamingAppData}/dimas` | `MacOS`: `$HOME/Library/Application Support/dimas`)
 //!
+//! # Environment overrides
+//! The methods using files apply following environment variables on top of the read configuration:
+//!  - `DIMAS_CONFIG`: the file used by [`Config::default`] instead of `default.json5`
+//!  - `DIMAS_MODE`: the zenoh mode, one of `peer`, `client` or `router`
+//!  - `DIMAS_CONNECT`: comma separated list of endpoints to connect to
+//!  - `DIMAS_LISTEN`: comma separated list of endpoints to listen on
+//!  - `DIMAS__<key>`: a single value, with key segments separated by `__`,
+//!    e.g. `DIMAS__zenoh__scouting__multicast__enabled=false`, `DIMAS__parameters__speed=2.5` or
+//!    `DIMAS__tracing__format=json`
+//!
+//! Overrides given programmatically with the [`ConfigBuilder`] take precedence over the environment.
+//! ```rust,no_run
+//! # use dimas_config::Config;
+//! # fn main() -> Result<(), Box<dyn core::error::Error + Send + Sync + 'static>> {
+//! let config = Config::builder()
+//!     .file("client.json5")
+//!     .connect("tcp/192.168.1.10:7447")
+//!     .set("zenoh/scouting/multicast/enabled", "false")
+//!     .parameter("speed", "2.5")
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Tracing
+//! How the records of `tracing` are written is configured in a `tracing` section,
+//! see [`Tracing`] for its content.
+//!
+//! # Sessions and routes
+//! Besides the default session, additional sessions can be configured in a `sessions` section.
+//! Messages without an explicit session are sent via the session of the first matching route
+//! in the `routes` section or via the default session.
+//! Sessions with protocol `mqtt` connect to an MQTT broker, they need the feature `mqtt` of `dimas-com`.
+//! ```json5
+//! {
+//!   sessions: [
+//!     { protocol: "zenoh", name: "field", config: { mode: "client" } },
+//!     { protocol: "mqtt", name: "iot", mqtt: { host: "localhost", port: 1883 } },
+//!   ],
+//!   routes: [
+//!     { selector: "robot/telemetry/**", session: "field" },
+//!   ],
+//! }
+//! ```
+//!
 
 #[doc(hidden)]
 extern crate alloc;
@@ -48,23 +93,217 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::Result;
 use crate::utils::{find_config_file, read_config_file};
-use alloc::vec::Vec;
+use crate::{ConfigBuilder, Error, Result, Tracing};
+use alloc::{
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::fmt::Formatter;
+use serde::{
+	Deserializer,
+	de::{self, IgnoredAny, MapAccess, Visitor},
+};
+#[cfg(feature = "std")]
+use std::env;
 #[cfg(feature = "std")]
 use tracing::{debug, warn};
 // endregion:	--- modules
 
+// region:		--- overrides
+/// Environment variable with the configuration file to use by [`Config::default`]
+#[cfg(feature = "std")]
+const CONFIG_VAR: &str = "DIMAS_CONFIG";
+/// Environment variable overriding the zenoh mode
+#[cfg(feature = "std")]
+const MODE_VAR: &str = "DIMAS_MODE";
+/// Environment variable overriding the comma separated endpoints to connect to
+#[cfg(feature = "std")]
+const CONNECT_VAR: &str = "DIMAS_CONNECT";
+/// Environment variable overriding the comma separated endpoints to listen on
+#[cfg(feature = "std")]
+const LISTEN_VAR: &str = "DIMAS_LISTEN";
+/// Prefix of environment variables overriding a single value,
+/// the key segments are separated by `__`
+#[cfg(feature = "std")]
+const OVERRIDE_PREFIX: &str = "DIMAS__";
+
+/// Split a comma separated list of endpoints
+#[cfg(feature = "std")]
+fn split_endpoints(endpoints: &str) -> Vec<String> {
+	endpoints
+		.split(',')
+		.map(str::trim)
+		.filter(|endpoint| !endpoint.is_empty())
+		.map(ToString::to_string)
+		.collect()
+}
+
+/// Create a json5 list of strings
+fn json5_list(values: &[String]) -> String {
+	let values: Vec<String> = values
+		.iter()
+		.map(|value| json5_string(value))
+		.collect();
+	format!("[{}]", values.join(","))
+}
+
+/// Create a json5 string
+fn json5_string(value: &str) -> String {
+	format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Use a valid json5 value as it is, otherwise as a string
+fn json5_value(value: &str) -> String {
+	if json5::from_str::<IgnoredAny>(value).is_ok() {
+		value.to_string()
+	} else {
+		json5_string(value)
+	}
+}
+// endregion:	--- overrides
+
+// region:		--- parameters
+/// Deserializes the `parameters` section, a map with scalar values,
+/// into their text representation.
+/// The values are parsed according to the type of the declared parameter.
+fn deserialize_parameters<'de, D>(
+	deserializer: D,
+) -> core::result::Result<Option<BTreeMap<String, String>>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	struct ScalarText;
+
+	impl Visitor<'_> for ScalarText {
+		type Value = String;
+
+		fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+			formatter.write_str("a boolean, a number or a string")
+		}
+
+		fn visit_bool<E: de::Error>(self, value: bool) -> core::result::Result<String, E> {
+			Ok(value.to_string())
+		}
+
+		fn visit_i64<E: de::Error>(self, value: i64) -> core::result::Result<String, E> {
+			Ok(value.to_string())
+		}
+
+		fn visit_u64<E: de::Error>(self, value: u64) -> core::result::Result<String, E> {
+			Ok(value.to_string())
+		}
+
+		fn visit_f64<E: de::Error>(self, value: f64) -> core::result::Result<String, E> {
+			Ok(value.to_string())
+		}
+
+		fn visit_str<E: de::Error>(self, value: &str) -> core::result::Result<String, E> {
+			Ok(value.to_string())
+		}
+	}
+
+	struct Scalar(String);
+
+	impl<'de> serde::Deserialize<'de> for Scalar {
+		fn deserialize<D: Deserializer<'de>>(
+			deserializer: D,
+		) -> core::result::Result<Self, D::Error> {
+			deserializer
+				.deserialize_any(ScalarText)
+				.map(Scalar)
+		}
+	}
+
+	struct Parameters;
+
+	impl<'de> Visitor<'de> for Parameters {
+		type Value = BTreeMap<String, String>;
+
+		fn expecting(&self, formatter: &mut Formatter<'_>) -> core::fmt::Result {
+			formatter.write_str("a map of parameter names to values")
+		}
+
+		fn visit_map<A: MapAccess<'de>>(
+			self,
+			mut map: A,
+		) -> core::result::Result<Self::Value, A::Error> {
+			let mut result = BTreeMap::new();
+			while let Some((name, Scalar(value))) = map.next_entry::<String, Scalar>()? {
+				result.insert(name, value);
+			}
+			Ok(result)
+		}
+	}
+
+	deserializer.deserialize_map(Parameters).map(Some)
+}
+// endregion:	--- parameters
+
 // region:		--- Session
 #[derive(Debug, Default, Clone, serde::Deserialize)]
 pub struct Session {
 	pub protocol: alloc::string::String,
 	pub name: alloc::string::String,
-	#[serde(deserialize_with = "zenoh::Config::deserialize")]
+	#[serde(default, deserialize_with = "zenoh::Config::deserialize")]
 	pub config: zenoh::Config,
+	/// the settings of a session with protocol `mqtt`
+	#[serde(default)]
+	pub mqtt: Option<Mqtt>,
 }
 // endregion:	--- Session
 
+// region:		--- Mqtt
+/// The settings of a session with protocol `mqtt`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Mqtt {
+	/// the host of the broker
+	pub host: String,
+	/// the port of the broker
+	pub port: u16,
+	/// the client id, a random one is used if not given
+	pub client_id: Option<String>,
+	/// the keep alive interval in seconds
+	pub keep_alive: u64,
+	/// the user name for authentication at the broker
+	pub username: Option<String>,
+	/// the password for authentication at the broker
+	pub password: Option<String>,
+	/// whether put messages are retained by the broker
+	pub retain: bool,
+}
+
+impl Default for Mqtt {
+	/// A broker on `localhost` at the default port 1883
+	fn default() -> Self {
+		Self {
+			host: String::from("localhost"),
+			port: 1883,
+			client_id: None,
+			keep_alive: 5,
+			username: None,
+			password: None,
+			retain: false,
+		}
+	}
+}
+// endregion:	--- Mqtt
+
+// region:		--- Route
+/// A route sending the messages for key expressions matching `selector` via the session named `session`
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+	/// the key expression pattern
+	pub selector: String,
+	/// the name of the session, `default` for the default session
+	pub session: String,
+}
+// endregion:	--- Route
+
 // region:		--- Config
 /// Manages the configuration
 //#[repr(transparent)]
@@ -73,15 +312,18 @@ pub struct Config {
 	#[serde(deserialize_with = "zenoh::Config::deserialize")]
 	zenoh: zenoh::Config,
 	sessions: Option<Vec<Session>>,
+	routes: Option<Vec<Route>>,
+	#[serde(default, deserialize_with = "deserialize_parameters")]
+	parameters: Option<BTreeMap<String, String>>,
+	#[serde(default)]
+	tracing: Tracing,
 }
 
 #[cfg(not(feature = "std"))]
 impl Default for Config {
 	/// Create a default configuration
 	fn default() -> Self {
-		Self {
-			zenoh: zenoh::Config::default(),
-		}
+		Self::minimal()
 	}
 }
 
@@ -89,47 +331,44 @@ impl Default for Config {
 impl Default for Config {
 	/// Create a default configuration
 	///
-	/// Will search for a configuration file with name "default.json5" in the directories mentioned in [`Examples`](index.html#examples).
+	/// Will search for a configuration file with name "default.json5" in the directories mentioned in [`Examples`](index.html#examples),
+	/// or use the file given in the environment variable `DIMAS_CONFIG`.
 	/// This file should contain the wanted default configuration.
 	/// If no file is found, it will create a defined minimal default configuration.
 	/// Currently this is just a default zenoh peer configuration which connects to peers in same subnet.
-	#[allow(clippy::cognitive_complexity)]
+	/// The [environment overrides](index.html#environment-overrides) are applied on top.
 	fn default() -> Self {
-		match find_config_file("default.json5") {
-			Ok(path) => {
-				debug!("trying file {:?}", &path);
-				match read_config_file(&path) {
-					Ok(content) => match json5::from_str(&content) {
-						Ok(result) => result,
-						Err(error) => {
-							warn!("{}, using default dimas configuration instead", error);
-							Self {
-								zenoh: zenoh::Config::default(),
-								sessions: None,
-							}
-						}
-					},
-					Err(error) => {
-						warn!("{}, using default dimas configuration instead", error);
-						Self {
-							zenoh: zenoh::Config::default(),
-							sessions: None,
-						}
-					}
-				}
-			}
-			Err(error) => {
-				warn!("{}, using default dimas configuration instead", error);
-				Self {
-					zenoh: zenoh::Config::default(),
-					sessions: None,
-				}
-			}
+		let filename = env::var(CONFIG_VAR).unwrap_or_else(|_| String::from("default.json5"));
+		let mut config = Self::from_path(&filename).unwrap_or_else(|error| {
+			warn!("{}, using default dimas configuration instead", error);
+			Self::minimal()
+		});
+		if let Err(error) = config.apply_env() {
+			warn!("{}, ignoring further environment overrides", error);
 		}
+		config
 	}
 }
 
 impl Config {
+	/// Create a minimal configuration without reading any file
+	#[must_use]
+	pub fn minimal() -> Self {
+		Self {
+			zenoh: zenoh::Config::default(),
+			sessions: None,
+			routes: None,
+			parameters: None,
+			tracing: Tracing::default(),
+		}
+	}
+
+	/// Create a [`ConfigBuilder`] for programmatic construction of a configuration
+	#[must_use]
+	pub fn builder() -> ConfigBuilder {
+		ConfigBuilder::default()
+	}
+
 	/// Create a configuration based on file named `local.json5`.
 	///
 	/// Will search in the directories mentioned in [`Examples`](index.html#examples).
@@ -139,12 +378,7 @@ impl Config {
 	/// Returns a [`std::io::Error`], if file does not exist in any of the places or is not accessible.
 	#[cfg(feature = "std")]
 	pub fn local() -> Result<Self> {
-		let path = find_config_file("local.json5")?;
-		#[cfg(feature = "std")]
-		debug!("using file {:?}", &path);
-		let content = read_config_file(&path)?;
-		let cfg = json5::from_str(&content)?;
-		Ok(cfg)
+		Self::from_file("local.json5")
 	}
 
 	/// Create a configuration based on file named `client.json5`.
@@ -156,11 +390,7 @@ impl Config {
 	/// Returns a [`std::io::Error`], if file does not exist in any of the places or is not accessible.
 	#[cfg(feature = "std")]
 	pub fn client() -> Result<Self> {
-		let path = find_config_file("client.json5")?;
-		debug!("using file {:?}", &path);
-		let content = read_config_file(&path)?;
-		let cfg = json5::from_str(&content)?;
-		Ok(cfg)
+		Self::from_file("client.json5")
 	}
 
 	/// Create a configuration based on file named `peer.json5`.
@@ -172,11 +402,7 @@ impl Config {
 	/// Returns a [`std::io::Error`], if file does not exist in any of the places or is not accessible.
 	#[cfg(feature = "std")]
 	pub fn peer() -> Result<Self> {
-		let path = find_config_file("peer.json5")?;
-		debug!("using file {:?}", &path);
-		let content = read_config_file(&path)?;
-		let cfg = json5::from_str(&content)?;
-		Ok(cfg)
+		Self::from_file("peer.json5")
 	}
 
 	/// Create a configuration based on file named `router.json5`.
@@ -188,29 +414,140 @@ impl Config {
 	/// Returns a [`std::io::Error`], if file does not exist in any of the places or is not accessible.
 	#[cfg(feature = "std")]
 	pub fn router() -> Result<Self> {
-		let path = find_config_file("router.json5")?;
-		debug!("using file {:?}", &path);
-		let content = read_config_file(&path)?;
-
-		let cfg = json5::from_str(&content)?;
-		Ok(cfg)
+		Self::from_file("router.json5")
 	}
 
 	/// Create a configuration based on file with given filename.
 	///
 	/// Will search in the directories mentioned in [`Examples`](index.html#examples).
+	/// The [environment overrides](index.html#environment-overrides) are applied on top.
 	///
 	/// # Errors
 	/// Returns a [`std::io::Error`], if file does not exist in any of the places or is not accessible.
+	/// Returns an [`Error`] for an invalid override.
 	#[cfg(feature = "std")]
 	pub fn from_file(filename: &str) -> Result<Self> {
+		let mut config = Self::from_path(filename)?;
+		config.apply_env()?;
+		Ok(config)
+	}
+
+	/// Read the configuration from file without applying overrides.
+	/// # Errors
+	#[cfg(feature = "std")]
+	fn from_path(filename: &str) -> Result<Self> {
 		let path = find_config_file(filename)?;
 		debug!("using file {:?}", &path);
 		let content = read_config_file(&path)?;
-		let cfg = json5::from_str(&content)?;
+		let cfg = json5::from_str(&content).map_err(|error| {
+			Error::InvalidConfig(path.to_string_lossy().to_string(), error.to_string())
+		})?;
 		Ok(cfg)
 	}
 
+	/// Apply the [environment overrides](index.html#environment-overrides).
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an invalid override.
+	#[cfg(feature = "std")]
+	pub fn apply_env(&mut self) -> Result<()> {
+		if let Ok(mode) = env::var(MODE_VAR) {
+			self.set_mode(&mode)?;
+		}
+		if let Ok(endpoints) = env::var(CONNECT_VAR) {
+			self.set_connect(&split_endpoints(&endpoints))?;
+		}
+		if let Ok(endpoints) = env::var(LISTEN_VAR) {
+			self.set_listen(&split_endpoints(&endpoints))?;
+		}
+		let mut overrides: Vec<(String, String)> = env::vars()
+			.filter_map(|(name, value)| {
+				name.strip_prefix(OVERRIDE_PREFIX)
+					.map(|key| (key.replace("__", "/"), value))
+			})
+			.collect();
+		overrides.sort();
+		for (key, value) in overrides {
+			self.set(&key, &value)?;
+		}
+		Ok(())
+	}
+
+	/// Set the zenoh mode (`peer`, `client` or `router`)
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an invalid mode.
+	pub fn set_mode(&mut self, mode: &str) -> Result<()> {
+		self.set("zenoh/mode", mode)
+	}
+
+	/// Set the endpoints to connect to
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an invalid endpoint.
+	pub fn set_connect(&mut self, endpoints: &[String]) -> Result<()> {
+		self.set("zenoh/connect/endpoints", &json5_list(endpoints))
+	}
+
+	/// Set the endpoints to listen on
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an invalid endpoint.
+	pub fn set_listen(&mut self, endpoints: &[String]) -> Result<()> {
+		self.set("zenoh/listen/endpoints", &json5_list(endpoints))
+	}
+
+	/// Enable or disable zenoh's shared memory support including the shared memory provider
+	/// used by publishers, which needs the `shared-memory` feature of `dimas`.
+	///
+	/// # Errors
+	/// Returns an [`Error`], if the value can not be set.
+	pub fn set_shared_memory(&mut self, enabled: bool) -> Result<()> {
+		let value = enabled.to_string();
+		self.set("zenoh/transport/shared_memory/enabled", &value)?;
+		self.set(
+			"zenoh/transport/shared_memory/transport_optimization/enabled",
+			&value,
+		)
+	}
+
+	/// Set the size in bytes of the shared memory pool
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an invalid size.
+	pub fn set_shared_memory_pool_size(&mut self, size: usize) -> Result<()> {
+		self.set(
+			"zenoh/transport/shared_memory/transport_optimization/pool_size",
+			&size.to_string(),
+		)
+	}
+
+	/// Set a single value given by a `/` separated `key`.
+	///
+	/// Keys starting with `zenoh/` address the zenoh configuration,
+	/// keys starting with `parameters/` a parameter value
+	/// and keys starting with `tracing/` a value of the [`Tracing`] settings.
+	/// The `value` is interpreted as json5, if that is not possible as a string.
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an unknown key or an invalid value.
+	pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+		match key.split_once('/') {
+			Some(("zenoh", zenoh_key)) => self
+				.zenoh
+				.insert_json5(zenoh_key, &json5_value(value))
+				.map_err(|error| Error::InvalidOverride(key.to_string(), error.to_string()).into()),
+			Some(("parameters", name)) if !name.is_empty() => {
+				self.parameters
+					.get_or_insert_with(BTreeMap::new)
+					.insert(name.to_string(), value.to_string());
+				Ok(())
+			}
+			Some(("tracing", tracing_key)) => self.tracing.set(tracing_key, &json5_value(value)),
+			_ => Err(Error::UnknownOverride(key.to_string()).into()),
+		}
+	}
+
 	/// Method to extract the zenoh configuration from [`Config`].
 	///
 	/// Can be passed to `zenoh::open()`.
@@ -225,12 +562,47 @@ impl Config {
 	pub const fn sessions(&self) -> &Option<Vec<Session>> {
 		&self.sessions
 	}
+
+	/// Method to get access to the routes in [`Config`].
+	///
+	/// The first route whose selector includes a key expression determines its session.
+	#[must_use]
+	pub const fn routes(&self) -> &Option<Vec<Route>> {
+		&self.routes
+	}
+
+	/// Add a route for key expressions matching `selector` to the session named `session`
+	pub fn add_route(&mut self, selector: impl Into<String>, session: impl Into<String>) {
+		self.routes
+			.get_or_insert_with(Vec::new)
+			.push(Route {
+				selector: selector.into(),
+				session: session.into(),
+			});
+	}
+
+	/// Method to get the [`Tracing`] settings in [`Config`].
+	#[must_use]
+	pub const fn tracing(&self) -> &Tracing {
+		&self.tracing
+	}
+
+	/// Method to get the configured value of a parameter in its text representation.
+	///
+	/// The values are given in the `parameters` section as booleans, numbers or strings.
+	#[must_use]
+	pub fn parameter(&self, name: &str) -> Option<&String> {
+		self.parameters
+			.as_ref()
+			.and_then(|parameters| parameters.get(name))
+	}
 }
 // endregion:	--- Config
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::TracingFormat;
 
 	// check, that the auto traits are available
 	const fn is_normal<T: Sized + Send + Sync>() {}
@@ -280,6 +652,123 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn config_parameters() -> Result<()> {
+		let config: Config = json5::from_str(
+			r#"{ zenoh: {}, parameters: { flag: true, count: -3, speed: 1.5, label: "x" } }"#,
+		)?;
+		assert_eq!(config.parameter("flag").map(String::as_str), Some("true"));
+		assert_eq!(config.parameter("count").map(String::as_str), Some("-3"));
+		assert_eq!(config.parameter("speed").map(String::as_str), Some("1.5"));
+		assert_eq!(config.parameter("label").map(String::as_str), Some("x"));
+		assert!(config.parameter("other").is_none());
+		Ok(())
+	}
+
+	#[test]
+	fn config_set() -> Result<()> {
+		let mut config = Config::minimal();
+		config.set("zenoh/mode", "router")?;
+		config.set("zenoh/scouting/multicast/enabled", "false")?;
+		config.set_listen(&[String::from("tcp/0.0.0.0:7447")])?;
+		config.set("parameters/label", "a \"b\"")?;
+		config.set("tracing/format", "json")?;
+		config.set("tracing/filter", "warn,dimas=debug")?;
+		assert_eq!(config.zenoh.get_json("mode")?, r#""router""#);
+		assert_eq!(
+			config
+				.zenoh
+				.get_json("scouting/multicast/enabled")?,
+			"false"
+		);
+		assert_eq!(
+			config.zenoh.get_json("listen/endpoints")?,
+			r#"["tcp/0.0.0.0:7447"]"#
+		);
+		assert_eq!(
+			config.parameter("label").map(String::as_str),
+			Some("a \"b\"")
+		);
+		assert_eq!(config.tracing().format, TracingFormat::Json);
+		assert_eq!(config.tracing().directives(), "warn,dimas=debug");
+		assert!(config.set("zenoh/mode", "none").is_err());
+		assert!(config.set("tracing/format", "xml").is_err());
+		assert!(config.set("parameters/", "1").is_err());
+		assert!(config.set("other", "1").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn config_shared_memory() -> Result<()> {
+		let mut config = Config::minimal();
+		config.set_shared_memory(true)?;
+		config.set_shared_memory_pool_size(4 * 1024 * 1024)?;
+		assert_eq!(
+			config
+				.zenoh
+				.get_json("transport/shared_memory/enabled")?,
+			"true"
+		);
+		assert_eq!(
+			config
+				.zenoh
+				.get_json("transport/shared_memory/transport_optimization/enabled")?,
+			"true"
+		);
+		assert_eq!(
+			config
+				.zenoh
+				.get_json("transport/shared_memory/transport_optimization/pool_size")?,
+			"4194304"
+		);
+
+		config.set_shared_memory(false)?;
+		assert_eq!(
+			config
+				.zenoh
+				.get_json("transport/shared_memory/transport_optimization/enabled")?,
+			"false"
+		);
+		Ok(())
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn config_split_endpoints() {
+		assert_eq!(
+			split_endpoints(" tcp/a:1, ,udp/b:2 "),
+			[String::from("tcp/a:1"), String::from("udp/b:2")]
+		);
+	}
+
+	#[test]
+	fn config_sessions() -> Result<()> {
+		let config: Config = json5::from_str(
+			r#"{ zenoh: {}, sessions: [
+				{ protocol: "zenoh", name: "field", config: { mode: "client" } },
+				{ protocol: "mqtt", name: "iot", mqtt: { host: "broker", retain: true } },
+			] }"#,
+		)?;
+		let sessions = config.sessions().as_ref().ok_or("no sessions")?;
+		assert_eq!(sessions.len(), 2);
+		assert!(sessions[0].mqtt.is_none());
+		assert_eq!(
+			sessions[1].mqtt,
+			Some(Mqtt {
+				host: String::from("broker"),
+				retain: true,
+				..Mqtt::default()
+			})
+		);
+		assert!(
+			json5::from_str::<Config>(
+				r#"{ zenoh: {}, sessions: [ { protocol: "mqtt", name: "iot", mqtt: { other: 1 } } ] }"#
+			)
+			.is_err()
+		);
+		Ok(())
+	}
+
 	#[cfg(feature = "std")]
 	#[test]
 	fn config_from_file_fails() {
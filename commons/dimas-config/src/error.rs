@@ -15,9 +15,30 @@ use thiserror::Error;
 /// `dimas-time` error type.
 #[derive(Error, Debug)]
 pub enum Error {
-	/// ivalid #include directive
-	#[error("invalid '#include' at {0} in {0}")]
-	InvalidInclude(String),
+	/// invalid #include directive
+	#[error("invalid '#include' in {0}:{1}: {2}")]
+	InvalidInclude(String, usize, String),
+	/// #include directive including a file that is already being included
+	#[error("cyclic '#include' in {0}:{1}: {2} is already being included")]
+	IncludeCycle(String, usize, String),
+	/// comment without end
+	#[error("unterminated comment in {0}:{1}")]
+	UnterminatedComment(String, usize),
+	/// string without end
+	#[error("unterminated string in {0}:{1}")]
+	UnterminatedString(String, usize),
+	/// invalid json5 syntax
+	#[error("invalid syntax in {0}:{1}: {2}")]
+	InvalidSyntax(String, usize, String),
+	/// content not matching the configuration
+	#[error("invalid configuration in {0}: {1}")]
+	InvalidConfig(String, String),
+	/// invalid value for a configuration override
+	#[error("invalid value for override '{0}': {1}")]
+	InvalidOverride(String, String),
+	/// unknown key of a configuration override
+	#[error("unknown key for override '{0}'")]
+	UnknownOverride(String),
 }
 // region:		--- Error
 
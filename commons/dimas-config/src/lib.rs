@@ -4,13 +4,19 @@
 //! Library for configuration
 //!
 
+mod builder;
 mod config;
 mod error;
+mod logging;
 mod utils;
 
 // flatten
-pub use config::Config;
+pub use builder::ConfigBuilder;
+pub use config::{Config, Mqtt, Route};
 pub use error::Error;
+pub use logging::{Rotation, Tracing, TracingFile, TracingFormat};
+#[cfg(feature = "std")]
+pub use utils::{persistence_file, read_config_file};
 
 // region:		--- types
 #[doc(hidden)]
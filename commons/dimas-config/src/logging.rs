@@ -0,0 +1,250 @@
+// Copyright © 2025 Stephan Kunz
+
+//! The `tracing` section of the configuration.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::{Error, Result};
+use alloc::{
+	boxed::Box,
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use serde::{Deserialize, de::DeserializeOwned};
+// endregion:	--- modules
+
+// region:		--- TracingFormat
+/// The format of the written records
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TracingFormat {
+	/// Single line records with all details
+	#[default]
+	Full,
+	/// Shortened single line records
+	Compact,
+	/// Multi line records for humans
+	Pretty,
+	/// Records as JSON objects, one per line
+	Json,
+}
+// endregion:	--- TracingFormat
+
+// region:		--- Rotation
+/// How often a new log file is started
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+	/// Every minute
+	Minutely,
+	/// Every hour
+	Hourly,
+	/// Every day
+	#[default]
+	Daily,
+	/// Never, all records go into a single file
+	Never,
+}
+// endregion:	--- Rotation
+
+// region:		--- TracingFile
+/// The output of the records into rotated files
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TracingFile {
+	/// the directory of the files
+	pub directory: String,
+	/// the name of the files, extended by the date and time of their start if rotated
+	pub prefix: String,
+	/// how often a new file is started
+	pub rotation: Rotation,
+	/// the number of files to keep, all are kept if not given
+	pub max_files: Option<usize>,
+}
+
+impl Default for TracingFile {
+	/// Daily rotated files `dimas.log` in directory `log`
+	fn default() -> Self {
+		Self {
+			directory: String::from("log"),
+			prefix: String::from("dimas.log"),
+			rotation: Rotation::Daily,
+			max_files: None,
+		}
+	}
+}
+// endregion:	--- TracingFile
+
+// region:		--- Tracing
+/// The settings for writing the records of `tracing`, used by `init_tracing_from`.
+///
+/// ```json5
+/// {
+///   tracing: {
+///     // directives in the format of `RUST_LOG`, the environment variable takes precedence
+///     filter: "info",
+///     // levels per target, added to the filter
+///     targets: { zenoh: "warn", my_agent: "debug" },
+///     // one of "full", "compact", "pretty" or "json"
+///     format: "full",
+///     // whether the records are written to stdout
+///     stdout: true,
+///     // optional output into files rotated "minutely", "hourly", "daily" or "never"
+///     file: { directory: "log", prefix: "agent.log", rotation: "daily", max_files: 7 },
+///   },
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tracing {
+	/// the directives in the format of `RUST_LOG`
+	pub filter: String,
+	/// the levels per target
+	pub targets: BTreeMap<String, String>,
+	/// the format of the records
+	pub format: TracingFormat,
+	/// whether the records are written to stdout
+	pub stdout: bool,
+	/// the optional output into files
+	pub file: Option<TracingFile>,
+}
+
+impl Default for Tracing {
+	/// Records up to level `INFO` in the full format to stdout
+	fn default() -> Self {
+		Self {
+			filter: String::from("info"),
+			targets: BTreeMap::new(),
+			format: TracingFormat::Full,
+			stdout: true,
+			file: None,
+		}
+	}
+}
+
+impl Tracing {
+	/// Get the directives of the filter followed by those of the targets
+	#[must_use]
+	pub fn directives(&self) -> String {
+		let mut directives: Vec<String> = self
+			.filter
+			.split(',')
+			.map(str::trim)
+			.filter(|directive| !directive.is_empty())
+			.map(ToString::to_string)
+			.collect();
+		directives.extend(
+			self.targets
+				.iter()
+				.map(|(target, level)| format!("{target}={level}")),
+		);
+		directives.join(",")
+	}
+
+	/// Set a single value given by a `/` separated `key` relative to the section,
+	/// e.g. `format` or `targets/zenoh`. The `value` is json5.
+	///
+	/// # Errors
+	/// Returns an [`Error`] for an unknown key or an invalid value.
+	pub(crate) fn set(&mut self, key: &str, value: &str) -> Result<()> {
+		let invalid = |error: json5::Error| -> Box<dyn core::error::Error + Send + Sync> {
+			Error::InvalidOverride(format!("tracing/{key}"), error.to_string()).into()
+		};
+		match key.split_once('/') {
+			None => match key {
+				"filter" => self.filter = parse(value).map_err(invalid)?,
+				"format" => self.format = parse(value).map_err(invalid)?,
+				"stdout" => self.stdout = parse(value).map_err(invalid)?,
+				_ => return Err(Error::UnknownOverride(format!("tracing/{key}")).into()),
+			},
+			Some(("targets", target)) if !target.is_empty() => {
+				let level = parse(value).map_err(invalid)?;
+				self.targets.insert(target.to_string(), level);
+			}
+			Some(("file", file_key)) => {
+				let file = self.file.get_or_insert_with(TracingFile::default);
+				match file_key {
+					"directory" => file.directory = parse(value).map_err(invalid)?,
+					"prefix" => file.prefix = parse(value).map_err(invalid)?,
+					"rotation" => file.rotation = parse(value).map_err(invalid)?,
+					"max_files" => file.max_files = parse(value).map_err(invalid)?,
+					_ => return Err(Error::UnknownOverride(format!("tracing/{key}")).into()),
+				}
+			}
+			_ => return Err(Error::UnknownOverride(format!("tracing/{key}")).into()),
+		}
+		Ok(())
+	}
+}
+
+/// Parse a json5 `value`
+fn parse<T: DeserializeOwned>(value: &str) -> core::result::Result<T, json5::Error> {
+	json5::from_str(value)
+}
+// endregion:	--- Tracing
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Tracing>();
+		is_normal::<TracingFile>();
+		is_normal::<TracingFormat>();
+		is_normal::<Rotation>();
+	}
+
+	#[test]
+	fn tracing_section() -> Result<()> {
+		let tracing: Tracing = json5::from_str(
+			r#"{
+				filter: "warn, dimas=debug",
+				targets: { zenoh: "error" },
+				format: "json",
+				file: { directory: "/tmp/logs", rotation: "hourly", max_files: 3 },
+			}"#,
+		)?;
+		assert_eq!(tracing.format, TracingFormat::Json);
+		assert!(tracing.stdout);
+		assert_eq!(tracing.directives(), "warn,dimas=debug,zenoh=error");
+		let file = tracing.file.ok_or("missing file")?;
+		assert_eq!(file.directory, "/tmp/logs");
+		assert_eq!(file.prefix, "dimas.log");
+		assert_eq!(file.rotation, Rotation::Hourly);
+		assert_eq!(file.max_files, Some(3));
+
+		assert!(json5::from_str::<Tracing>("{ format: \"xml\" }").is_err());
+		assert!(json5::from_str::<Tracing>("{ level: \"info\" }").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn tracing_set() -> Result<()> {
+		let mut tracing = Tracing::default();
+		tracing.set("format", "\"pretty\"")?;
+		tracing.set("stdout", "false")?;
+		tracing.set("targets/zenoh", "\"warn\"")?;
+		tracing.set("file/rotation", "\"never\"")?;
+		assert_eq!(tracing.format, TracingFormat::Pretty);
+		assert!(!tracing.stdout);
+		assert_eq!(tracing.directives(), "info,zenoh=warn");
+		assert_eq!(
+			tracing.file.as_ref().map(|file| file.rotation),
+			Some(Rotation::Never)
+		);
+
+		assert!(tracing.set("format", "\"xml\"").is_err());
+		assert!(tracing.set("level", "\"info\"").is_err());
+		assert!(tracing.set("targets/", "\"info\"").is_err());
+		assert!(tracing.set("file/size", "10").is_err());
+		Ok(())
+	}
+}
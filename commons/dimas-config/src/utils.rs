@@ -12,81 +12,209 @@ use alloc::{
 	boxed::Box,
 	format,
 	string::{String, ToString},
+	vec::Vec,
 };
 #[cfg(feature = "std")]
 use dirs::{config_dir, config_local_dir, home_dir};
 #[cfg(feature = "std")]
 use std::{
 	env,
-	fs::File,
-	io::{BufRead, BufReader},
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 // endregion:	--- modules
 
 // region:		--- utils
+/// Marker of an include directive within a string
+#[cfg(feature = "std")]
+const INCLUDE: &str = "#include ";
+
+/// An include directive found while preprocessing
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct Include {
+	/// Position of the directives string within the preprocessed content
+	start: usize,
+	/// Position after the directives string within the preprocessed content
+	end: usize,
+	/// Line of the directive
+	line: usize,
+	/// The file to include
+	file: String,
+}
+
 /// read a config file given by path
 ///
-/// function parses for #include directives, loads the files and puts the content in place
+/// Comments are removed, a string of the form `"#include <file>"` is replaced by the
+/// preprocessed content of that file, which is resolved relative to the including file.
+/// Every file is checked to be valid json5.
 /// # Errors
+/// if a file is not readable, an include is invalid or cyclic, or a file is no valid json5
 #[cfg(feature = "std")]
-pub fn read_config_file(path: &PathBuf) -> Result<String> {
-	let filename = path.to_string_lossy().to_string();
-	let input = read_file_without_comments(path)?;
-	parse_config_file(&filename, input)
+pub fn read_config_file(path: &Path) -> Result<String> {
+	let mut stack = Vec::new();
+	preprocess_file(path, &mut stack)
 }
 
-/// read file and remove comments
+/// preprocess a file, `stack` contains the files currently being included
 ///
 /// # Errors
 #[cfg(feature = "std")]
-fn read_file_without_comments(path: &PathBuf) -> Result<String> {
-	let file = File::open(path)?;
-	let reader = BufReader::new(file);
-	let mut result = String::new();
-	for line in reader.lines() {
-		let line = line?.trim().to_string();
-		let pos = line.find("//");
-		if let Some(pos) = pos {
-			result.push_str(&line[..pos]);
-		} else {
-			result.push_str(&line);
-		}
+fn preprocess_file(path: &Path, stack: &mut Vec<PathBuf>) -> Result<String> {
+	let filename = path.to_string_lossy().to_string();
+	let source = std::fs::read_to_string(path)?;
+	let (content, includes) = preprocess(&filename, &source)?;
+
+	// check the syntax of this file with includes replaced by a placeholder
+	let mut skeleton = String::with_capacity(content.len());
+	let mut pos = 0;
+	for include in &includes {
+		skeleton.push_str(&content[pos..include.start]);
+		skeleton.push_str("null");
+		pos = include.end;
+	}
+	skeleton.push_str(&content[pos..]);
+	if let Err(json5::Error::Message { msg, location }) =
+		json5::from_str::<serde::de::IgnoredAny>(&skeleton)
+	{
+		let line = location.map_or(0, |location| location.line);
+		return Err(Error::InvalidSyntax(filename, line, msg).into());
+	}
+
+	if includes.is_empty() {
+		return Ok(content);
 	}
 
+	stack.push(path.canonicalize()?);
+	let base = path.parent().unwrap_or_else(|| Path::new(""));
+	let mut result = String::with_capacity(content.len());
+	let mut pos = 0;
+	for include in includes {
+		let include_path = base.join(&include.file);
+		let canonical = include_path.canonicalize().map_err(|error| {
+			Error::InvalidInclude(filename.clone(), include.line, error.to_string())
+		})?;
+		if stack.contains(&canonical) {
+			return Err(Error::IncludeCycle(filename, include.line, include.file).into());
+		}
+		result.push_str(&content[pos..include.start]);
+		result.push_str(&preprocess_file(&include_path, stack)?);
+		pos = include.end;
+	}
+	result.push_str(&content[pos..]);
+	stack.pop();
 	Ok(result)
 }
 
-/// parse a config string
+/// remove the comments from `source` and collect the include directives
 ///
-/// function parses for #include directives, loads the files and puts the content in place
+/// Comments are replaced by whitespace keeping the line breaks,
+/// so that lines of the result match the lines of the source.
 /// # Errors
+/// if a comment, a string or an include directive is invalid
 #[cfg(feature = "std")]
-pub fn parse_config_file(filename: &String, mut input: String) -> Result<String> {
-	// we always start from the very beginning
-	while let Some(pos) = input.find("#include ") {
-		// calculate offsets
-		let start = pos - 1;
-		let len = input[pos..]
-			.find('"')
-			.ok_or_else(|| Error::InvalidInclude(filename.clone()))?;
-		let end = 2 + start + len;
-		// create file path to load
-		let mut load_path = PathBuf::from(&filename);
-		let load_file = input[start + 10..end - 1].to_string();
-		// enhance relative path
-		if load_file.starts_with('.') {
-			let path = load_path
-				.parent()
-				.ok_or_else(|| Error::InvalidInclude(filename.clone()))?;
-			load_path = path.join(load_file);
+fn preprocess(filename: &str, source: &str) -> Result<(String, Vec<Include>)> {
+	let mut result = String::with_capacity(source.len());
+	let mut includes = Vec::new();
+	let mut line = 1;
+	let mut chars = source.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'/' if chars.peek() == Some(&'/') => {
+				// line comment, keep the line break
+				for c in chars.by_ref() {
+					if c == '\n' {
+						result.push(c);
+						line += 1;
+						break;
+					}
+				}
+			}
+			'/' if chars.peek() == Some(&'*') => {
+				// block comment, keep the line breaks
+				let start_line = line;
+				chars.next();
+				let mut last = ' ';
+				loop {
+					match chars.next() {
+						Some('/') if last == '*' => break,
+						Some('\n') => {
+							result.push('\n');
+							line += 1;
+							last = '\n';
+						}
+						Some(c) => last = c,
+						None => {
+							return Err(Error::UnterminatedComment(
+								filename.to_string(),
+								start_line,
+							)
+							.into());
+						}
+					}
+				}
+				result.push(' ');
+			}
+			'"' | '\'' => {
+				let start = result.len();
+				let start_line = line;
+				let mut text = String::new();
+				result.push(c);
+				loop {
+					match chars.next() {
+						Some('\\') => {
+							result.push('\\');
+							if let Some(escaped) = chars.next() {
+								if escaped == '\n' {
+									line += 1;
+								}
+								result.push(escaped);
+								text.push(escaped);
+							}
+						}
+						Some(quote) if quote == c => {
+							result.push(quote);
+							break;
+						}
+						Some('\n') | None => {
+							return Err(Error::UnterminatedString(
+								filename.to_string(),
+								start_line,
+							)
+							.into());
+						}
+						Some(other) => {
+							result.push(other);
+							text.push(other);
+						}
+					}
+				}
+				if let Some(file) = text.strip_prefix(INCLUDE) {
+					let file = file.trim();
+					if file.is_empty() {
+						return Err(Error::InvalidInclude(
+							filename.to_string(),
+							start_line,
+							String::from("missing file name"),
+						)
+						.into());
+					}
+					includes.push(Include {
+						start,
+						end: result.len(),
+						line: start_line,
+						file: file.to_string(),
+					});
+				}
+			}
+			'\n' => {
+				result.push(c);
+				line += 1;
+			}
+			_ => result.push(c),
 		}
-		let content = read_file_without_comments(&load_path)?;
-		let content = parse_config_file(filename, content)?;
-		input.replace_range(start..end, &content);
 	}
-	Ok(input)
+	Ok((result, includes))
 }
 
 /// find a config file given by name
@@ -149,4 +277,101 @@ pub fn find_config_file(filename: &str) -> Result<std::path::PathBuf> {
 		text,
 	)))
 }
+
+/// find a file for persisting data given by name
+///
+/// An existing file is searched in the same directories as with [`find_config_file`].
+/// If there is none, the path within the local config directory
+/// (`Linux`: `$XDG_CONFIG_HOME/dimas` or `$HOME/.config/dimas` | `Windows`: `{FOLDERID_LocalAppData}/dimas` | `MacOS`: `$HOME/Library/Application Support/dimas`)
+/// is returned, that directory is created if necessary.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn persistence_file(filename: &str) -> Result<std::path::PathBuf> {
+	if let Ok(path) = find_config_file(filename) {
+		return Ok(path);
+	}
+
+	let dir = config_local_dir()
+		.ok_or_else(|| {
+			std::io::Error::new(
+				std::io::ErrorKind::NotFound,
+				"no local config directory available",
+			)
+		})?
+		.join("dimas");
+	std::fs::create_dir_all(&dir)?;
+	Ok(dir.join(filename))
+}
 // endregion:	--- utils
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::collections::BTreeMap;
+
+	/// create a directory with the given files for a test
+	fn setup(test: &str, files: &[(&str, &str)]) -> Result<PathBuf> {
+		let dir = env::temp_dir().join(format!("dimas-config-{test}-{}", std::process::id()));
+		std::fs::create_dir_all(dir.join("sub"))?;
+		for (name, content) in files {
+			std::fs::write(dir.join(name), content)?;
+		}
+		Ok(dir)
+	}
+
+	#[test]
+	fn comments_and_strings() -> Result<()> {
+		let source = "{\n  // a comment with \"quotes\"\n  url: \"http://host/*x*/\", /* block\n comment */ other: 'it''s',\n}";
+		let (content, includes) = preprocess("test", source)?;
+		assert!(includes.is_empty());
+		assert_eq!(content.lines().count(), source.lines().count());
+		assert!(content.contains("\"http://host/*x*/\""));
+		assert!(!content.contains("comment"));
+		assert!(preprocess("test", "{ /* open").is_err());
+		assert!(preprocess("test", "{ a: \"open }").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn includes() -> Result<()> {
+		let dir = setup(
+			"includes",
+			&[
+				("main.json5", "{\n  zenoh: \"#include sub/zenoh.json5\",\n}"),
+				("sub/zenoh.json5", "{ mode: \"#include mode.json5\" }"),
+				("sub/mode.json5", "// the mode\n\"peer\""),
+			],
+		)?;
+		let content = read_config_file(&dir.join("main.json5"))?;
+		let value: BTreeMap<String, BTreeMap<String, String>> = json5::from_str(&content)?;
+		assert_eq!(value["zenoh"]["mode"], "peer");
+		std::fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+
+	#[test]
+	fn invalid_files() -> Result<()> {
+		let dir = setup(
+			"invalid",
+			&[
+				("cycle.json5", "{ a: \"#include sub/cycle.json5\" }"),
+				("sub/cycle.json5", "{\n b: \"#include ../cycle.json5\" }"),
+				("missing.json5", "{ a: \"#include none.json5\" }"),
+				("syntax.json5", "{\n a: 1,\n b: }"),
+			],
+		)?;
+		let error = read_config_file(&dir.join("cycle.json5"))
+			.expect_err("cycle not detected")
+			.to_string();
+		assert!(error.starts_with("cyclic"));
+		assert!(error.contains("sub/cycle.json5:2"));
+		assert!(read_config_file(&dir.join("missing.json5")).is_err());
+		let error = read_config_file(&dir.join("syntax.json5"))
+			.expect_err("syntax error not detected")
+			.to_string();
+		assert!(error.contains("syntax.json5:3"));
+		std::fs::remove_dir_all(dir)?;
+		Ok(())
+	}
+}
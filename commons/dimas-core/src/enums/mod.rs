@@ -11,6 +11,7 @@ use crate::error::Error;
 use alloc::{
 	boxed::Box,
 	string::{String, ToString},
+	vec::Vec,
 };
 use bitcode::{Decode, Encode};
 use core::fmt::{Debug, Display};
@@ -70,6 +71,88 @@ impl Display for OperationState {
 }
 // endregion:	--- OperationState
 
+// region:		--- LogLevel
+/// The verbosity levels for log records, ordered from quiet to verbose
+#[derive(Debug, Decode, Encode, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub enum LogLevel {
+	/// No log records at all
+	#[default]
+	Off,
+	/// Only errors
+	Error,
+	/// Warnings and above
+	Warn,
+	/// Informational records and above
+	Info,
+	/// Debug records and above
+	Debug,
+	/// All records
+	Trace,
+}
+
+impl TryFrom<&str> for LogLevel {
+	type Error = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+	fn try_from(
+		value: &str,
+	) -> core::result::Result<Self, Box<dyn core::error::Error + Send + Sync + 'static>> {
+		let v = value.to_lowercase();
+		match v.as_str() {
+			"off" => Ok(Self::Off),
+			"error" => Ok(Self::Error),
+			"warn" => Ok(Self::Warn),
+			"info" => Ok(Self::Info),
+			"debug" => Ok(Self::Debug),
+			"trace" => Ok(Self::Trace),
+			_ => Err(Error::UnknownLogLevel {
+				level: value.to_string(),
+			}
+			.into()),
+		}
+	}
+}
+
+impl Display for LogLevel {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Off => write!(f, "OFF"),
+			Self::Error => write!(f, "ERROR"),
+			Self::Warn => write!(f, "WARN"),
+			Self::Info => write!(f, "INFO"),
+			Self::Debug => write!(f, "DEBUG"),
+			Self::Trace => write!(f, "TRACE"),
+		}
+	}
+}
+
+impl From<tracing::Level> for LogLevel {
+	fn from(value: tracing::Level) -> Self {
+		match value {
+			tracing::Level::ERROR => Self::Error,
+			tracing::Level::WARN => Self::Warn,
+			tracing::Level::INFO => Self::Info,
+			tracing::Level::DEBUG => Self::Debug,
+			tracing::Level::TRACE => Self::Trace,
+		}
+	}
+}
+
+impl LogLevel {
+	/// Get the level from its numeric representation, which is the position within the enum
+	#[must_use]
+	pub const fn from_u8(value: u8) -> Self {
+		match value {
+			0 => Self::Off,
+			1 => Self::Error,
+			2 => Self::Warn,
+			3 => Self::Info,
+			4 => Self::Debug,
+			_ => Self::Trace,
+		}
+	}
+}
+// endregion:	--- LogLevel
+
 // region:		--- Signal
 /// All defined commands of `DiMAS`
 #[derive(Debug, Decode, Encode)]
@@ -80,6 +163,29 @@ pub enum Signal {
 	Ping {
 		/// the utc time coordinate when the request was sent
 		sent: i64,
+		/// an arbitrary payload to measure size dependent latency, echoed back by the receiver
+		payload: Vec<u8>,
+	},
+	/// Get or set the level for streaming log records
+	Log {
+		/// Optional `LogLevel` to set
+		level: Option<LogLevel>,
+	},
+	/// Get the operational metrics
+	Metrics,
+	/// List all parameters
+	ParameterList,
+	/// Get a parameter
+	ParameterGet {
+		/// name of the parameter
+		name: String,
+	},
+	/// Set a parameter
+	ParameterSet {
+		/// name of the parameter
+		name: String,
+		/// text representation of the new value, parsed according to the parameters type
+		value: String,
 	},
 	/// Shutdown application
 	Shutdown,
@@ -11,7 +11,10 @@ extern crate std;
 
 // region:		--- modules
 #[cfg(doc)]
-use super::enums::OperationState;
+use super::{
+	enums::{LogLevel, OperationState},
+	parameter::Parameter,
+};
 use alloc::{boxed::Box, string::String};
 use thiserror::Error;
 // endregion:	--- modules
@@ -49,6 +52,58 @@ pub enum Error {
 		/// name of the operation state
 		state: String,
 	},
+	/// An unknown [`LogLevel`] is given
+	#[error("the log level {level} is unknown")]
+	UnknownLogLevel {
+		/// name of the log level
+		level: String,
+	},
+	/// An unknown [`Parameter`] is addressed
+	#[error("the parameter {name} is unknown")]
+	UnknownParameter {
+		/// name of the parameter
+		name: String,
+	},
+	/// A text is no valid value for a [`Parameter`]s type
+	#[error("'{value}' is no valid {kind} value")]
+	ParseParameter {
+		/// the text
+		value: String,
+		/// the expected type
+		kind: String,
+	},
+	/// A value has not the [`Parameter`]s type
+	#[error("the parameter {name} needs a {kind} value")]
+	ParameterType {
+		/// name of the parameter
+		name: String,
+		/// the expected type
+		kind: String,
+	},
+	/// Setting up the telemetry failed
+	#[error("setting up the telemetry failed: reason {source}")]
+	Telemetry {
+		/// the original OpenTelemetry error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// Configuring the output of `tracing` failed
+	#[error("configuring tracing failed: reason {source}")]
+	Tracing {
+		/// the original error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// A value is outside of the [`Parameter`]s range
+	#[error("the value {value} for parameter {name} is outside of [{min}, {max}]")]
+	ParameterRange {
+		/// name of the parameter
+		name: String,
+		/// the rejected value
+		value: String,
+		/// the lower bound
+		min: f64,
+		/// the upper bound
+		max: f64,
+	},
 }
 // region:		--- Error
 
@@ -0,0 +1,191 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Health of `DiMAS` entities
+//!
+//! An agent evaluates its health checks periodically and aggregates their results to a [`Health`],
+//! whose [`HealthStatus`] is the worst status of all checks.
+//! The [`Health`] is published together with the `About` information and returned by `Signal::About`.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+// endregion:	--- modules
+
+// region:		--- HealthStatus
+/// The status of a health check or of a whole entity, ordered from healthy to failed
+#[derive(Debug, Decode, Encode, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub enum HealthStatus {
+	/// Everything works as expected
+	#[default]
+	Ok,
+	/// Working with limitations
+	Degraded,
+	/// Not working properly
+	Failed,
+}
+
+impl Display for HealthStatus {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Ok => write!(f, "Ok"),
+			Self::Degraded => write!(f, "Degraded"),
+			Self::Failed => write!(f, "Failed"),
+		}
+	}
+}
+// endregion:	--- HealthStatus
+
+// region:		--- CheckReport
+/// The result of a single health check
+#[derive(Debug, Decode, Encode, Clone, PartialEq, Eq)]
+pub struct CheckReport {
+	name: String,
+	status: HealthStatus,
+	reason: Option<String>,
+}
+
+impl Display for CheckReport {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}: {}", self.name, self.status)?;
+		if let Some(reason) = &self.reason {
+			write!(f, " ({reason})")?;
+		}
+		Ok(())
+	}
+}
+
+impl CheckReport {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, status: HealthStatus, reason: Option<String>) -> Self {
+		Self {
+			name,
+			status,
+			reason,
+		}
+	}
+
+	/// Get the name of the check
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the status
+	#[must_use]
+	pub const fn status(&self) -> HealthStatus {
+		self.status
+	}
+
+	/// Get the reason for a status other than [`HealthStatus::Ok`]
+	#[must_use]
+	pub fn reason(&self) -> Option<&str> {
+		self.reason.as_deref()
+	}
+}
+// endregion:	--- CheckReport
+
+// region:		--- Health
+/// The aggregated health of an entity
+#[derive(Debug, Decode, Encode, Clone, Default, PartialEq, Eq)]
+pub struct Health {
+	status: HealthStatus,
+	checks: Vec<CheckReport>,
+}
+
+impl Display for Health {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}", self.status)
+	}
+}
+
+impl Health {
+	/// Aggregate the `checks` sorted by name, the status is the worst of all checks
+	#[must_use]
+	pub fn new(mut checks: Vec<CheckReport>) -> Self {
+		checks.sort_by(|a, b| a.name.cmp(&b.name));
+		let status = checks
+			.iter()
+			.map(CheckReport::status)
+			.max()
+			.unwrap_or_default();
+		Self { status, checks }
+	}
+
+	/// Get the aggregated status
+	#[must_use]
+	pub const fn status(&self) -> HealthStatus {
+		self.status
+	}
+
+	/// Get the results of the single checks
+	#[must_use]
+	pub fn checks(&self) -> &[CheckReport] {
+		&self.checks
+	}
+
+	/// Get the results of the checks not being [`HealthStatus::Ok`]
+	pub fn problems(&self) -> impl Iterator<Item = &CheckReport> {
+		self.checks
+			.iter()
+			.filter(|check| check.status != HealthStatus::Ok)
+	}
+}
+// endregion:	--- Health
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::string::ToString;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<HealthStatus>();
+		is_normal::<CheckReport>();
+		is_normal::<Health>();
+	}
+
+	#[test]
+	fn aggregation() {
+		let health = Health::default();
+		assert_eq!(health.status(), HealthStatus::Ok);
+		assert!(health.checks().is_empty());
+
+		let health = Health::new(alloc::vec![
+			CheckReport::new("sensor".into(), HealthStatus::Degraded, Some("late".into())),
+			CheckReport::new("battery".into(), HealthStatus::Ok, None),
+		]);
+		assert_eq!(health.status(), HealthStatus::Degraded);
+		assert_eq!(health.checks()[0].name(), "battery");
+		let problems: Vec<String> = health
+			.problems()
+			.map(ToString::to_string)
+			.collect();
+		assert_eq!(problems, ["sensor: Degraded (late)"]);
+
+		let health = Health::new(alloc::vec![
+			CheckReport::new("sensor".into(), HealthStatus::Degraded, None),
+			CheckReport::new("motor".into(), HealthStatus::Failed, Some("stalled".into())),
+		]);
+		assert_eq!(health.status(), HealthStatus::Failed);
+		assert_eq!(health.to_string(), "Failed");
+	}
+
+	#[test]
+	fn encoding() {
+		let health = Health::new(alloc::vec![CheckReport::new(
+			"sensor".into(),
+			HealthStatus::Failed,
+			Some("no data".into()),
+		)]);
+		let decoded: Health = bitcode::decode(&bitcode::encode(&health)).expect("decoding failed");
+		assert_eq!(decoded, health);
+	}
+}
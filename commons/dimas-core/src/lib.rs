@@ -10,8 +10,14 @@ pub mod builder_states;
 pub mod enums;
 /// Error handling
 pub mod error;
+/// Health of entities
+pub mod health;
 /// `Message`, `Request`, `Response`, `Feedback`
 pub mod message_types;
+/// Operational metrics
+pub mod metrics;
+/// Runtime parameters
+pub mod parameter;
 /// Traits
 pub mod traits;
 /// Utilities
@@ -7,28 +7,154 @@ extern crate alloc;
 
 // region:		--- modules
 use crate::error::{Error, Result};
-use alloc::{boxed::Box, string::ToString, vec::Vec};
+use alloc::{
+	borrow::Cow,
+	boxed::Box,
+	collections::BTreeMap,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
 use bitcode::{Decode, Encode, decode, encode};
-use core::ops::Deref;
+use core::{any::Any, fmt::Debug, ops::Deref};
+#[cfg(doc)]
+use zenoh::sample::Sample;
 use zenoh::{Wait, query::Query};
 // endregion:	--- modules
 
-// region:		--- Message
-/// Implementation of a [`Message`].
-#[derive(Debug)]
-pub struct Message(Vec<u8>);
+// region:		--- Attachments
+/// Metadata sent along with a message as zenoh attachment without changing its payload,
+/// e.g. correlation ids, frame ids or a trace context.
+///
+/// Read access is given via [`Deref`] to the underlying map.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+pub struct Attachments(BTreeMap<String, String>);
 
-impl Deref for Message {
-	type Target = Vec<u8>;
+impl Deref for Attachments {
+	type Target = BTreeMap<String, String>;
 
 	fn deref(&self) -> &Self::Target {
 		&self.0
 	}
 }
 
-impl Clone for Message {
-	fn clone(&self) -> Self {
-		Self(self.0.clone())
+impl Attachments {
+	/// Create empty [`Attachments`]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(BTreeMap::new())
+	}
+
+	/// Add the entry `key` with `value`
+	#[must_use]
+	pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+		self.insert(key, value);
+		self
+	}
+
+	/// Insert the entry `key` with `value`, returning a replaced value
+	pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+		self.0.insert(key.into(), value.into())
+	}
+
+	/// Remove the entry `key`, returning its value
+	pub fn remove(&mut self, key: &str) -> Option<String> {
+		self.0.remove(key)
+	}
+
+	/// Encode the [`Attachments`] for sending
+	#[must_use]
+	pub fn to_bytes(&self) -> Vec<u8> {
+		encode(self)
+	}
+
+	/// Decode received [`Attachments`]
+	/// # Errors
+	/// if `bytes` do not contain encoded [`Attachments`]
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+		decode::<Self>(bytes).map_err(|source| {
+			Error::Decoding {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+// endregion:	--- Attachments
+
+// region:		--- Message
+/// A value shared between publisher and subscribers within one process
+#[derive(Clone)]
+struct SharedValue {
+	value: Arc<dyn Any + Send + Sync>,
+	encoder: fn(&(dyn Any + Send + Sync)) -> Vec<u8>,
+}
+
+/// Encode a shared value of type `T`
+fn encode_shared<T>(value: &(dyn Any + Send + Sync)) -> Vec<u8>
+where
+	T: Encode + 'static,
+{
+	value
+		.downcast_ref::<T>()
+		.map(encode)
+		.unwrap_or_default()
+}
+
+/// Metadata of a received [`Message`], available via [`Message::info`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SampleInfo {
+	/// The concrete key expression the message was published on,
+	/// which may differ from the selector of a subscriber using wildcards
+	pub key_expr: String,
+	/// The publishers timestamp in nanoseconds since the UNIX epoch, if given
+	pub timestamp: Option<i64>,
+	/// The zenoh id of the publishing session, if known
+	pub source_id: Option<String>,
+	/// The sequence number of the message at its publisher, if known.
+	/// Remote messages carry it in zenoh's source information with feature `unstable`,
+	/// otherwise as an [`Attachments`] entry, which is removed on reception.
+	/// It is unknown for messages of publishers other than dimas ones.
+	pub sequence_number: Option<u32>,
+	/// The encoding of the message
+	pub encoding: String,
+	/// The raw attachment of the message, if given
+	pub attachment: Option<Vec<u8>>,
+}
+
+impl SampleInfo {
+	/// Decode the raw attachment into [`Attachments`],
+	/// if there is one in that format
+	#[must_use]
+	pub fn attachments(&self) -> Option<Attachments> {
+		self.attachment
+			.as_ref()
+			.and_then(|attachment| Attachments::from_bytes(attachment).ok())
+	}
+}
+
+/// Implementation of a [`Message`].
+///
+/// A [`Message`] either contains the encoded data or,
+/// when created with [`Message::share`], a shared value that is handed to subscribers
+/// within the same process without encoding and copying.
+/// The shared value is only encoded, if that is necessary.
+#[derive(Clone)]
+pub struct Message {
+	content: Vec<u8>,
+	shared: Option<SharedValue>,
+	attachments: Option<Attachments>,
+	info: Option<Box<SampleInfo>>,
+}
+
+impl Debug for Message {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Message")
+			.field("content", &self.content)
+			.field("shared", &self.shared.is_some())
+			.field("attachments", &self.attachments)
+			.field("info", &self.info)
+			.finish()
 	}
 }
 
@@ -36,7 +162,12 @@ impl Message {
 	/// Create a Message from raw data
 	#[must_use]
 	pub const fn new(value: Vec<u8>) -> Self {
-		Self(value)
+		Self {
+			content: value,
+			shared: None,
+			attachments: None,
+			info: None,
+		}
 	}
 
 	/// Encode Message
@@ -46,7 +177,59 @@ impl Message {
 		T: Encode,
 	{
 		let content = encode(message);
-		Self(content)
+		Self::new(content)
+	}
+
+	/// Create a Message sharing `value`.
+	///
+	/// Subscribers within the same process receive the value itself,
+	/// if the publisher uses intra-process delivery.
+	#[must_use]
+	pub fn share<T>(value: Arc<T>) -> Self
+	where
+		T: Encode + Send + Sync + 'static,
+	{
+		Self {
+			content: Vec::new(),
+			shared: Some(SharedValue {
+				value,
+				encoder: encode_shared::<T>,
+			}),
+			attachments: None,
+			info: None,
+		}
+	}
+
+	/// Add [`Attachments`] to be sent along with the [`Message`]
+	#[must_use]
+	pub fn with_attachments(mut self, attachments: Attachments) -> Self {
+		self.attachments = Some(attachments);
+		self
+	}
+
+	/// Get the [`Attachments`] to be sent respectively received along with the [`Message`]
+	#[must_use]
+	pub const fn attachments(&self) -> Option<&Attachments> {
+		self.attachments.as_ref()
+	}
+
+	/// Add the [`SampleInfo`] of a received [`Message`]
+	#[must_use]
+	pub fn with_info(mut self, info: SampleInfo) -> Self {
+		self.info = Some(Box::new(info));
+		self
+	}
+
+	/// Get the [`SampleInfo`] of a received [`Message`]
+	#[must_use]
+	pub fn info(&self) -> Option<&SampleInfo> {
+		self.info.as_deref()
+	}
+
+	/// Check whether the [`Message`] contains a shared value
+	#[must_use]
+	pub const fn is_shared(&self) -> bool {
+		self.shared.is_some()
 	}
 
 	/// Decode Message
@@ -56,8 +239,8 @@ impl Message {
 	where
 		T: for<'a> Decode<'a>,
 	{
-		let value: Vec<u8> = self.0;
-		decode::<T>(value.as_slice()).map_err(|source| {
+		let value = self.to_bytes();
+		decode::<T>(&value).map_err(|source| {
 			Error::Decoding {
 				source: Box::new(source),
 			}
@@ -65,10 +248,36 @@ impl Message {
 		})
 	}
 
-	/// Get value of [`Message`]
+	/// Get the shared value of the [`Message`] without copying,
+	/// an encoded [`Message`] or a value of another type is decoded.
+	///
+	/// # Errors
+	/// if the [`Message`] can not be decoded into `T`
+	pub fn shared<T>(&self) -> Result<Arc<T>>
+	where
+		T: for<'a> Decode<'a> + Send + Sync + 'static,
+	{
+		if let Some(shared) = &self.shared {
+			if let Ok(value) = shared.value.clone().downcast::<T>() {
+				return Ok(value);
+			}
+		}
+		self.clone().decode::<T>().map(Arc::new)
+	}
+
+	/// Get the encoded value of the [`Message`], the same as [`Message::to_bytes`]
 	#[must_use]
-	pub const fn value(&self) -> &Vec<u8> {
-		&self.0
+	pub fn value(&self) -> Cow<'_, [u8]> {
+		self.to_bytes()
+	}
+
+	/// Get the encoded data of the [`Message`], encoding a shared value if necessary
+	#[must_use]
+	pub fn to_bytes(&self) -> Cow<'_, [u8]> {
+		self.shared.as_ref().map_or_else(
+			|| Cow::Borrowed(self.content.as_slice()),
+			|shared| Cow::Owned((shared.encoder)(shared.value.as_ref())),
+		)
 	}
 }
 // endregion:	--- Message
@@ -101,16 +310,39 @@ impl QueryMsg {
 	where
 		T: Encode,
 	{
-		let key = self.0.selector().key_expr().to_string();
-		let encoded: Vec<u8> = encode(&value);
+		self.send_reply(encode(&value), None)
+	}
 
+	/// Reply to the given [`QueryMsg`] with [`Attachments`] sent along with the value
+	///
+	/// # Errors
+	#[allow(clippy::needless_pass_by_value)]
+	pub fn reply_with_attachments<T>(self, value: T, attachments: &Attachments) -> Result<()>
+	where
+		T: Encode,
+	{
+		self.send_reply(encode(&value), Some(attachments))
+	}
+
+	/// Send the `encoded` reply with optional `attachments`
+	fn send_reply(self, encoded: Vec<u8>, attachments: Option<&Attachments>) -> Result<()> {
+		let key = self.0.selector().key_expr().to_string();
 		self.0
 			.reply(&key, encoded)
+			.attachment(attachments.map(Attachments::to_bytes))
 			.wait()
 			.map_err(|source| Error::Reply { source })?;
 		Ok(())
 	}
 
+	/// Get the [`Attachments`] sent along with the query, if given in that format
+	#[must_use]
+	pub fn attachments(&self) -> Option<Attachments> {
+		self.0
+			.attachment()
+			.and_then(|attachment| Attachments::from_bytes(&attachment.to_bytes()).ok())
+	}
+
 	/// Access the queries parameters
 	#[must_use]
 	pub fn parameters(&self) -> &str {
@@ -140,31 +372,50 @@ impl QueryMsg {
 
 // region:		--- QueryableMsg
 /// Implementation of a `Queryable` message handled by a `Query`
-#[derive(Debug)]
-pub struct QueryableMsg(pub Vec<u8>);
-
-impl Clone for QueryableMsg {
-	fn clone(&self) -> Self {
-		Self(self.0.clone())
-	}
+#[derive(Debug, Clone)]
+pub struct QueryableMsg {
+	content: Vec<u8>,
+	attachments: Option<Attachments>,
 }
 
 impl Deref for QueryableMsg {
 	type Target = Vec<u8>;
 
 	fn deref(&self) -> &Self::Target {
-		&self.0
+		&self.content
 	}
 }
 
 impl QueryableMsg {
+	/// Create a [`QueryableMsg`] from raw data
+	#[must_use]
+	pub const fn new(content: Vec<u8>) -> Self {
+		Self {
+			content,
+			attachments: None,
+		}
+	}
+
 	/// Encode [`QueryableMsg`]
 	pub fn encode<T>(message: &T) -> Self
 	where
 		T: Encode,
 	{
 		let content = encode(message);
-		Self(content)
+		Self::new(content)
+	}
+
+	/// Add the [`Attachments`] received along with the reply
+	#[must_use]
+	pub fn with_attachments(mut self, attachments: Option<Attachments>) -> Self {
+		self.attachments = attachments;
+		self
+	}
+
+	/// Get the [`Attachments`] received along with the reply
+	#[must_use]
+	pub const fn attachments(&self) -> Option<&Attachments> {
+		self.attachments.as_ref()
 	}
 
 	/// Decode [`QueryableMsg`]
@@ -174,7 +425,7 @@ impl QueryableMsg {
 	where
 		T: for<'a> Decode<'a>,
 	{
-		let value: Vec<u8> = self.0;
+		let value: Vec<u8> = self.content;
 		decode::<T>(value.as_slice()).map_err(|source| {
 			Error::Decoding {
 				source: Box::new(source),
@@ -228,4 +479,74 @@ mod tests {
 		is_normal::<ControlResponse>();
 		is_normal::<ObservableResponse>();
 	}
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Data {
+		values: Vec<u32>,
+	}
+
+	#[test]
+	fn shared_message() -> Result<()> {
+		let data = Arc::new(Data {
+			values: alloc::vec![1, 2, 3],
+		});
+		let message = Message::share(data.clone());
+		assert!(message.is_shared());
+		// the shared value is handed out without copying
+		assert!(Arc::ptr_eq(&message.shared::<Data>()?, &data));
+		// but can be encoded and decoded
+		assert_eq!(
+			message.to_bytes(),
+			Message::encode(data.as_ref()).to_bytes()
+		);
+		assert_eq!(message.value(), message.to_bytes());
+		assert_eq!(message.decode::<Data>()?, *data);
+
+		let message = Message::encode(data.as_ref());
+		assert!(!message.is_shared());
+		assert_eq!(*message.shared::<Data>()?, *data);
+		Ok(())
+	}
+
+	#[test]
+	fn sample_info() -> Result<()> {
+		let message = Message::encode(&42_u32);
+		assert!(message.info().is_none());
+		let info = SampleInfo {
+			key_expr: "robot/1/speed".into(),
+			sequence_number: Some(7),
+			..Default::default()
+		};
+		let message = message.with_info(info.clone());
+		assert_eq!(message.info(), Some(&info));
+		assert_eq!(message.decode::<u32>()?, 42);
+		Ok(())
+	}
+
+	#[test]
+	fn attachments() -> Result<()> {
+		let attachments = Attachments::new()
+			.with("correlation-id", "42")
+			.with("frame-id", "base_link");
+		let message = Message::encode(&1_u32).with_attachments(attachments.clone());
+		assert_eq!(message.attachments(), Some(&attachments));
+		assert_eq!(
+			attachments.get("frame-id").map(String::as_str),
+			Some("base_link")
+		);
+
+		let bytes = attachments.to_bytes();
+		assert_eq!(Attachments::from_bytes(&bytes)?, attachments);
+		let info = SampleInfo {
+			attachment: Some(bytes),
+			..Default::default()
+		};
+		assert_eq!(info.attachments(), Some(attachments));
+		let info = SampleInfo {
+			attachment: Some(alloc::vec![0xff]),
+			..Default::default()
+		};
+		assert_eq!(info.attachments(), None);
+		Ok(())
+	}
 }
@@ -0,0 +1,402 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Operational metrics of `DiMAS` entities
+//!
+//! The [`Metrics`] of an agent collect counters and histograms recorded by its capabilities,
+//! e.g. the messages published and received per topic or the durations of callbacks.
+//! A snapshot of them can be queried with `Signal::Metrics`
+//! and rendered in the Prometheus text format with [`prometheus`].
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use bitcode::{Decode, Encode};
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use {
+	alloc::{collections::BTreeMap, sync::Arc},
+	core::time::Duration,
+	std::sync::Mutex,
+};
+// endregion:	--- modules
+
+// region:		--- names
+/// Counter of the messages published per `topic`
+pub const MESSAGES_PUBLISHED: &str = "dimas_messages_published_total";
+/// Counter of the encoded bytes published per `topic`, shared values are not encoded and not counted
+pub const BYTES_PUBLISHED: &str = "dimas_published_bytes_total";
+/// Counter of the messages received per `topic`
+pub const MESSAGES_RECEIVED: &str = "dimas_messages_received_total";
+/// Counter of the encoded bytes received per `topic`, shared values are not encoded and not counted
+pub const BYTES_RECEIVED: &str = "dimas_received_bytes_total";
+/// Histogram of the callback durations per `kind` of capability and `topic`
+pub const CALLBACK_DURATION: &str = "dimas_callback_duration_seconds";
+/// Histogram of the durations of the queries per `topic` until all replies are handled
+pub const QUERY_DURATION: &str = "dimas_query_duration_seconds";
+/// Counter of the executions started by observables per `topic`
+pub const OBSERVABLE_EXECUTIONS: &str = "dimas_observable_executions_total";
+/// Counter of the timer callbacks per `timer` lasting longer than the timers interval
+pub const TIMER_OVERRUNS: &str = "dimas_timer_overruns_total";
+/// Counter of the transitions into a `state`
+pub const STATE_TRANSITIONS: &str = "dimas_state_transitions_total";
+
+/// The upper bounds of the histogram buckets in seconds, an implicit last bucket is unbounded
+pub const BUCKETS: [f64; 12] = [
+	0.000_1, 0.000_5, 0.001, 0.002_5, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+];
+
+/// The description of the metric `name`
+fn help(name: &str) -> &'static str {
+	match name {
+		MESSAGES_PUBLISHED => "Messages published per topic",
+		BYTES_PUBLISHED => "Bytes published per topic",
+		MESSAGES_RECEIVED => "Messages received per topic",
+		BYTES_RECEIVED => "Bytes received per topic",
+		CALLBACK_DURATION => "Duration of callbacks in seconds",
+		QUERY_DURATION => "Duration of queries in seconds",
+		OBSERVABLE_EXECUTIONS => "Executions started by observables",
+		TIMER_OVERRUNS => "Timer callbacks lasting longer than the interval",
+		STATE_TRANSITIONS => "Transitions into an operation state",
+		_ => "Custom metric",
+	}
+}
+// endregion:	--- names
+
+// region:		--- MetricValue
+/// The value of a [`Metric`]
+#[derive(Debug, Decode, Encode, Clone, PartialEq)]
+pub enum MetricValue {
+	/// A monotonically increasing count
+	Counter(u64),
+	/// A distribution of durations
+	Histogram {
+		/// The number of observations per bucket of [`BUCKETS`], not cumulated,
+		/// the observations above the last bound are only contained in `count`
+		buckets: Vec<u64>,
+		/// The sum of the observations in seconds
+		sum: f64,
+		/// The number of observations
+		count: u64,
+	},
+}
+// endregion:	--- MetricValue
+
+// region:		--- Metric
+/// A metric with its labels and current value
+#[derive(Debug, Decode, Encode, Clone, PartialEq)]
+pub struct Metric {
+	name: String,
+	labels: Vec<(String, String)>,
+	value: MetricValue,
+}
+
+impl Metric {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, labels: Vec<(String, String)>, value: MetricValue) -> Self {
+		Self {
+			name,
+			labels,
+			value,
+		}
+	}
+
+	/// Get the name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the labels
+	#[must_use]
+	pub const fn labels(&self) -> &Vec<(String, String)> {
+		&self.labels
+	}
+
+	/// Get the value of the label `key`
+	#[must_use]
+	pub fn label(&self, key: &str) -> Option<&str> {
+		self.labels
+			.iter()
+			.find(|(label, _)| label == key)
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Get the value
+	#[must_use]
+	pub const fn value(&self) -> &MetricValue {
+		&self.value
+	}
+}
+// endregion:	--- Metric
+
+// region:		--- Metrics
+/// The key of a metric: its name and its labels
+#[cfg(feature = "std")]
+type Key = (String, Vec<(String, String)>);
+
+/// The metrics of an agent, cheap to clone.
+/// Clones share the recorded values.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+	values: Arc<Mutex<BTreeMap<Key, MetricValue>>>,
+}
+
+#[cfg(feature = "std")]
+impl Metrics {
+	/// Create empty [`Metrics`]
+	#[must_use]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Increment the counter `name` with `labels` by `value`
+	pub fn increment(&self, name: &str, labels: &[(&str, &str)], value: u64) {
+		if let Ok(mut values) = self.values.lock() {
+			let entry = values
+				.entry(key(name, labels))
+				.or_insert(MetricValue::Counter(0));
+			if let MetricValue::Counter(count) = entry {
+				*count = count.saturating_add(value);
+			}
+		}
+	}
+
+	/// Record the `duration` in the histogram `name` with `labels`
+	pub fn observe(&self, name: &str, labels: &[(&str, &str)], duration: Duration) {
+		if let Ok(mut values) = self.values.lock() {
+			let entry = values
+				.entry(key(name, labels))
+				.or_insert_with(|| MetricValue::Histogram {
+					buckets: alloc::vec![0; BUCKETS.len()],
+					sum: 0.0,
+					count: 0,
+				});
+			if let MetricValue::Histogram {
+				buckets,
+				sum,
+				count,
+			} = entry
+			{
+				let seconds = duration.as_secs_f64();
+				if let Some(index) = BUCKETS.iter().position(|bound| seconds <= *bound) {
+					buckets[index] += 1;
+				}
+				*sum += seconds;
+				*count += 1;
+			}
+		}
+	}
+
+	/// Get the current values of all metrics, ordered by name and labels
+	#[must_use]
+	pub fn snapshot(&self) -> Vec<Metric> {
+		self.values
+			.lock()
+			.map(|values| {
+				values
+					.iter()
+					.map(|((name, labels), value)| {
+						Metric::new(name.clone(), labels.clone(), value.clone())
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+}
+
+/// Create the key for the metric `name` with `labels`
+#[cfg(feature = "std")]
+fn key(name: &str, labels: &[(&str, &str)]) -> Key {
+	(
+		name.to_string(),
+		labels
+			.iter()
+			.map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+			.collect(),
+	)
+}
+// endregion:	--- Metrics
+
+// region:		--- Prometheus
+/// Render `metrics` ordered by name in the Prometheus text exposition format
+#[must_use]
+pub fn prometheus(metrics: &[Metric]) -> String {
+	let mut text = String::new();
+	let mut previous: Option<&str> = None;
+	for metric in metrics {
+		if previous != Some(metric.name()) {
+			let kind = match metric.value {
+				MetricValue::Counter(_) => "counter",
+				MetricValue::Histogram { .. } => "histogram",
+			};
+			let _ = writeln!(text, "# HELP {} {}", metric.name, help(&metric.name));
+			let _ = writeln!(text, "# TYPE {} {kind}", metric.name);
+			previous = Some(metric.name());
+		}
+		match &metric.value {
+			MetricValue::Counter(count) => {
+				let _ = writeln!(
+					text,
+					"{}{} {count}",
+					metric.name,
+					labels(&metric.labels, None)
+				);
+			}
+			MetricValue::Histogram {
+				buckets,
+				sum,
+				count,
+			} => {
+				let mut cumulated = 0;
+				for (bound, observations) in BUCKETS.iter().zip(buckets) {
+					cumulated += observations;
+					let _ = writeln!(
+						text,
+						"{}_bucket{} {cumulated}",
+						metric.name,
+						labels(&metric.labels, Some(&bound.to_string()))
+					);
+				}
+				let _ = writeln!(
+					text,
+					"{}_bucket{} {count}",
+					metric.name,
+					labels(&metric.labels, Some("+Inf"))
+				);
+				let _ = writeln!(
+					text,
+					"{}_sum{} {sum}",
+					metric.name,
+					labels(&metric.labels, None)
+				);
+				let _ = writeln!(
+					text,
+					"{}_count{} {count}",
+					metric.name,
+					labels(&metric.labels, None)
+				);
+			}
+		}
+	}
+	text
+}
+
+/// Render the `labels` and an optional bucket bound `le`
+fn labels(labels: &[(String, String)], le: Option<&str>) -> String {
+	let mut pairs: Vec<String> = labels
+		.iter()
+		.map(|(key, value)| alloc::format!("{key}=\"{}\"", escape(value)))
+		.collect();
+	if let Some(le) = le {
+		pairs.push(alloc::format!("le=\"{le}\""));
+	}
+	if pairs.is_empty() {
+		String::new()
+	} else {
+		alloc::format!("{{{}}}", pairs.join(","))
+	}
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+	value
+		.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\n', "\\n")
+}
+// endregion:	--- Prometheus
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Metric>();
+		is_normal::<MetricValue>();
+		is_normal::<Metrics>();
+	}
+
+	#[test]
+	fn recording() {
+		let metrics = Metrics::new();
+		let clone = metrics.clone();
+		metrics.increment(MESSAGES_PUBLISHED, &[("topic", "robot/speed")], 1);
+		// clones share the values
+		std::thread::spawn(move || {
+			clone.increment(MESSAGES_PUBLISHED, &[("topic", "robot/speed")], 2);
+		})
+		.join()
+		.expect("recording thread failed");
+		metrics.increment(MESSAGES_PUBLISHED, &[("topic", "robot/mode")], 1);
+		metrics.observe(
+			CALLBACK_DURATION,
+			&[("kind", "timer"), ("topic", "tick")],
+			Duration::from_millis(3),
+		);
+		metrics.observe(
+			CALLBACK_DURATION,
+			&[("kind", "timer"), ("topic", "tick")],
+			Duration::from_secs(2),
+		);
+
+		let snapshot = metrics.snapshot();
+		assert_eq!(snapshot.len(), 3);
+		assert_eq!(snapshot[0].name(), CALLBACK_DURATION);
+		assert_eq!(snapshot[0].label("kind"), Some("timer"));
+		let MetricValue::Histogram {
+			buckets,
+			sum,
+			count,
+		} = snapshot[0].value()
+		else {
+			panic!("histogram expected");
+		};
+		assert_eq!(buckets.iter().sum::<u64>(), 1);
+		assert_eq!(buckets[4], 1);
+		assert_eq!(*count, 2);
+		assert!((sum - 2.003).abs() < 1e-9);
+		assert_eq!(snapshot[1].label("topic"), Some("robot/mode"));
+		assert_eq!(snapshot[2].value(), &MetricValue::Counter(3));
+	}
+
+	#[test]
+	fn rendering() {
+		let metrics = Metrics::new();
+		metrics.increment(MESSAGES_RECEIVED, &[("topic", "a\"b")], 5);
+		metrics.increment(MESSAGES_RECEIVED, &[("topic", "c")], 1);
+		metrics.observe(QUERY_DURATION, &[], Duration::from_millis(20));
+
+		let text = prometheus(&metrics.snapshot());
+		let expected = [
+			"# HELP dimas_messages_received_total Messages received per topic",
+			"# TYPE dimas_messages_received_total counter",
+			"dimas_messages_received_total{topic=\"a\\\"b\"} 5",
+			"dimas_messages_received_total{topic=\"c\"} 1",
+			"# TYPE dimas_query_duration_seconds histogram",
+			"dimas_query_duration_seconds_bucket{le=\"0.01\"} 0",
+			"dimas_query_duration_seconds_bucket{le=\"0.025\"} 1",
+			"dimas_query_duration_seconds_bucket{le=\"1\"} 1",
+			"dimas_query_duration_seconds_bucket{le=\"+Inf\"} 1",
+			"dimas_query_duration_seconds_sum 0.02",
+			"dimas_query_duration_seconds_count 1",
+		];
+		for line in expected {
+			assert!(text.lines().any(|text| text == line), "missing: {line}");
+		}
+		assert_eq!(text.matches("# TYPE").count(), 2);
+	}
+}
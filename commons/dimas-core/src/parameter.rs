@@ -0,0 +1,290 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Runtime parameters of `DiMAS` entities
+//!
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::{Result, error::Error};
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+// endregion:	--- modules
+
+// region:		--- ParameterValue
+/// The value of a [`Parameter`]
+#[derive(Debug, Decode, Encode, Clone, PartialEq)]
+pub enum ParameterValue {
+	/// A boolean value
+	Bool(bool),
+	/// An integer value
+	Integer(i64),
+	/// A floating point value
+	Float(f64),
+	/// A text value
+	Text(String),
+}
+
+impl Display for ParameterValue {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Bool(value) => write!(f, "{value}"),
+			Self::Integer(value) => write!(f, "{value}"),
+			Self::Float(value) => write!(f, "{value}"),
+			Self::Text(value) => write!(f, "{value}"),
+		}
+	}
+}
+
+impl From<bool> for ParameterValue {
+	fn from(value: bool) -> Self {
+		Self::Bool(value)
+	}
+}
+
+impl From<i32> for ParameterValue {
+	fn from(value: i32) -> Self {
+		Self::Integer(value.into())
+	}
+}
+
+impl From<i64> for ParameterValue {
+	fn from(value: i64) -> Self {
+		Self::Integer(value)
+	}
+}
+
+impl From<f64> for ParameterValue {
+	fn from(value: f64) -> Self {
+		Self::Float(value)
+	}
+}
+
+impl From<&str> for ParameterValue {
+	fn from(value: &str) -> Self {
+		Self::Text(value.to_string())
+	}
+}
+
+impl From<String> for ParameterValue {
+	fn from(value: String) -> Self {
+		Self::Text(value)
+	}
+}
+
+impl ParameterValue {
+	/// Get the name of the values type
+	#[must_use]
+	pub const fn kind(&self) -> &'static str {
+		match self {
+			Self::Bool(_) => "bool",
+			Self::Integer(_) => "integer",
+			Self::Float(_) => "float",
+			Self::Text(_) => "text",
+		}
+	}
+
+	/// Parse a text into a value of the same type as `self`
+	/// # Errors
+	/// if the text is no valid representation for the type
+	pub fn parse_as(&self, text: &str) -> Result<Self> {
+		let error = || -> Box<dyn core::error::Error + Send + Sync + 'static> {
+			Error::ParseParameter {
+				value: text.to_string(),
+				kind: self.kind().to_string(),
+			}
+			.into()
+		};
+		let text = text.trim();
+		match self {
+			Self::Bool(_) => text.parse().map(Self::Bool).map_err(|_| error()),
+			Self::Integer(_) => text
+				.parse()
+				.map(Self::Integer)
+				.map_err(|_| error()),
+			Self::Float(_) => text.parse().map(Self::Float).map_err(|_| error()),
+			Self::Text(_) => Ok(Self::Text(text.to_string())),
+		}
+	}
+
+	/// Get the value as `bool`, if it is one
+	#[must_use]
+	pub const fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// Get the value as `i64`, if it is an integer
+	#[must_use]
+	pub const fn as_i64(&self) -> Option<i64> {
+		match self {
+			Self::Integer(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// Get the value as `f64`, if it is a number
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub const fn as_f64(&self) -> Option<f64> {
+		match self {
+			Self::Integer(value) => Some(*value as f64),
+			Self::Float(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	/// Get the value as `&str`, if it is a text
+	#[must_use]
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::Text(value) => Some(value),
+			_ => None,
+		}
+	}
+}
+// endregion:	--- ParameterValue
+
+// region:		--- Parameter
+/// The description and current value of a named parameter
+#[derive(Debug, Decode, Encode, Clone, PartialEq)]
+pub struct Parameter {
+	name: String,
+	value: ParameterValue,
+	default: ParameterValue,
+	range: Option<(f64, f64)>,
+	description: String,
+}
+
+impl Display for Parameter {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}: {} = {}", &self.name, self.value.kind(), &self.value)
+	}
+}
+
+impl Parameter {
+	/// Constructor, the value is initialized with the `default`
+	#[must_use]
+	pub fn new(
+		name: impl Into<String>,
+		default: ParameterValue,
+		range: Option<(f64, f64)>,
+		description: impl Into<String>,
+	) -> Self {
+		Self {
+			name: name.into(),
+			value: default.clone(),
+			default,
+			range,
+			description: description.into(),
+		}
+	}
+
+	/// Get the name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the current value
+	#[must_use]
+	pub const fn value(&self) -> &ParameterValue {
+		&self.value
+	}
+
+	/// Get the default value
+	#[must_use]
+	pub const fn default(&self) -> &ParameterValue {
+		&self.default
+	}
+
+	/// Get the allowed range for numeric values
+	#[must_use]
+	pub const fn range(&self) -> Option<(f64, f64)> {
+		self.range
+	}
+
+	/// Get the description
+	#[must_use]
+	pub fn description(&self) -> &str {
+		&self.description
+	}
+
+	/// Check whether `value` has the parameters type and is within its range
+	/// # Errors
+	/// if the type does not match or the value is out of range
+	pub fn check(&self, value: &ParameterValue) -> Result<()> {
+		if core::mem::discriminant(value) != core::mem::discriminant(&self.default) {
+			return Err(Error::ParameterType {
+				name: self.name.clone(),
+				kind: self.default.kind().to_string(),
+			}
+			.into());
+		}
+		if let (Some((min, max)), Some(number)) = (self.range, value.as_f64()) {
+			if number < min || number > max {
+				return Err(Error::ParameterRange {
+					name: self.name.clone(),
+					value: value.to_string(),
+					min,
+					max,
+				}
+				.into());
+			}
+		}
+		Ok(())
+	}
+
+	/// Set a new value without any checks
+	pub fn set_value(&mut self, value: ParameterValue) {
+		self.value = value;
+	}
+}
+// endregion:	--- Parameter
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<ParameterValue>();
+		is_normal::<Parameter>();
+	}
+
+	#[test]
+	fn parse_and_check() {
+		let parameter = Parameter::new("speed", 1.5.into(), Some((0.0, 10.0)), "the speed");
+		let value = parameter
+			.default()
+			.parse_as(" 2.5")
+			.expect("parsing failed");
+		assert_eq!(value, ParameterValue::Float(2.5));
+		assert!(parameter.check(&value).is_ok());
+		assert!(
+			parameter
+				.check(&ParameterValue::Float(10.5))
+				.is_err()
+		);
+		assert!(
+			parameter
+				.check(&ParameterValue::Integer(2))
+				.is_err()
+		);
+		assert!(parameter.default().parse_as("fast").is_err());
+		assert_eq!(
+			ParameterValue::Bool(false).parse_as("true").ok(),
+			Some(ParameterValue::Bool(true))
+		);
+	}
+}
@@ -10,16 +10,21 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+#[cfg(feature = "std")]
+use crate::utils::TracingHandle;
 use crate::{
-	enums::{OperationState, TaskSignal},
+	enums::{LogLevel, OperationState, TaskSignal},
 	error::Result,
+	health::Health,
 	message_types::{Message, QueryableMsg},
+	metrics::Metrics,
+	parameter::{Parameter, ParameterValue},
 	utils::selector_from,
 };
-use alloc::{string::String, sync::Arc};
-use core::fmt::Debug;
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{fmt::Debug, time::Duration};
 #[cfg(feature = "std")]
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::{Receiver, Sender};
 use zenoh::Session;
 // endregion:	--- modules
 
@@ -52,6 +57,60 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	/// # Errors
 	fn set_state(&self, state: OperationState) -> Result<()>;
 
+	/// Get the level for streaming log records
+	#[must_use]
+	fn log_level(&self) -> LogLevel;
+
+	/// Set the level for streaming log records,
+	/// [`LogLevel::Off`] stops the streaming.
+	fn set_log_level(&self, level: LogLevel);
+
+	/// Get the current value of a parameter
+	#[must_use]
+	fn parameter(&self, name: &str) -> Option<ParameterValue>;
+
+	/// Get all parameters ordered by name
+	#[must_use]
+	fn parameters(&self) -> Vec<Parameter>;
+
+	/// Set a parameter.
+	///
+	/// The value must match the parameters type and range
+	/// and is checked by a given validation callback.
+	/// After the change the change callback is called.
+	/// Setting the current value again has no effect.
+	/// # Errors
+	fn set_parameter(&self, name: &str, value: ParameterValue) -> Result<()>;
+
+	/// Store the parameters and properties, if a persistence is set up for the agent.
+	///
+	/// This is done automatically on parameter changes and when the agent stops,
+	/// changes of the properties can be stored in between with this method.
+	/// # Errors
+	fn persist(&self) -> Result<()>;
+
+	/// Get the current time of the agents clock,
+	/// since the UNIX epoch respectively since the epoch of an external time source
+	#[must_use]
+	fn now(&self) -> Duration;
+
+	/// Get the [`Metrics`] recorded by the agents capabilities
+	#[must_use]
+	fn metrics(&self) -> &Metrics;
+
+	/// Get the [`TracingHandle`] to change the filter of the log records at runtime,
+	/// if tracing is initialized with `init_tracing` or `init_tracing_from`
+	#[cfg(feature = "std")]
+	fn tracing(&self) -> Option<&TracingHandle>;
+
+	/// Signal that the heartbeat check `name` is alive, e.g. when fresh sensor data arrived.
+	/// The check fails, if the last heartbeat is older than its allowed age.
+	fn heartbeat(&self, name: &str);
+
+	/// Evaluate the health checks and get the aggregated [`Health`]
+	#[must_use]
+	fn health(&self) -> Health;
+
 	/// Get the uuid
 	#[must_use]
 	fn uuid(&self) -> String;
@@ -165,10 +224,53 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	}
 
 	/// Send an observation request for a `selector` with a [`Message`].
+	/// If there is an observer stored, it will be used
+	/// otherwise an ad-hoc request will be done
 	///
 	/// # Errors
 	fn observe_with(&self, selector: &str, message: Option<Message>) -> Result<()>;
 
+	/// Method to do an ad-hoc publishing for a `selector` via the session `session_id`
+	///
+	/// # Errors
+	fn put_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()>;
+
+	/// Method to do an ad-hoc deletion for a `selector` via the session `session_id`
+	///
+	/// # Errors
+	fn delete_from(&self, session_id: &str, selector: &str) -> Result<()>;
+
+	/// Send an ad-hoc query for a `selector` with an optional [`Message`] via the session `session_id`
+	///
+	/// # Errors
+	fn get_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()>;
+
+	/// Send an ad-hoc observation request for a `selector` with a [`Message`] via the session `session_id`
+	///
+	/// # Errors
+	fn observe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<()>;
+
+	/// Method to subscribe to a `selector` via the session `session_id`,
+	/// used for sessions which are not a zenoh session.
+	/// The received messages are passed to the returned receiver until it is dropped,
+	/// a `None` stands for a deletion.
+	///
+	/// # Errors
+	#[cfg(feature = "std")]
+	fn subscribe_from(&self, session_id: &str, selector: &str)
+	-> Result<Receiver<Option<Message>>>;
+
 	/// Cancel an observation request for a `topic`.
 	/// The `topic` will be enhanced with the prefix.
 	///
@@ -0,0 +1,242 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Output of log records
+//!
+//! The records are written to stdout and into rotated files in the format given by the [`Tracing`] settings.
+//! They are filtered by directives in the format of `RUST_LOG`, which can be changed at runtime
+//! with the [`TracingHandle`] of the initialized tracing.
+
+#[doc(hidden)]
+extern crate alloc;
+
+extern crate std;
+
+// region:		--- modules
+use crate::{Result, enums::LogLevel, error::Error};
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use dimas_config::{Rotation, Tracing, TracingFormat};
+use std::{env, sync::OnceLock};
+use tracing_appender::rolling::{self, RollingFileAppender};
+use tracing_subscriber::{
+	EnvFilter, Layer, Registry,
+	filter::Filtered,
+	fmt::{self, MakeWriter},
+	reload,
+};
+// endregion:	--- modules
+
+// region:		--- types
+/// Environment variable with the filter directives, taking precedence over the [`Tracing`] settings
+const FILTER_VAR: &str = "RUST_LOG";
+
+/// The handle of the initialized tracing
+static HANDLE: OnceLock<TracingHandle> = OnceLock::new();
+
+/// The layers writing the records, filtered by a reloadable [`EnvFilter`]
+pub type OutputLayer = Filtered<
+	Vec<Box<dyn Layer<Registry> + Send + Sync>>,
+	reload::Layer<EnvFilter, Registry>,
+	Registry,
+>;
+// endregion:	--- types
+
+// region:		--- TracingHandle
+/// Changes the filter of the written records at runtime
+#[derive(Debug, Clone)]
+pub struct TracingHandle {
+	handle: reload::Handle<EnvFilter, Registry>,
+}
+
+impl TracingHandle {
+	/// Get the current filter directives
+	/// # Errors
+	/// if the filter is gone
+	pub fn filter(&self) -> Result<String> {
+		self.handle
+			.with_current(ToString::to_string)
+			.map_err(|source| {
+				Error::Tracing {
+					source: Box::new(source),
+				}
+				.into()
+			})
+	}
+
+	/// Replace the filter by `directives` in the format of `RUST_LOG`, e.g. `info,zenoh=warn`
+	/// # Errors
+	/// if the directives are invalid or the filter is gone
+	pub fn set_filter(&self, directives: &str) -> Result<()> {
+		let filter = EnvFilter::try_new(directives).map_err(|source| Error::Tracing {
+			source: Box::new(source),
+		})?;
+		self.handle.reload(filter).map_err(|source| {
+			Error::Tracing {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+
+	/// Set the `level` of the records of `target`, keeping the other directives
+	/// # Errors
+	/// if the target is invalid or the filter is gone
+	pub fn set_level(&self, target: &str, level: LogLevel) -> Result<()> {
+		let prefix = format!("{target}=");
+		let mut directives: Vec<String> = self
+			.filter()?
+			.split(',')
+			.filter(|directive| !directive.starts_with(&prefix))
+			.map(ToString::to_string)
+			.collect();
+		directives.push(format!("{prefix}{}", level.to_string().to_lowercase()));
+		self.set_filter(&directives.join(","))
+	}
+}
+
+/// Get the [`TracingHandle`], if tracing is initialized with `init_tracing` or `init_tracing_from`
+#[must_use]
+pub fn tracing_handle() -> Option<&'static TracingHandle> {
+	HANDLE.get()
+}
+
+/// Make `handle` available via [`tracing_handle`], an already registered one is kept
+pub(super) fn register(handle: TracingHandle) {
+	let _ = HANDLE.set(handle);
+}
+// endregion:	--- TracingHandle
+
+// region:		--- OutputLayer
+/// Create the layer writing the records as given by `settings`
+/// together with the [`TracingHandle`] to change its filter.
+///
+/// The filter is taken from the environment variable `RUST_LOG`, if it is set.
+/// # Errors
+/// if the filter directives are invalid or the log directory can not be created
+pub fn output_layer(settings: &Tracing) -> Result<(OutputLayer, TracingHandle)> {
+	let directives = env::var(FILTER_VAR).unwrap_or_else(|_| settings.directives());
+	let filter = EnvFilter::try_new(directives).map_err(|source| Error::Tracing {
+		source: Box::new(source),
+	})?;
+	let (filter, handle) = reload::Layer::new(filter);
+
+	let mut layers = Vec::new();
+	if settings.stdout {
+		layers.push(format_layer(settings.format, std::io::stdout, true));
+	}
+	if let Some(file) = &settings.file {
+		let rotation = match file.rotation {
+			Rotation::Minutely => rolling::Rotation::MINUTELY,
+			Rotation::Hourly => rolling::Rotation::HOURLY,
+			Rotation::Daily => rolling::Rotation::DAILY,
+			Rotation::Never => rolling::Rotation::NEVER,
+		};
+		let mut builder = RollingFileAppender::builder()
+			.rotation(rotation)
+			.filename_prefix(&file.prefix);
+		if let Some(max_files) = file.max_files {
+			builder = builder.max_log_files(max_files);
+		}
+		let appender = builder
+			.build(&file.directory)
+			.map_err(|source| Error::Tracing {
+				source: Box::new(source),
+			})?;
+		layers.push(format_layer(settings.format, appender, false));
+	}
+
+	Ok((layers.with_filter(filter), TracingHandle { handle }))
+}
+
+/// Create a layer writing the records to `writer` in the given `format`
+fn format_layer<W>(
+	format: TracingFormat,
+	writer: W,
+	ansi: bool,
+) -> Box<dyn Layer<Registry> + Send + Sync>
+where
+	W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+	let layer = fmt::layer()
+		.with_writer(writer)
+		.with_ansi(ansi)
+		.with_thread_ids(true)
+		.with_thread_names(true)
+		.with_level(true)
+		.with_target(true);
+	match format {
+		TracingFormat::Full => layer.boxed(),
+		TracingFormat::Compact => layer.compact().boxed(),
+		TracingFormat::Pretty => layer.pretty().boxed(),
+		TracingFormat::Json => layer.json().boxed(),
+	}
+}
+// endregion:	--- OutputLayer
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use dimas_config::TracingFile;
+	use tracing_subscriber::layer::SubscriberExt;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<TracingHandle>();
+		is_normal::<OutputLayer>();
+	}
+
+	fn emit_records() {
+		tracing::info!(target: "first", "info");
+		tracing::debug!(target: "first", "debug");
+		tracing::debug!(target: "second", "debug");
+	}
+
+	fn write_records(handle: &TracingHandle) -> Result<()> {
+		emit_records();
+		handle.set_level("first", LogLevel::Debug)?;
+		assert_eq!(handle.filter()?, "first=debug,info");
+		emit_records();
+		handle.set_filter("warn")?;
+		emit_records();
+		assert!(handle.set_filter("first=loud").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn file_output() -> Result<()> {
+		let directory = env::temp_dir().join(format!("dimas-log-{}", std::process::id()));
+		let settings = Tracing {
+			filter: String::from("info"),
+			format: TracingFormat::Json,
+			stdout: false,
+			file: Some(TracingFile {
+				directory: directory.to_string_lossy().to_string(),
+				prefix: String::from("test.log"),
+				rotation: Rotation::Never,
+				max_files: None,
+			}),
+			..Default::default()
+		};
+		let (layer, handle) = output_layer(&settings)?;
+		let subscriber = tracing_subscriber::registry().with(layer);
+		tracing::subscriber::with_default(subscriber, || write_records(&handle))?;
+
+		let content = std::fs::read_to_string(directory.join("test.log"))?;
+		let _ = std::fs::remove_dir_all(&directory);
+		let lines: Vec<&str> = content.lines().collect();
+		assert_eq!(lines.len(), 3);
+		assert!(lines.iter().all(|line| line.starts_with('{')));
+		assert!(lines[0].contains("\"message\":\"info\""));
+		assert!(lines[1].contains("\"message\":\"info\""));
+		assert!(lines[2].contains("\"level\":\"DEBUG\""));
+		assert!(lines[2].contains("\"target\":\"first\""));
+		Ok(())
+	}
+}
@@ -0,0 +1,430 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Streaming of log records
+//!
+//! The [`LogLayer`] captures the log records of the process and hands them
+//! to the registered [`LogStream`]s accepting the records level.
+//! Records emitted within the [`LogStream::scope`] of a stream are handed to that stream only,
+//! so several agents in one process each get their own records.
+//! Records outside of any scope are handed to all streams.
+//! Records of the targets `zenoh*` are never captured,
+//! as publishing them would produce new records.
+
+#[doc(hidden)]
+extern crate alloc;
+
+extern crate std;
+
+// region:		--- modules
+use crate::enums::LogLevel;
+use alloc::{
+	format,
+	string::{String, ToString},
+	sync::Arc,
+	vec::Vec,
+};
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+use std::{
+	sync::RwLock,
+	time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tracing::{
+	Event, Metadata, Span, Subscriber,
+	field::{Field, Visit},
+	span::{Attributes, Id, Record},
+	subscriber::Interest,
+};
+use tracing_subscriber::{
+	Layer,
+	layer::{Context, Filter},
+	registry::LookupSpan,
+};
+// endregion:	--- modules
+
+// region:		--- types
+/// Number of records buffered per [`LogStream`], further records are dropped
+const CAPACITY: usize = 256;
+/// Prefix of the targets which are never captured
+const EXCLUDED_TARGET: &str = "zenoh";
+/// Name of the span marking the scope of a [`LogStream`]
+const SCOPE_NAME: &str = "log_scope";
+/// Field of the scope span holding the id of the [`LogStream`]
+const SCOPE_FIELD: &str = "log_stream";
+
+/// The registered receivers of log records
+static SINKS: RwLock<Vec<Sink>> = RwLock::new(Vec::new());
+/// The id for the next registered [`LogStream`]
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+/// The most verbose level of all registered [`LogStream`]s
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Off as u8);
+
+/// A registered receiver of log records
+struct Sink {
+	id: usize,
+	level: Arc<AtomicU8>,
+	sender: Sender<LogRecord>,
+}
+
+/// Recalculates the most verbose level of all registered [`LogStream`]s
+fn update_max_level() {
+	let max = SINKS
+		.read()
+		.ok()
+		.and_then(|sinks| {
+			sinks
+				.iter()
+				.map(|sink| sink.level.load(Ordering::Relaxed))
+				.max()
+		})
+		.unwrap_or(LogLevel::Off as u8);
+	MAX_LEVEL.store(max, Ordering::Relaxed);
+}
+
+/// Check whether records with the given metadata are captured,
+/// the scope spans are always captured as the levels may be raised later
+fn accepts(metadata: &Metadata<'_>) -> bool {
+	(metadata.is_span() && metadata.name() == SCOPE_NAME)
+		|| (LogLevel::from(*metadata.level()) as u8 <= MAX_LEVEL.load(Ordering::Relaxed)
+			&& !metadata.target().starts_with(EXCLUDED_TARGET))
+}
+// endregion:	--- types
+
+// region:		--- LogRecord
+/// A captured log record
+#[derive(Debug, Clone)]
+pub struct LogRecord {
+	/// The utc time in nanoseconds since the unix epoch
+	pub timestamp: i64,
+	/// The level
+	pub level: LogLevel,
+	/// The target, typically the module path
+	pub target: String,
+	/// The message
+	pub message: String,
+	/// The further fields of the record and of the spans it belongs to,
+	/// the latter prefixed with the span name
+	pub fields: Vec<(String, String)>,
+}
+// endregion:	--- LogRecord
+
+// region:		--- LogLevelHandle
+/// Handle to get and change the level of a [`LogStream`]
+#[derive(Debug, Clone)]
+pub struct LogLevelHandle {
+	level: Arc<AtomicU8>,
+}
+
+impl LogLevelHandle {
+	/// Get the current level
+	#[must_use]
+	pub fn get(&self) -> LogLevel {
+		LogLevel::from_u8(self.level.load(Ordering::Relaxed))
+	}
+
+	/// Set a new level
+	pub fn set(&self, level: LogLevel) {
+		self.level.store(level as u8, Ordering::Relaxed);
+		update_max_level();
+	}
+}
+// endregion:	--- LogLevelHandle
+
+// region:		--- LogStream
+/// Receives the log records captured by the [`LogLayer`]
+#[derive(Debug)]
+pub struct LogStream {
+	id: usize,
+	level: LogLevelHandle,
+	receiver: Receiver<LogRecord>,
+}
+
+impl Drop for LogStream {
+	fn drop(&mut self) {
+		if let Ok(mut sinks) = SINKS.write() {
+			sinks.retain(|sink| sink.id != self.id);
+		}
+		update_max_level();
+	}
+}
+
+impl LogStream {
+	/// Constructor, registering a stream receiving the records up to `level`
+	#[must_use]
+	pub fn new(level: LogLevel) -> Self {
+		let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+		let level = Arc::new(AtomicU8::new(level as u8));
+		let (sender, receiver) = mpsc::channel(CAPACITY);
+		if let Ok(mut sinks) = SINKS.write() {
+			sinks.push(Sink {
+				id,
+				level: level.clone(),
+				sender,
+			});
+		}
+		update_max_level();
+		Self {
+			id,
+			level: LogLevelHandle { level },
+			receiver,
+		}
+	}
+
+	/// Get a handle to the level
+	#[must_use]
+	pub fn level(&self) -> LogLevelHandle {
+		self.level.clone()
+	}
+
+	/// Get a span marking the scope of this stream,
+	/// the records emitted within it are handed to this stream only
+	#[must_use]
+	pub fn scope(&self) -> Span {
+		tracing::error_span!(SCOPE_NAME, log_stream = self.id)
+	}
+
+	/// Receive the next record, waiting until one is available
+	pub async fn recv(&mut self) -> Option<LogRecord> {
+		self.receiver.recv().await
+	}
+
+	/// Receive the next record, if one is available
+	pub fn try_recv(&mut self) -> Option<LogRecord> {
+		self.receiver.try_recv().ok()
+	}
+}
+// endregion:	--- LogStream
+
+// region:		--- LogLayer
+/// The fields of a span
+struct SpanFields(Vec<(String, String)>);
+
+/// The id of the [`LogStream`] whose scope a span marks
+struct StreamScope(usize);
+
+/// Collects the message and the fields of an event or span
+struct FieldVisitor<'a> {
+	message: Option<&'a mut String>,
+	fields: &'a mut Vec<(String, String)>,
+}
+
+impl Visit for FieldVisitor<'_> {
+	fn record_str(&mut self, field: &Field, value: &str) {
+		self.record(field, value.to_string());
+	}
+
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+		self.record(field, format!("{value:?}"));
+	}
+}
+
+impl FieldVisitor<'_> {
+	fn record(&mut self, field: &Field, value: String) {
+		match self.message.as_deref_mut() {
+			Some(message) if field.name() == "message" => *message = value,
+			_ => self
+				.fields
+				.push((field.name().to_string(), value)),
+		}
+	}
+}
+
+/// Filter for the [`LogLayer`], following the levels of the registered [`LogStream`]s
+#[derive(Debug, Default)]
+pub struct LogFilter;
+
+impl<S> Filter<S> for LogFilter {
+	fn enabled(&self, metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+		accepts(metadata)
+	}
+
+	fn callsite_enabled(&self, _metadata: &'static Metadata<'static>) -> Interest {
+		// the levels may change at runtime, so the result must not be cached
+		Interest::sometimes()
+	}
+}
+
+/// Tracing layer capturing log records for the registered [`LogStream`]s.
+/// It should be used together with the [`LogFilter`], as it is done by `init_tracing`.
+#[derive(Debug, Default)]
+pub struct LogLayer;
+
+impl<S> Layer<S> for LogLayer
+where
+	S: Subscriber + for<'a> LookupSpan<'a>,
+{
+	fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			let mut fields = Vec::new();
+			attrs.record(&mut FieldVisitor {
+				message: None,
+				fields: &mut fields,
+			});
+			if attrs.metadata().name() == SCOPE_NAME {
+				let id = fields
+					.iter()
+					.find(|(name, _)| name == SCOPE_FIELD)
+					.and_then(|(_, value)| value.parse().ok());
+				if let Some(id) = id {
+					span.extensions_mut().insert(StreamScope(id));
+				}
+			} else {
+				span.extensions_mut().insert(SpanFields(fields));
+			}
+		}
+	}
+
+	fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+		if let Some(span) = ctx.span(id) {
+			if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+				values.record(&mut FieldVisitor {
+					message: None,
+					fields: &mut fields.0,
+				});
+			}
+		}
+	}
+
+	fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+		let metadata = event.metadata();
+		let level = LogLevel::from(*metadata.level());
+
+		let mut message = String::new();
+		let mut fields = Vec::new();
+		// the innermost scope of a stream decides the receiver
+		let mut owner = None;
+		if let Some(scope) = ctx.event_scope(event) {
+			for span in scope.from_root() {
+				let extensions = span.extensions();
+				if let Some(span_fields) = extensions.get::<SpanFields>() {
+					for (name, value) in &span_fields.0 {
+						fields.push((format!("{}.{name}", span.name()), value.clone()));
+					}
+				}
+				if let Some(stream_scope) = extensions.get::<StreamScope>() {
+					owner = Some(stream_scope.0);
+				}
+			}
+		}
+		event.record(&mut FieldVisitor {
+			message: Some(&mut message),
+			fields: &mut fields,
+		});
+
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map_or(0, |duration| {
+				i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX)
+			});
+		let record = LogRecord {
+			timestamp,
+			level,
+			target: metadata.target().to_string(),
+			message,
+			fields,
+		};
+
+		if let Ok(sinks) = SINKS.read() {
+			for sink in sinks
+				.iter()
+				.filter(|sink| owner.is_none_or(|owner| owner == sink.id))
+			{
+				if level as u8 <= sink.level.load(Ordering::Relaxed) {
+					// never block the logging code, drop the record if the buffer is full
+					let _ = sink.sender.try_send(record.clone());
+				}
+			}
+		}
+	}
+}
+// endregion:	--- LogLayer
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tracing_subscriber::layer::SubscriberExt;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<LogRecord>();
+		is_normal::<LogLevelHandle>();
+		is_normal::<LogStream>();
+		is_normal::<LogLayer>();
+		is_normal::<LogFilter>();
+	}
+
+	#[test]
+	#[allow(clippy::cognitive_complexity)]
+	fn streaming() {
+		let subscriber = tracing_subscriber::registry().with(LogLayer.with_filter(LogFilter));
+		tracing::subscriber::with_default(subscriber, || {
+			let mut stream = LogStream::new(LogLevel::Info);
+			let span = tracing::info_span!("outer", id = 7);
+			let _guard = span.enter();
+			tracing::info!(value = 42, "first");
+			tracing::debug!("filtered");
+			tracing::info!(target: "zenoh::net", "excluded");
+
+			let record = stream.try_recv().expect("record missing");
+			assert_eq!(record.level, LogLevel::Info);
+			assert_eq!(record.message, "first");
+			assert!(
+				record
+					.fields
+					.contains(&(String::from("outer.id"), String::from("7")))
+			);
+			assert!(
+				record
+					.fields
+					.contains(&(String::from("value"), String::from("42")))
+			);
+			assert!(stream.try_recv().is_none());
+
+			stream.level().set(LogLevel::Debug);
+			tracing::debug!("second");
+			let record = stream.try_recv().expect("record missing");
+			assert_eq!(record.level, LogLevel::Debug);
+			assert_eq!(record.message, "second");
+		});
+	}
+
+	#[test]
+	fn scoped() {
+		let subscriber = tracing_subscriber::registry().with(LogLayer.with_filter(LogFilter));
+		tracing::subscriber::with_default(subscriber, || {
+			let mut first = LogStream::new(LogLevel::Info);
+			let mut second = LogStream::new(LogLevel::Info);
+			first.scope().in_scope(|| {
+				let span = tracing::info_span!("inner");
+				let _guard = span.enter();
+				tracing::info!("first");
+			});
+			second
+				.scope()
+				.in_scope(|| tracing::info!("second"));
+			tracing::info!("unscoped");
+
+			let record = first.try_recv().expect("record missing");
+			assert_eq!(record.message, "first");
+			assert!(record.fields.is_empty());
+			assert_eq!(
+				first.try_recv().expect("record missing").message,
+				"unscoped"
+			);
+			assert!(first.try_recv().is_none());
+
+			assert_eq!(second.try_recv().expect("record missing").message, "second");
+			assert_eq!(
+				second.try_recv().expect("record missing").message,
+				"unscoped"
+			);
+			assert!(second.try_recv().is_none());
+		});
+	}
+}
@@ -6,22 +6,108 @@
 #[doc(hidden)]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 // region:		--- modules
+#[cfg(feature = "std")]
+mod log_output;
+#[cfg(feature = "std")]
+mod log_stream;
+#[cfg(feature = "std")]
+mod telemetry;
+
+#[cfg(feature = "std")]
+use crate::{Result, error::Error};
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
 use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use dimas_config::Config;
+#[cfg(feature = "std")]
+pub use log_output::*;
+#[cfg(feature = "std")]
+pub use log_stream::*;
+#[cfg(feature = "std")]
+pub use telemetry::*;
+#[cfg(feature = "otlp")]
+use tracing_subscriber::filter::LevelFilter;
+#[cfg(feature = "std")]
+use tracing_subscriber::{Layer, layer::SubscriberExt};
 // endregion:	--- modules
 
 // region:    --- tracing
-/// Initialize tracing
+/// Initialize tracing with the settings of [`Config::minimal`] modified by the environment,
+/// see [`init_tracing_from`].
+///
+/// Without further settings records up to level `INFO` are written to stdout,
+/// which can be changed with the environment variable `RUST_LOG`.
+/// Invalid settings are reported on stderr and replaced by the default settings.
+#[cfg(feature = "std")]
 pub fn init_tracing() {
-	let subscriber = tracing_subscriber::fmt()
-		//.with_env_filter(env_filter)
-		.with_thread_ids(true)
-		.with_thread_names(true)
-		.with_level(true)
-		.with_target(true);
+	let config = env_config();
+	if let Err(error) = init_tracing_from(&config) {
+		std::eprintln!("initializing tracing failed, using the default settings: {error}");
+		if let Err(error) = init_tracing_from(&Config::minimal()) {
+			std::eprintln!("initializing tracing with the default settings failed: {error}");
+		}
+	}
+}
+
+/// The settings of [`Config::minimal`] modified by the environment,
+/// the default settings if the environment is invalid
+#[cfg(feature = "std")]
+fn env_config() -> Config {
+	let mut config = Config::minimal();
+	if let Err(error) = config.apply_env() {
+		std::eprintln!("ignoring the invalid environment overrides: {error}");
+		return Config::minimal();
+	}
+	config
+}
+
+/// Initialize tracing with the `tracing` section of `config`.
+///
+/// The records are filtered by the directives of the environment variable `RUST_LOG`, if set,
+/// otherwise by those of the configuration. The filter can be changed at runtime
+/// with the [`TracingHandle`] given by [`tracing_handle`].
+/// Additionally the [`LogLayer`] captures records for streaming.
+/// # Errors
+/// if the settings are invalid or tracing is already initialized
+#[cfg(feature = "std")]
+pub fn init_tracing_from(config: &Config) -> Result<()> {
+	let (output, handle) = output_layer(config.tracing())?;
+	let subscriber = tracing_subscriber::registry()
+		.with(output)
+		.with(LogLayer.with_filter(LogFilter));
+	tracing::subscriber::set_global_default(subscriber).map_err(|source| Error::Tracing {
+		source: Box::new(source),
+	})?;
+	register(handle);
+	Ok(())
+}
 
-	let subscriber = subscriber.finish();
-	let _ = tracing::subscriber::set_global_default(subscriber);
+/// Initialize tracing like [`init_tracing`] and additionally export the spans up to level `INFO`
+/// via OTLP to the collector at `endpoint`, e.g. `http://localhost:4318`, under the name `service_name`.
+///
+/// The trace context is propagated to other agents, see [`inject_trace_context`].
+/// Spans are exported until the returned [`OtlpGuard`] is dropped.
+/// Invalid environment overrides are reported on stderr and ignored.
+/// # Errors
+/// if the exporter can not be created
+#[cfg(feature = "otlp")]
+pub fn init_tracing_otlp(service_name: &str, endpoint: &str) -> Result<OtlpGuard> {
+	let config = env_config();
+	let (output, handle) = output_layer(config.tracing())?;
+	let (otlp_layer, guard) = otlp_layer(service_name, endpoint)?;
+	let subscriber = tracing_subscriber::registry()
+		.with(output)
+		.with(LogLayer.with_filter(LogFilter))
+		.with(otlp_layer.with_filter(LevelFilter::INFO));
+	if tracing::subscriber::set_global_default(subscriber).is_ok() {
+		register(handle);
+	}
+	Ok(guard)
 }
 // endregion: --- tracing
 
@@ -0,0 +1,350 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Distributed tracing with OpenTelemetry
+//!
+//! The trace context of the current span is sent along with puts, queries and observation requests
+//! as W3C trace context in the entries [`TRACEPARENT`] and [`TRACESTATE`] of the [`Attachments`].
+//! The receiving subscribers, queryables and observables run their callbacks in a span
+//! linked to the senders span, so a trace can be followed across agents.
+//!
+//! Spans only have a trace context, if an OpenTelemetry layer is installed,
+//! e.g. with `init_tracing_otlp` of feature `otlp`, otherwise nothing is propagated.
+//! The propagation needs feature `telemetry`, which is part of feature `otlp`,
+//! without it the functions leave the messages and spans unchanged.
+
+#[doc(hidden)]
+extern crate alloc;
+
+extern crate std;
+
+// region:		--- modules
+use crate::message_types::{Attachments, Message};
+#[cfg(feature = "otlp")]
+use crate::{Result, error::Error};
+#[cfg(feature = "otlp")]
+use alloc::boxed::Box;
+#[cfg(feature = "telemetry")]
+use alloc::format;
+#[cfg(feature = "otlp")]
+use alloc::string::String;
+#[cfg(feature = "telemetry")]
+use core::str::FromStr;
+#[cfg(feature = "otlp")]
+use opentelemetry::trace::TracerProvider;
+#[cfg(feature = "telemetry")]
+use opentelemetry::trace::{SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState};
+#[cfg(feature = "otlp")]
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+#[cfg(feature = "otlp")]
+use opentelemetry_sdk::{
+	Resource,
+	trace::{SdkTracer, SdkTracerProvider},
+};
+use tracing::Span;
+#[cfg(feature = "otlp")]
+use tracing::Subscriber;
+#[cfg(feature = "otlp")]
+use tracing_opentelemetry::OpenTelemetryLayer;
+#[cfg(feature = "telemetry")]
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+#[cfg(feature = "otlp")]
+use tracing_subscriber::registry::LookupSpan;
+// endregion:	--- modules
+
+// region:		--- types
+/// The [`Attachments`] entry holding the W3C `traceparent`
+pub const TRACEPARENT: &str = "traceparent";
+/// The [`Attachments`] entry holding the W3C `tracestate`
+pub const TRACESTATE: &str = "tracestate";
+/// The version of the W3C trace context format
+#[cfg(feature = "telemetry")]
+const VERSION: &str = "00";
+// endregion:	--- types
+
+// region:		--- propagation
+/// Add the trace context of the current span to a copy of `attachments`.
+/// Returns the unchanged `attachments`, if the current span has no valid trace context.
+#[cfg(feature = "telemetry")]
+#[must_use]
+pub fn inject_trace_context(attachments: Option<&Attachments>) -> Option<Attachments> {
+	let context = Span::current().context();
+	let span = context.span();
+	let span_context = span.span_context();
+	if !span_context.is_valid() {
+		return attachments.cloned();
+	}
+
+	let mut attachments = attachments.cloned().unwrap_or_default();
+	attachments.insert(
+		TRACEPARENT,
+		format!(
+			"{VERSION}-{}-{}-{:02x}",
+			span_context.trace_id(),
+			span_context.span_id(),
+			span_context.trace_flags().to_u8()
+		),
+	);
+	let state = span_context.trace_state().header();
+	if !state.is_empty() {
+		attachments.insert(TRACESTATE, state);
+	}
+	Some(attachments)
+}
+
+/// Without feature `telemetry` there is no trace context, the `attachments` are returned unchanged.
+#[cfg(not(feature = "telemetry"))]
+#[must_use]
+pub fn inject_trace_context(attachments: Option<&Attachments>) -> Option<Attachments> {
+	attachments.cloned()
+}
+
+/// Add the trace context of the current span to the [`Attachments`] of `message`
+#[must_use]
+pub fn with_trace_context(message: Message) -> Message {
+	match inject_trace_context(message.attachments()) {
+		Some(attachments) => message.with_attachments(attachments),
+		None => message,
+	}
+}
+
+/// Extract the remote trace context from `attachments`, if there is a valid one
+#[cfg(feature = "telemetry")]
+#[must_use]
+pub fn extract_trace_context(attachments: &Attachments) -> Option<SpanContext> {
+	let parent = attachments.get(TRACEPARENT)?;
+	let mut parts = parent.split('-');
+	let (Some(VERSION), Some(trace_id), Some(span_id), Some(flags), None) = (
+		parts.next(),
+		parts.next(),
+		parts.next(),
+		parts.next(),
+		parts.next(),
+	) else {
+		return None;
+	};
+	if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+		return None;
+	}
+	let trace_id = TraceId::from_hex(trace_id).ok()?;
+	let span_id = SpanId::from_hex(span_id).ok()?;
+	let flags = u8::from_str_radix(flags, 16).ok()? & TraceFlags::SAMPLED.to_u8();
+	let state = attachments
+		.get(TRACESTATE)
+		.and_then(|state| TraceState::from_str(state).ok())
+		.unwrap_or_default();
+
+	let span_context = SpanContext::new(trace_id, span_id, TraceFlags::new(flags), true, state);
+	span_context.is_valid().then_some(span_context)
+}
+
+/// Link `span` to the trace context received in `attachments`, if there is one
+#[cfg(feature = "telemetry")]
+pub fn link_trace_context(span: &Span, attachments: Option<&Attachments>) {
+	if let Some(span_context) = attachments.and_then(extract_trace_context) {
+		span.add_link(span_context);
+	}
+}
+
+/// Without feature `telemetry` the `span` is left unchanged.
+#[cfg(not(feature = "telemetry"))]
+pub const fn link_trace_context(_span: &Span, _attachments: Option<&Attachments>) {}
+// endregion:	--- propagation
+
+// region:		--- OTLP
+/// Keeps the OTLP exporter running, created with `init_tracing_otlp` or [`otlp_layer`].
+/// Dropping it exports the remaining spans and shuts the exporter down.
+#[cfg(feature = "otlp")]
+#[derive(Debug)]
+pub struct OtlpGuard {
+	provider: SdkTracerProvider,
+}
+
+#[cfg(feature = "otlp")]
+impl Drop for OtlpGuard {
+	fn drop(&mut self) {
+		// the exporter is gone anyway
+		let _ = self.provider.shutdown();
+	}
+}
+
+#[cfg(feature = "otlp")]
+impl OtlpGuard {
+	/// Export the finished spans immediately
+	/// # Errors
+	/// if the export fails
+	pub fn flush(&self) -> Result<()> {
+		self.provider.force_flush().map_err(|source| {
+			Error::Telemetry {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+
+/// Create a layer exporting the spans via OTLP over HTTP.
+///
+/// The spans are sent to the collector at `endpoint`, e.g. `http://localhost:4318`,
+/// under the name `service_name` in batches, until the returned [`OtlpGuard`] is dropped.
+/// # Errors
+/// if the exporter can not be created
+#[cfg(feature = "otlp")]
+pub fn otlp_layer<S>(
+	service_name: &str,
+	endpoint: &str,
+) -> Result<(OpenTelemetryLayer<S, SdkTracer>, OtlpGuard)>
+where
+	S: Subscriber + for<'span> LookupSpan<'span>,
+{
+	let exporter = SpanExporter::builder()
+		.with_http()
+		.with_endpoint(traces_endpoint(endpoint))
+		.build()
+		.map_err(|source| Error::Telemetry {
+			source: Box::new(source),
+		})?;
+	let provider = SdkTracerProvider::builder()
+		.with_batch_exporter(exporter)
+		.with_resource(
+			Resource::builder()
+				.with_service_name(String::from(service_name))
+				.build(),
+		)
+		.build();
+	let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("dimas"));
+	Ok((layer, OtlpGuard { provider }))
+}
+
+/// The url for sending traces to the collector at `endpoint`
+#[cfg(feature = "otlp")]
+fn traces_endpoint(endpoint: &str) -> String {
+	format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+}
+// endregion:	--- OTLP
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Result;
+	use std::{
+		io::{BufRead, BufReader, Read, Write},
+		net::TcpListener,
+		string::String,
+		vec::Vec,
+	};
+	use tracing_subscriber::layer::SubscriberExt;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<OtlpGuard>();
+	}
+
+	#[cfg(feature = "telemetry")]
+	#[test]
+	fn extract() {
+		let valid = Attachments::new()
+			.with(
+				TRACEPARENT,
+				"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+			)
+			.with(TRACESTATE, "vendor=value");
+		let span_context = extract_trace_context(&valid);
+		assert!(span_context.is_some());
+		let span_context = span_context.unwrap_or_else(SpanContext::empty_context);
+		assert!(span_context.is_remote());
+		assert!(span_context.is_sampled());
+		assert_eq!(
+			Ok(span_context.trace_id()),
+			TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736")
+		);
+		assert_eq!(span_context.trace_state().header(), "vendor=value");
+
+		for invalid in [
+			"01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+			"00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+			"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+			"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-00",
+			"00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+		] {
+			let attachments = Attachments::new().with(TRACEPARENT, invalid);
+			assert!(extract_trace_context(&attachments).is_none(), "{invalid}");
+		}
+		assert!(extract_trace_context(&Attachments::new()).is_none());
+	}
+
+	#[test]
+	fn without_layer() {
+		let span = tracing::error_span!("untraced");
+		let _entered = span.enter();
+		assert_eq!(inject_trace_context(None), None);
+		let attachments = Attachments::new().with("key", "value");
+		assert_eq!(inject_trace_context(Some(&attachments)), Some(attachments));
+	}
+
+	/// A stand-in for a collector, accepting one request and returning its request line and body
+	fn collector(listener: &TcpListener) -> std::io::Result<(String, Vec<u8>)> {
+		let (mut stream, _) = listener.accept()?;
+		let mut reader = BufReader::new(stream.try_clone()?);
+		let mut request = String::new();
+		reader.read_line(&mut request)?;
+		let mut length = 0;
+		loop {
+			let mut header = String::new();
+			reader.read_line(&mut header)?;
+			let header = header.trim().to_ascii_lowercase();
+			if header.is_empty() {
+				break;
+			}
+			if let Some(value) = header.strip_prefix("content-length:") {
+				length = value.trim().parse().unwrap_or_default();
+			}
+		}
+		let mut body = std::vec![0; length];
+		reader.read_exact(&mut body)?;
+		stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")?;
+		Ok((request, body))
+	}
+
+	#[test]
+	fn otlp_export() -> Result<()> {
+		let listener = TcpListener::bind("127.0.0.1:0")?;
+		let endpoint = format!("http://{}/", listener.local_addr()?);
+		let collector = std::thread::spawn(move || collector(&listener));
+
+		let (layer, guard) = otlp_layer("dimas-test", &endpoint)?;
+		let subscriber = tracing_subscriber::registry().with(layer);
+		tracing::subscriber::with_default(subscriber, || -> Result<()> {
+			let span = tracing::error_span!("exported_span");
+			let _entered = span.enter();
+			let attachments = inject_trace_context(Some(&Attachments::new().with("key", "value")))
+				.ok_or("missing trace context")?;
+			assert_eq!(attachments.get("key").map(String::as_str), Some("value"));
+			let received = extract_trace_context(&attachments).ok_or("invalid trace context")?;
+			let context = span.context();
+			let sent = context.span();
+			assert_eq!(received.trace_id(), sent.span_context().trace_id());
+			assert_eq!(received.span_id(), sent.span_context().span_id());
+
+			let linked = tracing::error_span!("linked_span");
+			link_trace_context(&linked, Some(&attachments));
+			Ok(())
+		})?;
+		guard.flush()?;
+
+		let (request, body) = collector
+			.join()
+			.map_err(|_| "collector panicked")??;
+		assert!(request.starts_with("POST /v1/traces "));
+		let contains = |text: &[u8]| {
+			body.windows(text.len())
+				.any(|window| window == text)
+		};
+		assert!(contains(b"exported_span"));
+		assert!(contains(b"linked_span"));
+		assert!(contains(b"dimas-test"));
+		Ok(())
+	}
+}
@@ -0,0 +1,319 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `clock` provides the [`Clock`] used by an agent for its timers and log record timestamps.
+//! The timestamps of published messages are given by the zenoh session and stay on the wall time.
+//!
+//! A [`Clock`] follows one of the [`ClockSource`]s
+//! - the wall time of the system,
+//! - tokio's time, which can be paused and advanced in tests,
+//! - an external time, e.g. the simulation time published by a simulator on a `clock` topic.
+//!
+//! The time of a clock is given as [`Duration`] since the UNIX epoch,
+//! for an external clock since the epoch of the time source, typically the start of a simulation.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::error::Error;
+use alloc::{string::String, sync::Arc};
+use core::time::Duration;
+use dimas_core::Result;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::{sync::watch, time::Instant};
+// endregion:	--- modules
+
+// region:		--- ClockSource
+/// The source a [`Clock`] takes its time from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockSource {
+	/// The wall time of the system
+	Wall,
+	/// Tokio's time, which follows a paused and advanced runtime
+	Tokio,
+	/// A time set from outside, e.g. by a simulator
+	External,
+}
+// endregion:	--- ClockSource
+
+// region:		--- Clock
+/// The internal state of a [`Clock`]
+#[derive(Debug)]
+enum Source {
+	Wall,
+	Tokio {
+		/// the wall time at creation
+		origin: Duration,
+		/// tokio's instant at creation
+		start: Instant,
+	},
+	External {
+		/// the selector of the topic the time is published on, if any
+		topic: Option<String>,
+		/// the current time
+		time: watch::Sender<Duration>,
+	},
+}
+
+/// A clock giving the time for timers and log record timestamps, cheap to clone.
+/// Clones share the time of an external clock.
+#[derive(Debug, Clone)]
+pub struct Clock {
+	source: Arc<Source>,
+}
+
+impl Default for Clock {
+	/// A clock following the wall time
+	fn default() -> Self {
+		Self::wall()
+	}
+}
+
+impl Clock {
+	/// Create a [`Clock`] following the wall time of the system
+	#[must_use]
+	pub fn wall() -> Self {
+		Self {
+			source: Arc::new(Source::Wall),
+		}
+	}
+
+	/// Create a [`Clock`] following tokio's time, starting at the current wall time.
+	/// Within a runtime with paused time it only advances with the runtimes time.
+	#[must_use]
+	pub fn tokio() -> Self {
+		Self {
+			source: Arc::new(Source::Tokio {
+				origin: wall_time(),
+				start: Instant::now(),
+			}),
+		}
+	}
+
+	/// Create a [`Clock`] following the time published on the topic `selector`,
+	/// as `u64` nanoseconds encoded into a `Message`.
+	/// The time is [`Duration::ZERO`] until the first publication.
+	#[must_use]
+	pub fn external(selector: &str) -> Self {
+		Self {
+			source: Arc::new(Source::External {
+				topic: Some(selector.into()),
+				time: watch::Sender::new(Duration::ZERO),
+			}),
+		}
+	}
+
+	/// Create a [`Clock`] whose time is only changed with [`Clock::set`], starting at [`Duration::ZERO`]
+	#[must_use]
+	pub fn manual() -> Self {
+		Self {
+			source: Arc::new(Source::External {
+				topic: None,
+				time: watch::Sender::new(Duration::ZERO),
+			}),
+		}
+	}
+
+	/// The [`ClockSource`] of the clock
+	#[must_use]
+	pub fn source(&self) -> ClockSource {
+		match *self.source {
+			Source::Wall => ClockSource::Wall,
+			Source::Tokio { .. } => ClockSource::Tokio,
+			Source::External { .. } => ClockSource::External,
+		}
+	}
+
+	/// The selector of the topic an external clock follows
+	#[must_use]
+	pub fn topic(&self) -> Option<&str> {
+		match &*self.source {
+			Source::External {
+				topic: Some(topic), ..
+			} => Some(topic),
+			_ => None,
+		}
+	}
+
+	/// The current time
+	#[must_use]
+	pub fn now(&self) -> Duration {
+		match &*self.source {
+			Source::Wall => wall_time(),
+			Source::Tokio { origin, start } => *origin + start.elapsed(),
+			Source::External { time, .. } => *time.borrow(),
+		}
+	}
+
+	/// The current time in nanoseconds, saturating at `i64::MAX`
+	#[must_use]
+	pub fn now_nanos(&self) -> i64 {
+		i64::try_from(self.now().as_nanos()).unwrap_or(i64::MAX)
+	}
+
+	/// Convert a timestamp of the wall time in nanoseconds into a timestamp of this clock
+	#[must_use]
+	pub fn from_wall(&self, wall: i64) -> i64 {
+		if self.source() == ClockSource::Wall {
+			wall
+		} else {
+			let age = Self::wall().now_nanos().saturating_sub(wall);
+			self.now_nanos().saturating_sub(age)
+		}
+	}
+
+	/// Set the time of an external clock, waking the timers which became due
+	/// # Errors
+	/// - `NotExternal`: the clock is not an external clock
+	pub fn set(&self, now: Duration) -> Result<()> {
+		match &*self.source {
+			Source::External { time, .. } => {
+				time.send_replace(now);
+				Ok(())
+			}
+			_ => Err(Error::NotExternal.into()),
+		}
+	}
+
+	/// Wait for `duration` on this clock
+	pub async fn sleep(&self, duration: Duration) {
+		match &*self.source {
+			Source::Wall | Source::Tokio { .. } => tokio::time::sleep(duration).await,
+			Source::External { .. } => self.sleep_until(self.now() + duration).await,
+		}
+	}
+
+	/// Wait until this clock reaches `deadline`
+	pub async fn sleep_until(&self, deadline: Duration) {
+		match &*self.source {
+			Source::Wall | Source::Tokio { .. } => {
+				tokio::time::sleep(deadline.saturating_sub(self.now())).await;
+			}
+			Source::External { time, .. } => {
+				// the sender lives as long as the clock, so waiting can not fail
+				let _ = time
+					.subscribe()
+					.wait_for(|now| *now >= deadline)
+					.await;
+			}
+		}
+	}
+
+	/// Create an [`Interval`] ticking every `period` on this clock, the first tick completes immediately
+	#[must_use]
+	pub fn interval(&self, period: Duration) -> Interval {
+		let interval = match *self.source {
+			Source::Wall | Source::Tokio { .. } => Some(tokio::time::interval(period)),
+			Source::External { .. } => None,
+		};
+		Interval {
+			clock: self.clone(),
+			period,
+			next: self.now(),
+			interval,
+		}
+	}
+}
+
+/// The wall time since the UNIX epoch
+fn wall_time() -> Duration {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.unwrap_or_default()
+}
+// endregion:	--- Clock
+
+// region:		--- Interval
+/// An interval of a [`Clock`], created with [`Clock::interval`].
+/// Missed ticks are caught up immediately.
+#[derive(Debug)]
+pub struct Interval {
+	/// the clock of the interval
+	clock: Clock,
+	/// the period of the ticks
+	period: Duration,
+	/// the time of the next tick on an external clock
+	next: Duration,
+	/// the tokio interval used for the wall and tokio clock
+	interval: Option<tokio::time::Interval>,
+}
+
+impl Interval {
+	/// Wait for the next tick
+	pub async fn tick(&mut self) {
+		if let Some(interval) = self.interval.as_mut() {
+			interval.tick().await;
+		} else {
+			self.clock.sleep_until(self.next).await;
+			self.next += self.period;
+		}
+	}
+}
+// endregion:	--- Interval
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Clock>();
+		is_normal::<Interval>();
+	}
+
+	#[test]
+	fn sources() -> Result<()> {
+		let wall = Clock::default();
+		assert_eq!(wall.source(), ClockSource::Wall);
+		assert!(wall.now() > Duration::ZERO);
+		assert!(wall.set(Duration::ZERO).is_err());
+
+		let external = Clock::external("clock");
+		assert_eq!(external.source(), ClockSource::External);
+		assert_eq!(external.topic(), Some("clock"));
+		assert_eq!(external.now(), Duration::ZERO);
+		external.set(Duration::from_secs(3))?;
+		assert_eq!(external.now(), Duration::from_secs(3));
+		assert_eq!(external.now_nanos(), 3_000_000_000);
+		// a timestamp of one second ago
+		let wall = Clock::wall().now_nanos() - 1_000_000_000;
+		assert!((1_999_000_000..=2_000_000_000).contains(&external.from_wall(wall)));
+		assert_eq!(Clock::manual().topic(), None);
+		Ok(())
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn tokio_time() {
+		let clock = Clock::tokio();
+		let start = clock.now();
+		tokio::time::advance(Duration::from_secs(10)).await;
+		assert_eq!(clock.now() - start, Duration::from_secs(10));
+		clock.sleep(Duration::from_secs(5)).await;
+		assert_eq!(clock.now() - start, Duration::from_secs(15));
+	}
+
+	#[tokio::test]
+	async fn external_interval() -> Result<()> {
+		let clock = Clock::manual();
+		let mut interval = clock.interval(Duration::from_millis(100));
+		// the first tick completes immediately
+		interval.tick().await;
+		let ticker = tokio::spawn(async move {
+			interval.tick().await;
+			interval.tick().await;
+		});
+		clock.set(Duration::from_millis(150))?;
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		assert!(!ticker.is_finished());
+		clock.set(Duration::from_millis(200))?;
+		tokio::time::timeout(Duration::from_secs(1), ticker).await??;
+		Ok(())
+	}
+}
@@ -18,6 +18,9 @@ pub enum Error {
 	/// a Mutex is poisoned.
 	#[error("a Mutex poison error happened in {0}")]
 	MutexPoison(String),
+	/// only the time of an external clock can be set
+	#[error("the clock is not an external clock")]
+	NotExternal,
 }
 // region:		--- Error
 
@@ -5,12 +5,16 @@
 //!
 
 // region:    --- modules
+#[cfg(feature = "std")]
+mod clock;
 mod error;
 mod timer;
 #[cfg(feature = "std")]
 mod timer_builder;
 
 // flatten
+#[cfg(feature = "std")]
+pub use clock::*;
 pub use timer::*;
 #[cfg(feature = "std")]
 pub use timer_builder::*;
@@ -10,18 +10,22 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+#[cfg(feature = "std")]
+use crate::clock::Clock;
+use crate::error::Error;
 use alloc::{boxed::Box, string::String, sync::Arc};
 use core::{fmt::Debug, time::Duration};
 use dimas_core::{
 	Result,
 	enums::{OperationState, TaskSignal},
+	metrics::{CALLBACK_DURATION, TIMER_OVERRUNS},
 	traits::{Capability, Context},
 };
 #[cfg(feature = "std")]
-use std::sync::Mutex;
+use std::{sync::Mutex, time::Instant};
 #[cfg(feature = "std")]
-use tokio::{task::JoinHandle, time};
-use tracing::{Level, error, info, instrument, warn};
+use tokio::task::JoinHandle;
+use tracing::{Instrument, Level, error, info, instrument, warn};
 // endregion:	--- modules
 
 // region:		--- types
@@ -48,6 +52,8 @@ where
 		callback: ArcTimerCallback<P>,
 		/// The interval in which the Timer is fired
 		interval: Duration,
+		/// The [`Clock`] the Timer follows
+		clock: Clock,
 		/// The handle to stop the Timer
 		handle: Mutex<Option<JoinHandle<()>>>,
 	},
@@ -65,6 +71,8 @@ where
 		interval: Duration,
 		/// The delay after which the first firing of the Timer happenes
 		delay: Duration,
+		/// The [`Clock`] the Timer follows
+		clock: Clock,
 		/// The handle to stop the Timer
 		handle: Mutex<Option<JoinHandle<()>>>,
 	},
@@ -103,6 +111,7 @@ where
 				activation_state,
 				interval: _,
 				callback: _,
+				clock: _,
 				handle: _,
 			}
 			| Self::DelayedInterval {
@@ -112,6 +121,7 @@ where
 				delay: _,
 				interval: _,
 				callback: _,
+				clock: _,
 				handle: _,
 			} => {
 				if state >= activation_state {
@@ -139,6 +149,7 @@ where
 		callback: ArcTimerCallback<P>,
 		interval: Duration,
 		delay: Option<Duration>,
+		clock: Clock,
 	) -> Self {
 		match delay {
 			Some(delay) => Self::DelayedInterval {
@@ -148,6 +159,7 @@ where
 				delay,
 				interval,
 				callback,
+				clock,
 				handle: Mutex::new(None),
 			},
 			None => Self::Interval {
@@ -156,13 +168,76 @@ where
 				activation_state,
 				interval,
 				callback,
+				clock,
 				handle: Mutex::new(None),
 			},
 		}
 	}
 
+	/// The interval in which the timer is fired
+	#[must_use]
+	pub const fn interval(&self) -> Duration {
+		match self {
+			Self::Interval { interval, .. } | Self::DelayedInterval { interval, .. } => *interval,
+		}
+	}
+
+	/// The delay after which the timer is fired the first time, if any
+	#[must_use]
+	pub const fn delay(&self) -> Option<Duration> {
+		match self {
+			Self::Interval { .. } => None,
+			Self::DelayedInterval { delay, .. } => Some(*delay),
+		}
+	}
+
+	/// The [`Clock`] the timer follows
+	#[must_use]
+	pub const fn clock(&self) -> &Clock {
+		match self {
+			Self::Interval { clock, .. } | Self::DelayedInterval { clock, .. } => clock,
+		}
+	}
+
+	/// Fire the timer once by calling its callback, independent of the timers state.
+	/// Allows driving a timer manually instead of running it.
+	/// # Errors
+	/// - `MutexPoison`: the callback is poisoned
+	/// - the error returned by the callback
+	pub fn fire(&self) -> Result<()> {
+		match self {
+			Self::Interval {
+				selector,
+				context,
+				callback,
+				interval,
+				..
+			}
+			| Self::DelayedInterval {
+				selector,
+				context,
+				callback,
+				interval,
+				..
+			} => {
+				let mut callback = callback
+					.lock()
+					.map_err(|_| Error::MutexPoison(String::from("timer callback")))?;
+				let start = Instant::now();
+				let result = callback(context.clone());
+				drop(callback);
+				record(context, selector, *interval, start.elapsed());
+				result
+			}
+		}
+	}
+
 	/// Start or restart the timer
 	/// An already running timer will be stopped.
+	#[allow(
+		clippy::cognitive_complexity,
+		reason = "the expanded `tracing` macros inflate the score of this otherwise linear function"
+	)]
 	#[instrument(level = Level::TRACE, skip_all)]
 	fn start(&self) -> Result<()> {
 		self.stop()?;
@@ -174,6 +249,7 @@ where
 				activation_state: _,
 				interval,
 				callback,
+				clock,
 				handle,
 			} => {
 				// check Mutexes
@@ -185,28 +261,33 @@ where
 				}
 
 				let key = selector.clone();
+				let name = selector.clone();
 				let interval = *interval;
 				let cb = callback.clone();
+				let clock = clock.clone();
 				let ctx1 = context.clone();
 				let ctx2 = context.clone();
 
 				handle.lock().map_or_else(
 					|_| todo!(),
 					|mut handle| {
-						handle.replace(tokio::task::spawn(async move {
-							std::panic::set_hook(Box::new(move |reason| {
-								error!("delayed timer panic: {}", reason);
-								if let Err(reason) = ctx1
-									.sender()
-									.blocking_send(TaskSignal::RestartTimer(key.clone()))
-								{
-									error!("could not restart timer: {}", reason);
-								} else {
-									info!("restarting timer!");
-								}
-							}));
-							run_timer(interval, cb, ctx2).await;
-						}));
+						handle.replace(tokio::task::spawn(
+							async move {
+								std::panic::set_hook(Box::new(move |reason| {
+									error!("delayed timer panic: {}", reason);
+									if let Err(reason) = ctx1
+										.sender()
+										.try_send(TaskSignal::RestartTimer(key.clone()))
+									{
+										error!("could not restart timer: {}", reason);
+									} else {
+										info!("restarting timer!");
+									}
+								}));
+								run_timer(&clock, &name, interval, cb, ctx2).await;
+							}
+							.in_current_span(),
+						));
 						Ok(())
 					},
 				)
@@ -218,6 +299,7 @@ where
 				delay,
 				interval,
 				callback,
+				clock,
 				handle,
 			} => {
 				// check Mutexes
@@ -229,30 +311,35 @@ where
 				}
 
 				let key = selector.clone();
+				let name = selector.clone();
 				let delay = *delay;
 				let interval = *interval;
 				let cb = callback.clone();
+				let clock = clock.clone();
 				let ctx1 = context.clone();
 				let ctx2 = context.clone();
 
 				handle.lock().map_or_else(
 					|_| todo!(),
 					|mut handle| {
-						handle.replace(tokio::task::spawn(async move {
-							std::panic::set_hook(Box::new(move |reason| {
-								error!("delayed timer panic: {}", reason);
-								if let Err(reason) = ctx1
-									.sender()
-									.blocking_send(TaskSignal::RestartTimer(key.clone()))
-								{
-									error!("could not restart timer: {}", reason);
-								} else {
-									info!("restarting timer!");
-								}
-							}));
-							tokio::time::sleep(delay).await;
-							run_timer(interval, cb, ctx2).await;
-						}));
+						handle.replace(tokio::task::spawn(
+							async move {
+								std::panic::set_hook(Box::new(move |reason| {
+									error!("delayed timer panic: {}", reason);
+									if let Err(reason) = ctx1
+										.sender()
+										.try_send(TaskSignal::RestartTimer(key.clone()))
+									{
+										error!("could not restart timer: {}", reason);
+									} else {
+										info!("restarting timer!");
+									}
+								}));
+								clock.sleep(delay).await;
+								run_timer(&clock, &name, interval, cb, ctx2).await;
+							}
+							.in_current_span(),
+						));
 						Ok(())
 					},
 				)
@@ -270,6 +357,7 @@ where
 				activation_state: _,
 				interval: _,
 				callback: _,
+				clock: _,
 				handle,
 			}
 			| Self::DelayedInterval {
@@ -279,6 +367,7 @@ where
 				delay: _,
 				interval: _,
 				callback: _,
+				clock: _,
 				handle,
 			} => handle.lock().map_or_else(
 				|_| todo!(),
@@ -294,20 +383,26 @@ where
 }
 
 #[instrument(name="timer", level = Level::ERROR, skip_all)]
-async fn run_timer<P>(interval: Duration, cb: ArcTimerCallback<P>, ctx: Context<P>)
-where
+async fn run_timer<P>(
+	clock: &Clock,
+	selector: &str,
+	interval: Duration,
+	cb: ArcTimerCallback<P>,
+	ctx: Context<P>,
+) where
 	P: Send + Sync + 'static,
 {
-	let mut interval = time::interval(interval);
+	let mut ticks = clock.interval(interval);
 	loop {
-		let ctx = ctx.clone();
-		interval.tick().await;
+		ticks.tick().await;
 
 		match cb.lock() {
 			Ok(mut cb) => {
-				if let Err(error) = cb(ctx) {
+				let start = Instant::now();
+				if let Err(error) = cb(ctx.clone()) {
 					error!("callback failed with {error}");
 				}
+				record(&ctx, selector, interval, start.elapsed());
 			}
 			Err(err) => {
 				error!("callback lock failed with {err}");
@@ -315,6 +410,22 @@ where
 		}
 	}
 }
+
+/// Record the `duration` of a callback of the timer `selector`, counting it as overrun if it exceeds the `interval`
+fn record<P>(ctx: &Context<P>, selector: &str, interval: Duration, duration: Duration)
+where
+	P: Send + Sync + 'static,
+{
+	ctx.metrics().observe(
+		CALLBACK_DURATION,
+		&[("kind", "timer"), ("topic", selector)],
+		duration,
+	);
+	if duration > interval {
+		ctx.metrics()
+			.increment(TIMER_OVERRUNS, &[("timer", selector)], 1);
+	}
+}
 // endregion:	--- Timer
 
 #[cfg(test)]
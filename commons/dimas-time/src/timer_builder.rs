@@ -15,7 +15,7 @@ use dimas_core::builder_states::{
 	Callback, Interval, NoCallback, NoInterval, NoSelector, NoStorage, Selector, Storage,
 };
 
-use super::{ArcTimerCallback, Timer};
+use super::{ArcTimerCallback, Clock, Timer};
 
 use alloc::{
 	format,
@@ -44,6 +44,7 @@ where
 	callback: C,
 	storage: S,
 	delay: Option<Duration>,
+	clock: Option<Clock>,
 }
 
 impl<P> TimerBuilder<P, NoSelector, NoInterval, NoCallback, NoStorage>
@@ -61,6 +62,7 @@ where
 			callback: NoCallback,
 			storage: NoStorage,
 			delay: None,
+			clock: None,
 		}
 	}
 }
@@ -82,6 +84,13 @@ where
 		self.delay.replace(delay);
 		self
 	}
+
+	/// Set the [`Clock`] the timer follows, default is the wall time
+	#[must_use]
+	pub fn clock(mut self, clock: Clock) -> Self {
+		self.clock.replace(clock);
+		self
+	}
 }
 
 impl<P, I, C, S> TimerBuilder<P, NoSelector, I, C, S>
@@ -98,6 +107,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 			..
 		} = self;
 		TimerBuilder {
@@ -110,6 +120,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 		}
 	}
 
@@ -128,6 +139,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 			..
 		} = self;
 		TimerBuilder {
@@ -138,6 +150,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 		}
 	}
 }
@@ -156,6 +169,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 			..
 		} = self;
 		TimerBuilder {
@@ -166,6 +180,7 @@ where
 			callback,
 			storage,
 			delay,
+			clock,
 		}
 	}
 }
@@ -187,6 +202,7 @@ where
 			interval,
 			storage,
 			delay,
+			clock,
 			..
 		} = self;
 		let callback: ArcTimerCallback<P> = Arc::new(Mutex::new(callback));
@@ -198,6 +214,7 @@ where
 			callback: Callback { callback },
 			storage,
 			delay,
+			clock,
 		}
 	}
 }
@@ -219,6 +236,7 @@ where
 			interval,
 			callback,
 			delay,
+			clock,
 			..
 		} = self;
 		TimerBuilder {
@@ -229,6 +247,7 @@ where
 			callback,
 			storage: Storage { storage },
 			delay,
+			clock,
 		}
 	}
 }
@@ -248,6 +267,7 @@ where
 			interval,
 			callback,
 			delay,
+			clock,
 			..
 		} = self;
 
@@ -258,6 +278,7 @@ where
 			callback.callback,
 			interval.interval,
 			delay,
+			clock.unwrap_or_default(),
 		))
 	}
 }
@@ -0,0 +1,72 @@
+//! `DiMAS` bridge example
+//! Copyright © 2025 Stephan Kunz
+
+use dimas::prelude::*;
+
+#[derive(Debug)]
+struct AgentProps {
+	count: u128,
+}
+
+/// the forwarded message
+#[derive(Debug, Encode, Decode)]
+pub struct PubSubMessage {
+	/// counter
+	pub count: u128,
+	/// text
+	pub text: String,
+}
+
+async fn fleet_telemetry(_ctx: Context<AgentProps>, message: Message) -> Result<()> {
+	let message: PubSubMessage = message.decode()?;
+	println!("Fleet received {} [{}]", message.text, message.count);
+	Ok(())
+}
+
+#[dimas::main]
+async fn main() -> Result<()> {
+	// the configuration 'bridge.json5' has an additional session named 'fleet'
+	let agent = Agent::new(AgentProps { count: 0 })
+		.prefix("robot")
+		.name("bridge")
+		.config(&Config::from_file("bridge.json5")?)?;
+
+	// forward the telemetry from the robot network to the fleet network,
+	// renamed to 'fleet/robot1/...' and at most one message per second
+	agent
+		.bridge()
+		.from("default")
+		.to("fleet")
+		.topic("telemetry/**")
+		.remap("robot", "fleet/robot1")
+		.rate_limit(Duration::from_secs(1))
+		.add()?;
+
+	// listen for the forwarded telemetry in the fleet network
+	agent
+		.subscriber_for("fleet")
+		.selector("fleet/robot1/telemetry/*")
+		.put_callback(fleet_telemetry)
+		.add()?;
+
+	// publish telemetry within the robot network twice a second
+	agent
+		.timer()
+		.name("telemetry")
+		.interval(Duration::from_millis(500))
+		.callback(|ctx| -> Result<()> {
+			let count = ctx.read()?.count;
+			let msg = PubSubMessage {
+				count,
+				text: String::from("telemetry"),
+			};
+			println!("Sending {} [{}]", msg.text, msg.count);
+			ctx.put("telemetry/status", Message::encode(&msg))?;
+			ctx.write()?.count += 1;
+			Ok(())
+		})
+		.add()?;
+
+	agent.start().await?;
+	Ok(())
+}
@@ -0,0 +1,88 @@
+//! `DiMAS` agent group example
+//! Copyright © 2025 Stephan Kunz
+
+use dimas::prelude::*;
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct PublisherProps {
+	count: u128,
+}
+
+#[derive(Debug)]
+struct SubscriberProps {
+	received: u128,
+}
+
+/// the message exchanged between the agents
+#[derive(Debug, Encode, Decode)]
+pub struct PubSubMessage {
+	/// counter
+	pub count: u128,
+	/// text
+	pub text: String,
+}
+
+async fn hello_publishing(ctx: Context<SubscriberProps>, message: Message) -> Result<()> {
+	// within the group the message is shared without encoding and copying
+	let message: Arc<PubSubMessage> = message.shared()?;
+	ctx.write()?.received += 1;
+	println!(
+		"Received {} [{}], {} messages in total",
+		message.text,
+		message.count,
+		ctx.read()?.received
+	);
+	Ok(())
+}
+
+#[dimas::main]
+async fn main() -> Result<()> {
+	// both agents share the sessions of the group
+	let mut group = AgentGroup::new(&Config::default())?;
+
+	// an agent publishing 'hello' messages
+	let publisher = Agent::new(PublisherProps { count: 0 })
+		.prefix("examples")
+		.name("group-publisher")
+		.group(&group)?;
+	publisher
+		.publisher()
+		.topic("hello")
+		.intra_process(true)
+		.add()?;
+	publisher
+		.timer()
+		.name("timer")
+		.interval(Duration::from_secs(1))
+		.callback(|ctx| -> Result<()> {
+			let count = ctx.read()?.count;
+			let msg = PubSubMessage {
+				count,
+				text: String::from("hello group!"),
+			};
+			println!("Sending {} [{}]", msg.text, msg.count);
+			let _ = ctx.put("hello", Message::share(Arc::new(msg)));
+			ctx.write()?.count += 1;
+			Ok(())
+		})
+		.add()?;
+	group.add(publisher);
+
+	// an agent listening for 'hello' messages
+	let subscriber = Agent::new(SubscriberProps { received: 0 })
+		.prefix("examples")
+		.name("group-subscriber")
+		.group(&group)?;
+	subscriber
+		.subscriber()
+		.topic("hello")
+		.put_callback(hello_publishing)
+		.add()?;
+	group.add(subscriber);
+
+	// run both agents until 'ctrl-c'
+	group.start_all().await?;
+
+	Ok(())
+}
@@ -0,0 +1,64 @@
+//! `DiMAS` MQTT example
+//! Needs an MQTT broker listening at `localhost:1883`, e.g. mosquitto
+//! Copyright © 2025 Stephan Kunz
+
+use dimas::prelude::*;
+
+#[derive(Debug)]
+struct AgentProps {
+	count: u32,
+}
+
+#[dimas::main]
+async fn main() -> Result<()> {
+	// the configuration 'mqtt.json5' routes the topics 'robot/iot/**' to the session 'iot'
+	let agent = Agent::new(AgentProps { count: 0 })
+		.prefix("robot")
+		.name("mqtt")
+		.config(&Config::from_file("mqtt.json5")?)?;
+
+	// publish a setpoint for the IoT devices via the broker, which retains it
+	agent
+		.timer()
+		.name("setpoint")
+		.interval(Duration::from_secs(1))
+		.callback(|ctx| -> Result<()> {
+			let count = ctx.read()?.count;
+			println!("Sending setpoint [{count}]");
+			ctx.put("iot/setpoint", Message::encode(&count))?;
+			ctx.write()?.count += 1;
+			Ok(())
+		})
+		.add()?;
+
+	// listen to the setpoints via the broker
+	agent
+		.subscriber_for("iot")
+		.topic("iot/setpoint")
+		.put_callback(|_ctx, message| async move {
+			let value: u32 = message.decode()?;
+			println!("Received setpoint [{value}]");
+			Ok(())
+		})
+		.add()?;
+
+	// read the retained messages of all IoT topics
+	agent
+		.timer()
+		.name("query")
+		.delay(Duration::from_millis(500))
+		.interval(Duration::from_secs(3))
+		.callback(|ctx| -> Result<()> {
+			let mut callback = |response: QueryableMsg| -> Result<()> {
+				let value: u32 = response.decode()?;
+				println!("Retained setpoint is [{value}]");
+				Ok(())
+			};
+			ctx.get("iot/*", None, Some(&mut callback))?;
+			Ok(())
+		})
+		.add()?;
+
+	agent.start().await?;
+	Ok(())
+}
@@ -47,29 +47,50 @@
 // region:		--- modules
 use crate::context::ContextImpl;
 use crate::error::Error;
-use chrono::Local;
+use crate::group::AgentGroup;
+use crate::health::{HealthCheckBuilder, Probe};
+use crate::parameter::ParameterBuilder;
+use crate::persistence::Persistence;
+#[cfg(feature = "testing")]
+use crate::testing::TestAgent;
+use bitcode::{Decode, Encode};
 use core::{fmt::Debug, time::Duration};
 use dimas_com::builder::LivelinessSubscriberBuilder;
 use dimas_com::builder::{
-	ObservableBuilder, ObserverBuilder, PublisherBuilder, QuerierBuilder, QueryableBuilder,
-	SubscriberBuilder,
+	BridgeBuilder, ObservableBuilder, ObserverBuilder, PublisherBuilder, QuerierBuilder,
+	QueryableBuilder, SubscriberBuilder,
 };
+#[cfg(feature = "testing")]
+use dimas_com::communicator::Loopback;
 use dimas_com::traits::LivelinessSubscriber;
-use dimas_com::traits::{Observer, Publisher, Querier, Responder};
-use dimas_commands::messages::{AboutEntity, PingEntity};
+use dimas_com::traits::{Communicator, Observer, Publisher, Querier, Responder};
+use dimas_commands::messages::{
+	AboutEntity, LogEntity, LogLevelEntity, MetricsEntity, ParameterEntity, PingEntity,
+};
 use dimas_config::Config;
 use dimas_core::{
 	Result,
 	builder_states::{NoCallback, NoInterval, NoSelector, Storage},
-	enums::{OperationState, Signal, TaskSignal},
+	enums::{LogLevel, OperationState, Signal, TaskSignal},
+	health::{Health, HealthStatus},
 	message_types::{Message, QueryMsg},
+	metrics::{Metrics, prometheus},
+	parameter::ParameterValue,
 	traits::{Capability, Context, ContextAbstraction},
+	utils::LogStream,
 };
-use dimas_time::{Timer, TimerBuilder};
+use dimas_time::{Clock, Timer, TimerBuilder};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
-use tokio::{select, signal, sync::mpsc};
-use tracing::{error, info, warn};
+use tokio::{
+	io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+	net::{TcpListener, TcpStream},
+	select, signal,
+	sync::{Mutex, mpsc},
+	task::JoinHandle,
+};
+use tracing::{Instrument, error, info, warn};
 use zenoh::liveliness::LivelinessToken;
 // endregion:	--- modules
 
@@ -84,7 +105,14 @@ where
 		let signal: Signal = Message::decode(msg)?;
 		match signal {
 			Signal::About => about_handler(ctx, request)?,
-			Signal::Ping { sent } => ping_handler(ctx, request, sent)?,
+			Signal::Log { level } => log_handler(ctx, request, level)?,
+			Signal::Metrics => metrics_handler(ctx, request)?,
+			Signal::ParameterList => parameter_handler(ctx, request, None, None)?,
+			Signal::ParameterGet { name } => parameter_handler(ctx, request, Some(&name), None)?,
+			Signal::ParameterSet { name, value } => {
+				parameter_handler(ctx, request, Some(&name), Some(value))?;
+			}
+			Signal::Ping { sent, payload } => ping_handler(ctx, request, sent, payload)?,
 			Signal::Shutdown => shutdown_handler(ctx, request)?,
 			Signal::State { state } => state_handler(ctx, request, state)?,
 		}
@@ -92,9 +120,10 @@ where
 	Ok(())
 }
 
-fn about_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+/// Collects the `About` information including the evaluated health
+fn about_entity<C>(ctx: &C) -> AboutEntity
 where
-	P: Send + Sync + 'static,
+	C: ContextAbstraction + ?Sized,
 {
 	let name = ctx
 		.fq_name()
@@ -102,55 +131,133 @@ where
 	let mode = ctx.mode().to_string();
 	let zid = ctx.uuid();
 	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
+	let health = ctx.health();
+	AboutEntity::new(name, mode, zid, state, health)
+}
+
+fn about_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	let value = about_entity(&*ctx);
 	drop(ctx);
 	request.reply(value)?;
 	Ok(())
 }
 
-fn ping_handler<P>(ctx: Context<P>, request: QueryMsg, sent: i64) -> Result<()>
+fn log_handler<P>(ctx: Context<P>, request: QueryMsg, level: Option<LogLevel>) -> Result<()>
 where
 	P: Send + Sync + 'static,
 {
-	let now = Local::now()
-		.naive_utc()
-		.and_utc()
-		.timestamp_nanos_opt()
-		.unwrap_or(0);
+	// is a level given?
+	if let Some(value) = level {
+		ctx.set_log_level(value);
+	}
 
+	// send back result
 	let name = ctx
 		.fq_name()
 		.unwrap_or_else(|| String::from("--"));
 	let zid = ctx.uuid();
-	let value = PingEntity::new(name, zid, now - sent);
+	let value = LogLevelEntity::new(name, zid, ctx.log_level());
 	drop(ctx);
 	request.reply(value)?;
 	Ok(())
 }
 
-fn shutdown_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+fn metrics_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
 where
 	P: Send + Sync + 'static,
 {
-	// send back current infos
 	let name = ctx
 		.fq_name()
 		.unwrap_or_else(|| String::from("--"));
-	let mode = ctx.mode().to_string();
 	let zid = ctx.uuid();
-	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
+	let value = MetricsEntity::new(name, zid, ctx.metrics().snapshot());
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
+fn parameter_handler<P>(
+	ctx: Context<P>,
+	request: QueryMsg,
+	name: Option<&str>,
+	value: Option<String>,
+) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	// is a value to set given?
+	let mut error = None;
+	if let (Some(name), Some(value)) = (name, value) {
+		let result = ctx
+			.parameter(name)
+			.ok_or_else(|| dimas_core::error::Error::UnknownParameter { name: name.into() }.into())
+			.and_then(|current: ParameterValue| current.parse_as(&value))
+			.and_then(|value| ctx.set_parameter(name, value));
+		if let Err(err) = result {
+			warn!("setting parameter {name} failed: {err}");
+			error = Some(err.to_string());
+		}
+	}
+
+	// send back result
+	let mut parameters = ctx.parameters();
+	if let Some(name) = name {
+		parameters.retain(|parameter| parameter.name() == name);
+		if parameters.is_empty() && error.is_none() {
+			error =
+				Some(dimas_core::error::Error::UnknownParameter { name: name.into() }.to_string());
+		}
+	}
+	let agent = ctx
+		.fq_name()
+		.unwrap_or_else(|| String::from("--"));
+	let zid = ctx.uuid();
+	let value = ParameterEntity::new(agent, zid, parameters, error);
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
+fn ping_handler<P>(ctx: Context<P>, request: QueryMsg, sent: i64, payload: Vec<u8>) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	// `sent` is the wall time of the sender, independent of the agents clock
+	let now = Clock::wall().now_nanos();
+
+	let name = ctx
+		.fq_name()
+		.unwrap_or_else(|| String::from("--"));
+	let zid = ctx.uuid();
+	let value = PingEntity::new(name, zid, now - sent, payload);
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
+fn shutdown_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	// send back current infos
+	let value = about_entity(&*ctx);
 	request.reply(value)?;
 
 	// shutdown agent after a short wait time to be able to send response
-	tokio::task::spawn(async move {
-		tokio::time::sleep(Duration::from_millis(10)).await;
-		// gracefully end agent
-		let _ = ctx.set_state(OperationState::Standby);
-		tokio::time::sleep(Duration::from_millis(100)).await;
-		let _ = ctx.set_state(OperationState::Created);
-		let _ = ctx.sender().send(TaskSignal::Shutdown).await;
-	});
+	tokio::task::spawn(
+		async move {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+			// gracefully end agent
+			let _ = ctx.set_state(OperationState::Standby);
+			tokio::time::sleep(Duration::from_millis(100)).await;
+			let _ = ctx.set_state(OperationState::Created);
+			let _ = ctx.sender().send(TaskSignal::Shutdown).await;
+		}
+		.in_current_span(),
+	);
 	Ok(())
 }
 
@@ -164,20 +271,181 @@ where
 	}
 
 	// send back result
-	let name = ctx
-		.fq_name()
-		.unwrap_or_else(|| String::from("--"));
-	let mode = ctx.mode().to_string();
-	let zid = ctx.uuid();
-	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
+	let value = about_entity(&*ctx);
 	drop(ctx);
 	request.reply(value)?;
 	Ok(())
 }
 // endregion:	--- callbacks
 
+// region:	   --- log streaming
+/// Publishes the log records captured by the `stream` on `<prefix>/<name>/log`,
+/// respectively on `<zid>/log` for agents without a name
+async fn forward_logs<P>(context: Arc<ContextImpl<P>>, stream: Arc<Mutex<LogStream>>)
+where
+	P: Debug + Send + Sync + 'static,
+{
+	let mut stream = stream.lock().await;
+	let zid = context.uuid();
+	let (name, selector) = context.fq_name().map_or_else(
+		|| (String::from("--"), format!("{zid}/log")),
+		|fq_name| (fq_name.clone(), format!("{fq_name}/log")),
+	);
+	while let Some(mut record) = stream.recv().await {
+		// the records are stamped with the wall time
+		record.timestamp = context.clock().from_wall(record.timestamp);
+		let entity = LogEntity::new(name.clone(), zid.clone(), record);
+		// failures can not be logged, as that would produce further records
+		let _ = context.put_with(&selector, Message::encode(&entity));
+	}
+}
+// endregion:   --- log streaming
+
+// region:	   --- health monitoring
+/// The default interval for evaluating and publishing the [`Health`]
+const HEALTH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Evaluates the health checks, lets the watchdog drop a failed agent to the `watchdog` state
+/// and publishes the `About` information with the [`Health`] on `<prefix>/<name>/health`,
+/// respectively on `<zid>/health` for agents without a name
+pub(crate) fn supervise<P>(context: &ContextImpl<P>, watchdog: Option<&OperationState>) -> Health
+where
+	P: Debug + Send + Sync + 'static,
+{
+	let mut entity = about_entity(context);
+	if let Some(state) = watchdog {
+		if watch(context, entity.health(), state) {
+			entity = about_entity(context);
+		}
+	}
+	let selector = context.fq_name().map_or_else(
+		|| format!("{}/health", context.uuid()),
+		|fq_name| format!("{fq_name}/health"),
+	);
+	if let Err(error) = context.put_with(&selector, Message::encode(&entity)) {
+		warn!("publishing health failed: {error}");
+	}
+	entity.health().clone()
+}
+
+/// Drops the agent to `state`, if the `health` is failed and the agent is in a higher state.
+/// Returns whether the state has been changed.
+fn watch<P>(context: &ContextImpl<P>, health: &Health, state: &OperationState) -> bool
+where
+	P: Debug + Send + Sync + 'static,
+{
+	if health.status() != HealthStatus::Failed || context.state() <= *state {
+		return false;
+	}
+	let problems = health
+		.problems()
+		.map(ToString::to_string)
+		.collect::<Vec<String>>()
+		.join(", ");
+	warn!("health failed with {problems}, watchdog changes state to {state}");
+	if let Err(error) = context.set_state(state.clone()) {
+		error!("watchdog failed: {error}");
+	}
+	true
+}
+
+/// Supervises the health every `interval`
+async fn monitor_health<P>(
+	context: Arc<ContextImpl<P>>,
+	interval: Duration,
+	watchdog: Option<OperationState>,
+) where
+	P: Debug + Send + Sync + 'static,
+{
+	let mut ticker = tokio::time::interval(interval);
+	loop {
+		ticker.tick().await;
+		supervise(&context, watchdog.as_ref());
+	}
+}
+// endregion:   --- health monitoring
+
+// region:	   --- metrics endpoint
+/// The path the [`Metrics`] are served on
+const METRICS_PATH: &str = "/metrics";
+/// The time a client gets to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// The maximum number of bytes read per line of a request, longer lines are split
+const MAX_LINE_LENGTH: u64 = 8192;
+/// The maximum number of header lines read
+const MAX_HEADERS: usize = 100;
+
+/// Serves the [`Metrics`] in the Prometheus text format to the clients connecting to `listener`
+async fn serve_metrics(listener: TcpListener, metrics: Metrics) {
+	loop {
+		match listener.accept().await {
+			Ok((stream, _)) => {
+				let metrics = metrics.clone();
+				tokio::spawn(
+					async move {
+						if let Err(error) = answer_scrape(stream, &metrics).await {
+							warn!("serving metrics failed: {error}");
+						}
+					}
+					.in_current_span(),
+				);
+			}
+			Err(error) => warn!("accepting metrics connection failed: {error}"),
+		}
+	}
+}
+
+/// Answers a single HTTP request, only `GET /metrics` is served
+async fn answer_scrape(stream: TcpStream, metrics: &Metrics) -> std::io::Result<()> {
+	let mut reader = BufReader::new(stream);
+	let request = tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader))
+		.await
+		.map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "request timed out"))??;
+
+	let mut parts = request.split_whitespace();
+	let response = if parts.next() == Some("GET") && parts.next() == Some(METRICS_PATH) {
+		let body = prometheus(&metrics.snapshot());
+		format!(
+			"HTTP/1.1 200 OK\r\ncontent-type: text/plain; version=0.0.4\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+			body.len()
+		)
+	} else {
+		String::from("HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+	};
+	let mut stream = reader.into_inner();
+	stream.write_all(response.as_bytes()).await?;
+	stream.shutdown().await
+}
+
+/// Reads the request line and skips the headers
+async fn read_request(reader: &mut BufReader<TcpStream>) -> std::io::Result<String> {
+	let request = read_line(reader).await?;
+	for _ in 0..MAX_HEADERS {
+		// an empty line ends the headers, nothing read means the client is done
+		if read_line(reader).await?.trim().is_empty() {
+			break;
+		}
+	}
+	Ok(request)
+}
+
+/// Reads a line of at most [`MAX_LINE_LENGTH`] bytes
+async fn read_line(reader: &mut BufReader<TcpStream>) -> std::io::Result<String> {
+	let mut line = String::new();
+	reader
+		.take(MAX_LINE_LENGTH)
+		.read_line(&mut line)
+		.await?;
+	Ok(line)
+}
+// endregion:   --- metrics endpoint
+
 // region:	   --- UnconfiguredAgent
+/// Environment variable overriding the agents name
+const NAME_VAR: &str = "DIMAS_NAME";
+/// Environment variable overriding the agents prefix
+const PREFIX_VAR: &str = "DIMAS_PREFIX";
+
 /// A new Agent without the basic configuration decisions
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug)]
@@ -187,6 +455,12 @@ where
 {
 	name: Option<String>,
 	prefix: Option<String>,
+	log_level: LogLevel,
+	clock: Clock,
+	persistence: Option<Persistence<P>>,
+	metrics_listener: Option<String>,
+	health_interval: Duration,
+	watchdog: Option<OperationState>,
 	props: P,
 }
 
@@ -195,50 +469,187 @@ where
 	P: Debug + Send + Sync + 'static,
 {
 	/// Constructor
-	const fn new(properties: P) -> Self {
+	fn new(properties: P) -> Self {
 		Self {
 			name: None,
 			prefix: None,
+			log_level: LogLevel::Off,
+			clock: Clock::wall(),
+			persistence: None,
+			metrics_listener: None,
+			health_interval: HEALTH_INTERVAL,
+			watchdog: None,
 			props: properties,
 		}
 	}
 
-	/// Set a name
+	/// Set a name, for an agent configured with [`config`](Self::config)
+	/// the environment variable `DIMAS_NAME` takes precedence
 	#[must_use]
 	pub fn name(mut self, name: impl Into<String>) -> Self {
 		self.name = Some(name.into());
 		self
 	}
 
-	/// Set a prefix
+	/// Set a prefix, for an agent configured with [`config`](Self::config)
+	/// the environment variable `DIMAS_PREFIX` takes precedence
 	#[must_use]
 	pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
 		self.prefix = Some(prefix.into());
 		self
 	}
 
+	/// Set the initial level for streaming log records, default is [`LogLevel::Off`].
+	///
+	/// The records are published on `<prefix>/<name>/log` while the agent is running,
+	/// which needs tracing to be initialized with `init_tracing`.
+	/// The level can be changed at runtime via `dimasctl`.
+	#[must_use]
+	pub const fn log_level(mut self, level: LogLevel) -> Self {
+		self.log_level = level;
+		self
+	}
+
+	/// Set the [`Clock`] for timers and log record timestamps, default is the wall time.
+	///
+	/// An external clock follows the time published on its topic,
+	/// e.g. the simulation time of a simulator.
+	/// The timestamps of published messages stay on the wall time.
+	#[must_use]
+	pub fn clock(mut self, clock: Clock) -> Self {
+		self.clock = clock;
+		self
+	}
+
+	/// Serve the agents metrics in the Prometheus text format via HTTP on `address`,
+	/// e.g. `0.0.0.0:9464`, under the path `/metrics` while the agent is running.
+	///
+	/// The metrics are also available via `dimasctl` without a listener.
+	#[must_use]
+	pub fn metrics_listener(mut self, address: impl Into<String>) -> Self {
+		self.metrics_listener = Some(address.into());
+		self
+	}
+
+	/// Set the interval for evaluating and publishing the health checks, default is 1s.
+	///
+	/// The `About` information with the aggregated [`Health`] is published on `<prefix>/<name>/health`
+	/// while the agent is running, if health checks are registered.
+	#[must_use]
+	pub const fn health_interval(mut self, interval: Duration) -> Self {
+		self.health_interval = interval;
+		self
+	}
+
+	/// Activate a watchdog, which drops a running agent to `state`
+	/// when the evaluated [`Health`] is [`HealthStatus::Failed`].
+	/// The agent stays in that state until it is changed explicitly, e.g. via `dimasctl`.
+	#[must_use]
+	pub const fn watchdog(mut self, state: OperationState) -> Self {
+		self.watchdog = Some(state);
+		self
+	}
+
 	/// Set the [`Config`]uration.
 	/// An agent with [`OperationState`] `Configured` can be started
-	/// and will respond to commands from dimasctl/dimasmon
+	/// and will respond to commands from dimasctl/dimasmon.
+	///
+	/// The environment variables `DIMAS_NAME` and `DIMAS_PREFIX` set by a launcher
+	/// override the name and prefix. They are not applied to agents of a group or to test agents,
+	/// as these share the process with other agents.
+	///
+	/// # Errors
+	///
+	pub fn config(mut self, config: &Config) -> Result<Agent<P>> {
+		// name and prefix may be given by a launcher
+		self.name = std::env::var(NAME_VAR).ok().or(self.name);
+		self.prefix = std::env::var(PREFIX_VAR).ok().or(self.prefix);
+		let communicator = dimas_com::communicator::from(config)?;
+		self.configure(config, communicator)
+	}
+
+	/// Use the [`Config`]uration and the sessions of an [`AgentGroup`].
+	/// The agent has its own id, state and signal queryables,
+	/// it can be started alone or together with the group via [`AgentGroup::start_all`].
+	///
+	/// # Errors
+	///
+	pub fn group(self, group: &AgentGroup) -> Result<Agent<P>> {
+		self.configure(group.config(), group.communicator().share()?)
+	}
+
+	/// Use a [`Loopback`] for communication without any network access, intended for tests.
+	/// Agents using the same [`Loopback`] communicate with each other only,
+	/// the traffic is recorded in the [`Loopback`].
 	///
 	/// # Errors
 	///
-	pub fn config(self, config: &Config) -> Result<Agent<P>> {
+	#[cfg(feature = "testing")]
+	pub fn loopback(self, loopback: &Loopback) -> Result<Agent<P>> {
+		self.configure(&Config::minimal(), loopback.communicator())
+	}
+
+	/// Use a [`Loopback`] and the [`Config`]uration for a [`TestAgent`],
+	/// whose timers are fired by advancing its virtual clock instead of running on their own.
+	///
+	/// # Errors
+	///
+	#[cfg(feature = "testing")]
+	pub fn testing(mut self, loopback: &Loopback, config: &Config) -> Result<TestAgent<P>> {
+		self.clock = Clock::manual();
+		let agent = self.configure(config, loopback.communicator())?;
+		agent.context.set_manual_timers();
+		Ok(TestAgent::new(
+			agent.context.clone(),
+			agent,
+			loopback.clone(),
+		))
+	}
+
+	/// Configure the agent to use the given [`Communicator`]
+	/// # Errors
+	fn configure(
+		mut self,
+		config: &Config,
+		communicator: Arc<dyn Communicator>,
+	) -> Result<Agent<P>> {
+		// restore a persisted state, a broken one must not prevent the agent from working
+		if let Some(persistence) = self.persistence.as_mut() {
+			match persistence.restore() {
+				Ok(Some(props)) => self.props = props,
+				Ok(None) => {}
+				Err(error) => warn!("restoring persisted state failed: {error}"),
+			}
+		}
+
 		// we need an mpsc channel with a receiver behind a mutex guard
 		let (tx, rx) = mpsc::channel(32);
+		let log_stream = LogStream::new(self.log_level);
+		// the tasks of the agent are started within the scope of its log stream,
+		// so the stream receives the records of this agent only
+		let log_scope = log_stream.scope();
+		let _scope = log_scope.enter();
 		let context: Arc<ContextImpl<P>> = Arc::new(ContextImpl::new(
 			config,
+			communicator,
 			self.props,
 			self.name,
 			tx,
 			self.prefix,
-		)?);
+			log_stream.level(),
+			self.persistence,
+			self.clock,
+		));
 
 		let agent = Agent {
 			rx,
 			context,
 			liveliness: false,
 			liveliness_token: RwLock::new(None),
+			log_stream: Arc::new(Mutex::new(log_stream)),
+			metrics_listener: self.metrics_listener,
+			health_interval: self.health_interval,
+			watchdog: self.watchdog,
 		};
 
 		// add signal queryables
@@ -261,6 +672,23 @@ where
 				.add()?;
 		}
 
+		// follow the time published for an external clock
+		if let Some(topic) = agent.context.clock().topic() {
+			let clock = agent.context.clock().clone();
+			agent
+				.subscriber()
+				.selector(topic)
+				.put_callback(move |_ctx, message| {
+					let clock = clock.clone();
+					async move {
+						let nanos: u64 = message.decode()?;
+						clock.set(Duration::from_nanos(nanos))
+					}
+				})
+				.activation_state(OperationState::Created)
+				.add()?;
+		}
+
 		// set [`OperationState`] to Created
 		// This will also start the basic queryables
 		agent.context.set_state(OperationState::Created)?;
@@ -268,6 +696,32 @@ where
 		Ok(agent)
 	}
 }
+impl<P> UnconfiguredAgent<P>
+where
+	P: Debug + Send + Sync + Encode + for<'a> Decode<'a> + 'static,
+{
+	/// Persist the parameters and properties in the file named `filename`.
+	///
+	/// An existing file is searched like the configuration files,
+	/// otherwise it is created in the local dimas config directory.
+	/// The state is restored when the agent is configured
+	/// and stored on every parameter change, when the agent stops
+	/// and whenever `Context::persist` is called.
+	#[must_use]
+	pub fn persistence(mut self, filename: impl Into<String>) -> Self {
+		self.persistence = Some(Persistence::new(filename));
+		self
+	}
+
+	/// Persist the parameters and properties in the file at `path`, which is used as given.
+	///
+	/// The state is restored and stored like with [`UnconfiguredAgent::persistence`].
+	#[must_use]
+	pub fn persistence_path(mut self, path: impl Into<PathBuf>) -> Self {
+		self.persistence = Some(Persistence::with_path(path));
+		self
+	}
+}
 // endregion:   --- UnconfiguredAgent
 
 // region:	   --- Agent
@@ -285,6 +739,14 @@ where
 	/// The liveliness token - typically the uuid sent to other participants.
 	/// Is available in the [`LivelinessSubscriber`] callback
 	liveliness_token: RwLock<Option<LivelinessToken>>,
+	/// The stream of captured log records
+	log_stream: Arc<Mutex<LogStream>>,
+	/// The address to serve the metrics on
+	metrics_listener: Option<String>,
+	/// The interval for supervising the health
+	health_interval: Duration,
+	/// The state a failed agent is dropped to
+	watchdog: Option<OperationState>,
 }
 
 impl<P> Debug for Agent<P>
@@ -312,7 +774,7 @@ where
 {
 	/// Builder
 	#[allow(clippy::new_ret_no_self)]
-	pub const fn new(properties: P) -> UnconfiguredAgent<P> {
+	pub fn new(properties: P) -> UnconfiguredAgent<P> {
 		UnconfiguredAgent::new(properties)
 	}
 
@@ -321,6 +783,12 @@ where
 		self.liveliness = activate;
 	}
 
+	/// Get a [`BridgeBuilder`], the builder for a `Bridge` forwarding messages between two sessions.
+	#[must_use]
+	pub fn bridge(&self) -> BridgeBuilder<P, NoSelector, Storage<Box<dyn Responder>>> {
+		BridgeBuilder::new(self.context.clone()).storage(self.context.responders())
+	}
+
 	/// Get a [`LivelinessSubscriberBuilder`], the builder for a `LivelinessSubscriber`.
 	#[must_use]
 	pub fn liveliness_subscriber(
@@ -452,10 +920,78 @@ where
 		SubscriberBuilder::new(session_id, self.context.clone()).storage(self.context.responders())
 	}
 
+	/// Get a [`HealthCheckBuilder`] for the health check `name`,
+	/// whose status is returned by the `callback`.
+	/// An error returned by the callback is reported as failure.
+	#[must_use]
+	pub fn health_check<F>(&self, name: impl Into<String>, callback: F) -> HealthCheckBuilder<P>
+	where
+		F: Fn(Context<P>) -> Result<HealthStatus> + Send + Sync + 'static,
+	{
+		HealthCheckBuilder::new(
+			self.context.clone(),
+			self.context.health_checks(),
+			name,
+			Probe::Callback(Arc::new(callback)),
+		)
+	}
+
+	/// Get a [`HealthCheckBuilder`] for the heartbeat check `name`, which fails
+	/// if `Context::heartbeat` was not called for it within `max_age`.
+	#[must_use]
+	pub fn heartbeat(&self, name: impl Into<String>, max_age: Duration) -> HealthCheckBuilder<P> {
+		HealthCheckBuilder::new(
+			self.context.clone(),
+			self.context.health_checks(),
+			name,
+			Probe::Heartbeat {
+				max_age,
+				last: Duration::ZERO,
+			},
+		)
+	}
+
+	/// Get the state a failed agent is dropped to by the watchdog
+	#[cfg(feature = "testing")]
+	pub(crate) const fn watchdog_state(&self) -> Option<&OperationState> {
+		self.watchdog.as_ref()
+	}
+
+	/// Get a [`ParameterBuilder`], the builder for a runtime parameter
+	/// whose type is given by the `default` value.
+	/// A value in the `parameters` section of the agents [`Config`] overrides the default,
+	/// a value restored from the agents persistence overrides both.
+	#[must_use]
+	pub fn parameter(
+		&self,
+		name: impl Into<String>,
+		default: impl Into<ParameterValue>,
+	) -> ParameterBuilder<P> {
+		let name = name.into();
+		let configured = self.context.configured_parameter(&name);
+		let restored = self.context.restored_parameter(&name);
+		ParameterBuilder::new(
+			self.context.clone(),
+			self.context.registered_parameters(),
+			configured,
+			restored,
+			name,
+			default,
+		)
+	}
+
 	/// Get a [`TimerBuilder`], the builder for a [`Timer`].
 	#[must_use]
 	pub fn timer(&self) -> TimerBuilder<P, NoSelector, NoInterval, NoCallback, Storage<Timer<P>>> {
-		TimerBuilder::new(self.context.clone()).storage(self.context.timers())
+		TimerBuilder::new(self.context.clone())
+			.clock(self.context.clock().clone())
+			.storage(self.context.timers())
+	}
+
+	/// Stop all tasks of a stopped agent, including the signal queryables.
+	/// # Errors
+	pub(crate) fn retire(&self) -> Result<()> {
+		self.context.retire()
 	}
 
 	/// Start the agent.
@@ -463,8 +999,21 @@ where
 	/// The agent can be stopped properly using `ctrl-c`
 	///
 	/// # Errors
-	#[tracing::instrument(skip_all)]
+	/// - `ActivateLiveliness`: the liveliness token could not be declared
+	/// - `MetricsListener`: the metrics address could not be bound
+	/// - `ModifyStruct`, `ReadAccess`: a lock of the agent is poisoned
+	/// - any error raised by a task of the running agent
+	///
+	/// # Panics
+	/// if liveliness is enabled but the agent has no `default` session
 	pub async fn start(self) -> Result<Self> {
+		let log_scope = self.log_stream.lock().await.scope();
+		self.launch().instrument(log_scope).await
+	}
+
+	/// Start the agent within the scope of its log stream
+	#[tracing::instrument(skip_all)]
+	async fn launch(self) -> Result<Self> {
 		// activate sending liveliness
 		if self.liveliness {
 			let session = self.context.session("default");
@@ -490,11 +1039,56 @@ where
 
 		self.context.set_state(OperationState::Active)?;
 
+		// serve the metrics
+		let metrics_task = match &self.metrics_listener {
+			Some(address) => {
+				let listener = TcpListener::bind(address)
+					.await
+					.map_err(|source| Error::MetricsListener {
+						source: Box::new(source),
+					})?;
+				info!("serving metrics on http://{address}{METRICS_PATH}");
+				Some(tokio::spawn(
+					serve_metrics(listener, self.context.metrics().clone()).in_current_span(),
+				))
+			}
+			None => None,
+		};
+
+		// start streaming of log records
+		let log_task = tokio::spawn(forward_logs(self.context.clone(), self.log_stream.clone()));
+
+		// supervise the health
+		self.context.reset_heartbeats()?;
+		let has_checks = !self
+			.context
+			.health_checks()
+			.read()
+			.map_err(|_| Error::ReadAccess)?
+			.is_empty();
+		let health_task = has_checks.then(|| {
+			tokio::spawn(
+				monitor_health(
+					self.context.clone(),
+					self.health_interval,
+					self.watchdog.clone(),
+				)
+				.in_current_span(),
+			)
+		});
+
 		RunningAgent {
 			rx: self.rx,
 			context: self.context,
 			liveliness: self.liveliness,
 			liveliness_token: self.liveliness_token,
+			log_stream: self.log_stream,
+			log_task,
+			metrics_listener: self.metrics_listener,
+			metrics_task,
+			health_interval: self.health_interval,
+			watchdog: self.watchdog,
+			health_task,
 		}
 		.run()
 		.await
@@ -518,6 +1112,20 @@ where
 	/// The liveliness token - typically the uuid sent to other participants.
 	/// Is available in the [`LivelinessSubscriber`] callback
 	liveliness_token: RwLock<Option<LivelinessToken>>,
+	/// The stream of captured log records
+	log_stream: Arc<Mutex<LogStream>>,
+	/// The task publishing the log records
+	log_task: JoinHandle<()>,
+	/// The address to serve the metrics on
+	metrics_listener: Option<String>,
+	/// The task serving the metrics
+	metrics_task: Option<JoinHandle<()>>,
+	/// The interval for supervising the health
+	health_interval: Duration,
+	/// The state a failed agent is dropped to
+	watchdog: Option<OperationState>,
+	/// The task supervising the health
+	health_task: Option<JoinHandle<()>>,
 }
 
 impl<P> RunningAgent<P>
@@ -603,6 +1211,24 @@ where
 	pub fn stop(self) -> Result<Agent<P>> {
 		self.context.set_state(OperationState::Created)?;
 
+		// store the state
+		if let Err(error) = self.context.persist() {
+			warn!("persisting failed: {error}");
+		}
+
+		// stop streaming of log records
+		self.log_task.abort();
+
+		// stop serving the metrics
+		if let Some(metrics_task) = self.metrics_task {
+			metrics_task.abort();
+		}
+
+		// stop supervising the health
+		if let Some(health_task) = self.health_task {
+			health_task.abort();
+		}
+
 		// stop liveliness
 		if self.liveliness {
 			self.liveliness_token
@@ -615,6 +1241,10 @@ where
 			context: self.context,
 			liveliness: self.liveliness,
 			liveliness_token: self.liveliness_token,
+			log_stream: self.log_stream,
+			metrics_listener: self.metrics_listener,
+			health_interval: self.health_interval,
+			watchdog: self.watchdog,
 		};
 		Ok(r)
 	}
@@ -34,7 +34,10 @@
 #[cfg(doc)]
 use crate::agent::Agent;
 use crate::error::Error;
-use core::fmt::Debug;
+use crate::health::RegisteredCheck;
+use crate::parameter::RegisteredParameter;
+use crate::persistence::Persistence;
+use core::{fmt::Debug, time::Duration};
 use dimas_com::traits::LivelinessSubscriber;
 use dimas_com::traits::{
 	Communicator, CommunicatorMethods, Observer, Publisher, Querier, Responder,
@@ -44,17 +47,25 @@ use dimas_config::Config;
 use dimas_core::traits::Context;
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
+	enums::{LogLevel, OperationState, TaskSignal},
+	health::Health,
 	message_types::{Message, QueryableMsg},
+	metrics::{BYTES_PUBLISHED, MESSAGES_PUBLISHED, Metrics, QUERY_DURATION, STATE_TRANSITIONS},
+	parameter::{Parameter, ParameterValue},
 	traits::{Capability, ContextAbstraction},
+	utils::{LogLevelHandle, TracingHandle, tracing_handle},
 };
-use dimas_time::Timer;
+use dimas_time::{Clock, Timer};
 use std::{
 	collections::HashMap,
-	sync::{Arc, RwLock},
+	sync::{
+		Arc, RwLock,
+		atomic::{AtomicBool, Ordering},
+	},
+	time::Instant,
 };
-use tokio::sync::mpsc::Sender;
-use tracing::{Level, info, instrument};
+use tokio::sync::mpsc::{Receiver, Sender};
+use tracing::{Level, info, instrument, warn};
 use zenoh::Session;
 // endregion:	--- modules
 
@@ -80,14 +91,30 @@ where
 	prefix: Option<String>,
 	/// The [`Agent`]s current operational state
 	state: Arc<RwLock<OperationState>>,
+	/// The level for streaming the [`Agent`]s log records
+	log_level: LogLevelHandle,
 	/// A sender for sending signals to owner of context
 	sender: Sender<TaskSignal>,
 	/// The [`Agent`]s property structure
 	props: Arc<RwLock<P>>,
 	/// The [`Agent`]s [`Communicator`]
 	communicator: Arc<dyn Communicator>,
+	/// The [`Clock`] for timers and log record timestamps
+	clock: Clock,
 	/// Registered [`Timer`]
 	timers: Arc<RwLock<HashMap<String, Timer<P>>>>,
+	/// Whether the [`Timer`]s are fired manually instead of running on their own
+	manual_timers: Arc<AtomicBool>,
+	/// Registered [`Parameter`]s
+	parameters: Arc<RwLock<HashMap<String, RegisteredParameter<P>>>>,
+	/// The [`Config`] containing the configured parameter values
+	config: Config,
+	/// The optional [`Persistence`] for parameters and properties
+	persistence: Option<Arc<Persistence<P>>>,
+	/// The [`Metrics`] recorded by the capabilities
+	metrics: Metrics,
+	/// Registered health checks
+	health_checks: Arc<RwLock<HashMap<String, RegisteredCheck<P>>>>,
 }
 
 impl<P> ContextAbstraction for ContextImpl<P>
@@ -118,6 +145,137 @@ where
 		self.state.read().expect("snh").clone()
 	}
 
+	fn log_level(&self) -> LogLevel {
+		self.log_level.get()
+	}
+
+	fn set_log_level(&self, level: LogLevel) {
+		info!("changing log streaming level to {}", &level);
+		self.log_level.set(level);
+	}
+
+	fn parameter(&self, name: &str) -> Option<ParameterValue> {
+		self.parameters
+			.read()
+			.ok()?
+			.get(name)
+			.map(|registered| registered.parameter.value().clone())
+	}
+
+	fn parameters(&self) -> Vec<Parameter> {
+		let mut parameters: Vec<Parameter> = self
+			.parameters
+			.read()
+			.map(|parameters| {
+				parameters
+					.values()
+					.map(|registered| registered.parameter.clone())
+					.collect()
+			})
+			.unwrap_or_default();
+		parameters.sort_by(|a, b| a.name().cmp(b.name()));
+		parameters
+	}
+
+	fn set_parameter(&self, name: &str, value: ParameterValue) -> Result<()> {
+		// callbacks are called without holding the lock, so they may access the parameters
+		let (context, validation, on_change) = {
+			let parameters = self
+				.parameters
+				.read()
+				.map_err(|_| Error::ReadContext("parameters".into()))?;
+			let registered = parameters
+				.get(name)
+				.ok_or_else(|| dimas_core::error::Error::UnknownParameter { name: name.into() })?;
+			registered.parameter.check(&value)?;
+			// nothing to do, if the value does not change
+			if registered.parameter.value() == &value {
+				return Ok(());
+			}
+			let callbacks = (
+				registered.context.clone(),
+				registered.validation.clone(),
+				registered.on_change.clone(),
+			);
+			drop(parameters);
+			callbacks
+		};
+		if let Some(validation) = validation {
+			validation(context.clone(), &value)?;
+		}
+
+		info!("changing parameter {name} to {value}");
+		self.parameters
+			.write()
+			.map_err(|_| Error::ModifyStruct("parameters".into()))?
+			.get_mut(name)
+			.ok_or_else(|| Error::GetMut("parameters".into()))?
+			.parameter
+			.set_value(value.clone());
+
+		let result = on_change.map_or(Ok(()), |on_change| on_change(context, &value));
+		if let Err(error) = self.persist() {
+			warn!("persisting failed: {error}");
+		}
+		result
+	}
+
+	fn persist(&self) -> Result<()> {
+		if let Some(persistence) = &self.persistence {
+			let parameters = self
+				.parameters()
+				.into_iter()
+				.map(|parameter| (parameter.name().to_string(), parameter.value().clone()))
+				.collect();
+			persistence.store(&*self.read()?, parameters)?;
+		}
+		Ok(())
+	}
+
+	fn now(&self) -> Duration {
+		self.clock.now()
+	}
+
+	fn metrics(&self) -> &Metrics {
+		&self.metrics
+	}
+
+	fn tracing(&self) -> Option<&TracingHandle> {
+		tracing_handle()
+	}
+
+	fn heartbeat(&self, name: &str) {
+		let now = self.clock.now();
+		if let Ok(mut checks) = self.health_checks.write() {
+			if let Some(check) = checks.get_mut(name) {
+				check.beat(now);
+			} else {
+				warn!("heartbeat for unknown health check {name}");
+			}
+		}
+	}
+
+	fn health(&self) -> Health {
+		// callbacks are called without holding the lock, so they may send heartbeats
+		let checks: Vec<(String, RegisteredCheck<P>)> = self
+			.health_checks
+			.read()
+			.map(|checks| {
+				checks
+					.iter()
+					.map(|(name, check)| (name.clone(), check.clone()))
+					.collect()
+			})
+			.unwrap_or_default();
+		let now = self.clock.now();
+		Health::new(
+			checks
+				.iter()
+				.map(|(name, check)| check.evaluate(name, now))
+				.collect(),
+		)
+	}
+
 	fn uuid(&self) -> String {
 		self.uuid.clone()
 	}
@@ -201,44 +359,15 @@ where
 
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn put_with(&self, selector: &str, message: Message) -> Result<()> {
-		if self
-			.publishers()
-			.read()
-			.map_err(|_| Error::ReadContext("publishers".into()))?
-			.get(selector)
-			.is_some()
-		{
-			self.publishers()
-				.read()
-				.map_err(|_| Error::ReadContext("publishers".into()))?
-				.get(selector)
-				.ok_or_else(|| Error::Get("publishers".into()))?
-				.put(message)?;
-		} else {
-			self.communicator.put(selector, message)?;
-		};
-		Ok(())
+		// the communicator uses a registered publisher if there is one
+		self.record_put(selector, message, |message| {
+			self.communicator.put(selector, message)
+		})
 	}
 
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn delete_with(&self, selector: &str) -> Result<()> {
-		if self
-			.publishers()
-			.read()
-			.map_err(|_| Error::ReadContext("publishers".into()))?
-			.get(selector)
-			.is_some()
-		{
-			self.publishers()
-				.read()
-				.map_err(|_| Error::ReadContext("publishers".into()))?
-				.get(selector)
-				.ok_or_else(|| Error::Get("publishers".into()))?
-				.delete()?;
-		} else {
-			todo!(); //self.communicator.delete(selector)?;
-		}
-		Ok(())
+		self.communicator.delete(selector)
 	}
 
 	#[instrument(level = Level::ERROR, skip_all)]
@@ -248,35 +377,68 @@ where
 		message: Option<Message>,
 		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()> {
-		if self
-			.queriers()
-			.read()
-			.map_err(|_| Error::ReadContext("queries".into()))?
-			.get(selector)
-			.is_some()
-		{
-			self.queriers()
-				.read()
-				.map_err(|_| Error::ReadContext("queries".into()))?
-				.get(selector)
-				.ok_or_else(|| Error::Get("queries".into()))?
-				.get(message, callback)?;
-		} else {
-			self.communicator
-				.get(selector, message, callback)?;
-		};
-		Ok(())
+		let start = Instant::now();
+		// the communicator uses a registered querier if there is one
+		let result = self.communicator.get(selector, message, callback);
+		self.metrics
+			.observe(QUERY_DURATION, &[("topic", selector)], start.elapsed());
+		result
 	}
 
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn observe_with(&self, selector: &str, message: Option<Message>) -> Result<()> {
-		self.observers()
-			.read()
-			.map_err(|_| Error::ReadContext("observers".into()))?
-			.get(selector)
-			.ok_or_else(|| Error::Get("observers".into()))?
-			.request(message)?;
-		Ok(())
+		self.communicator.observe(selector, message)
+	}
+
+	#[instrument(level = Level::ERROR, skip_all)]
+	fn put_from(&self, session_id: &str, selector: &str, message: Message) -> Result<()> {
+		self.record_put(selector, message, |message| {
+			self.communicator
+				.put_from(session_id, selector, message)
+		})
+	}
+
+	#[instrument(level = Level::ERROR, skip_all)]
+	fn delete_from(&self, session_id: &str, selector: &str) -> Result<()> {
+		self.communicator
+			.delete_from(session_id, selector)
+	}
+
+	#[instrument(level = Level::ERROR, skip_all)]
+	fn get_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<()> {
+		let start = Instant::now();
+		let result = self
+			.communicator
+			.get_from(session_id, selector, message, callback);
+		self.metrics
+			.observe(QUERY_DURATION, &[("topic", selector)], start.elapsed());
+		result
+	}
+
+	#[instrument(level = Level::ERROR, skip_all)]
+	fn observe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+		message: Option<Message>,
+	) -> Result<()> {
+		self.communicator
+			.observe_from(session_id, selector, message)
+	}
+
+	fn subscribe_from(
+		&self,
+		session_id: &str,
+		selector: &str,
+	) -> Result<Receiver<Option<Message>>> {
+		self.communicator
+			.subscribe_from(session_id, selector)
 	}
 
 	#[instrument(level = Level::ERROR, skip_all)]
@@ -311,36 +473,69 @@ impl<P> ContextImpl<P>
 where
 	P: Send + Sync + 'static,
 {
-	/// Constructor for the [`ContextImpl`]
-	/// # Errors
+	/// Constructor for the [`ContextImpl`] using the given [`Communicator`]
+	#[allow(clippy::too_many_arguments)]
+	#[must_use]
 	pub fn new(
 		config: &Config,
+		communicator: Arc<dyn Communicator>,
 		props: P,
 		name: Option<String>,
 		sender: Sender<TaskSignal>,
 		prefix: Option<String>,
-	) -> Result<Self> {
-		let communicator = dimas_com::communicator::from(config)?;
+		log_level: LogLevelHandle,
+		persistence: Option<Persistence<P>>,
+		clock: Clock,
+	) -> Self {
 		let uuid = communicator.uuid();
-		Ok(Self {
+		Self {
 			uuid,
 			name,
 			prefix,
 			state: Arc::new(RwLock::new(OperationState::Created)),
+			log_level,
 			sender,
 			communicator,
 			props: Arc::new(RwLock::new(props)),
+			clock,
 			timers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
-		})
+			manual_timers: Arc::new(AtomicBool::new(false)),
+			parameters: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			config: config.clone(),
+			persistence: persistence.map(Arc::new),
+			metrics: Metrics::new(),
+			health_checks: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+		}
+	}
+
+	/// Put a `message` to `selector` with `put` and count it, if it was sent successfully.
+	/// The bytes of a shared value are not counted.
+	fn record_put<F>(&self, selector: &str, message: Message, put: F) -> Result<()>
+	where
+		F: FnOnce(Message) -> Result<()>,
+	{
+		let bytes = (!message.is_shared()).then(|| message.value().len() as u64);
+		put(message)?;
+		let labels = [("topic", selector)];
+		self.metrics
+			.increment(MESSAGES_PUBLISHED, &labels, 1);
+		if let Some(bytes) = bytes {
+			self.metrics
+				.increment(BYTES_PUBLISHED, &labels, bytes);
+		}
+		Ok(())
 	}
 
 	/// Set the [`Context`]s state
 	/// # Errors
 	fn modify_state_property(&self, state: OperationState) -> Result<()> {
+		let label = state.to_string();
 		*(self
 			.state
 			.write()
 			.map_err(|_| Error::ModifyStruct("state".into()))?) = state;
+		self.metrics
+			.increment(STATE_TRANSITIONS, &[("state", &label)], 1);
 		Ok(())
 	}
 
@@ -376,12 +571,62 @@ where
 		self.communicator.responders()
 	}
 
+	/// Get the clock
+	#[must_use]
+	pub const fn clock(&self) -> &Clock {
+		&self.clock
+	}
+
 	/// Get the timers
 	#[must_use]
 	pub fn timers(&self) -> Arc<RwLock<HashMap<String, Timer<P>>>> {
 		self.timers.clone()
 	}
 
+	/// Fire the [`Timer`]s manually, so that they are not started with the agent
+	#[cfg(feature = "testing")]
+	pub fn set_manual_timers(&self) {
+		self.manual_timers.store(true, Ordering::Relaxed);
+	}
+
+	/// Get the parameters
+	#[must_use]
+	pub fn registered_parameters(&self) -> Arc<RwLock<HashMap<String, RegisteredParameter<P>>>> {
+		self.parameters.clone()
+	}
+
+	/// Get the health checks
+	#[must_use]
+	pub fn health_checks(&self) -> Arc<RwLock<HashMap<String, RegisteredCheck<P>>>> {
+		self.health_checks.clone()
+	}
+
+	/// Restart the heartbeats of all health checks at the current time
+	/// # Errors
+	pub fn reset_heartbeats(&self) -> Result<()> {
+		let now = self.clock.now();
+		self.health_checks
+			.write()
+			.map_err(|_| Error::ModifyStruct("health checks".into()))?
+			.values_mut()
+			.for_each(|check| check.beat(now));
+		Ok(())
+	}
+
+	/// Get the configured value of a parameter in its text representation
+	#[must_use]
+	pub fn configured_parameter(&self, name: &str) -> Option<String> {
+		self.config.parameter(name).cloned()
+	}
+
+	/// Get the restored value of a parameter
+	#[must_use]
+	pub fn restored_parameter(&self, name: &str) -> Option<ParameterValue> {
+		self.persistence
+			.as_ref()
+			.and_then(|persistence| persistence.parameter(name))
+	}
+
 	/// Internal function for starting all registere)d tasks.
 	///
 	/// The tasks are started in the order
@@ -403,19 +648,28 @@ where
 		self.communicator
 			.manage_operation_state(&new_state)?;
 
-		// start all registered timers
-		self.timers
-			.write()
-			.map_err(|_| Error::ModifyStruct("timers".into()))?
-			.iter_mut()
-			.for_each(|timer| {
-				let _ = timer.1.manage_operation_state(&new_state);
-			});
+		// start all registered timers, unless they are fired manually
+		if !self.manual_timers.load(Ordering::Relaxed) {
+			self.timers
+				.write()
+				.map_err(|_| Error::ModifyStruct("timers".into()))?
+				.iter_mut()
+				.for_each(|timer| {
+					let _ = timer.1.manage_operation_state(&new_state);
+				});
+		}
 
 		self.modify_state_property(new_state)?;
 		Ok(())
 	}
 
+	/// Stop all registered tasks including those active in [`OperationState::Created`],
+	/// so that a stopped agent within a still running process does no longer respond.
+	/// # Errors
+	pub fn retire(&self) -> Result<()> {
+		self.downgrade_registered_tasks(OperationState::Error)
+	}
+
 	/// Internal function for stopping all registered tasks.
 	///
 	/// The tasks are stopped in reverse order of their start in [`Context::start_registered_tasks()`]
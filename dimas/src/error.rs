@@ -14,6 +14,30 @@ pub enum Error {
 		/// the original zenoh error
 		source: Box<dyn core::error::Error + Send + Sync>,
 	},
+	/// injecting a message failed
+	#[error("injecting a message failed: reason {source:?}")]
+	Inject {
+		/// the original zenoh error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// serving the metrics failed
+	#[error("serving the metrics failed: reason {source:?}")]
+	MetricsListener {
+		/// the original io error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// issuing a query failed
+	#[error("querying failed: reason {source:?}")]
+	Query {
+		/// the original zenoh error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// waiting for a message timed out
+	#[error("no message for {0} in time")]
+	Timeout(String),
+	/// handling an action did not finish in time
+	#[error("handling {0} did not finish in time")]
+	Unsettled(String),
 	/// manage state failed
 	#[error("managing state failed")]
 	ManageState,
@@ -0,0 +1,145 @@
+// Copyright © 2025 Stephan Kunz
+
+//! A group of [`Agent`]s running in one process and sharing the communication sessions.
+//!
+//! Every agent of the group has its own id, name, prefix, properties, state and signal queryables,
+//! so it is handled by `dimasctl` and `dimasmon` like an agent running in its own process.
+//! Communication between the agents of a group stays within the shared sessions.
+//!
+//! # Examples
+//! ```rust,no_run
+//! # use dimas::prelude::*;
+//! # #[derive(Debug)]
+//! # struct Sensor {}
+//! # #[derive(Debug)]
+//! # struct Controller {}
+//! # #[dimas::main]
+//! # async fn main() -> Result<()> {
+//! let mut group = AgentGroup::new(&Config::default())?;
+//!
+//! let sensor = Agent::new(Sensor {})
+//!   .prefix("robot")
+//!   .name("sensor")
+//!   .group(&group)?;
+//! // add publishers, timers, ... to the sensor
+//! group.add(sensor);
+//!
+//! let controller = Agent::new(Controller {})
+//!   .prefix("robot")
+//!   .name("controller")
+//!   .group(&group)?;
+//! // add subscribers, ... to the controller
+//! group.add(controller);
+//!
+//! // runs until all agents are stopped, e.g. with 'ctrl-c'
+//! group.start_all().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+// region:		--- modules
+use crate::agent::Agent;
+use core::{fmt::Debug, pin::Pin};
+use dimas_com::traits::Communicator;
+use dimas_config::Config;
+use dimas_core::Result;
+use std::{future::Future, sync::Arc};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for a started agent
+type AgentFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+// endregion:	--- types
+
+// region:		--- AgentGroup
+/// A group of [`Agent`]s sharing the [`Config`] and the communication sessions
+pub struct AgentGroup {
+	/// The [`Config`] of all agents
+	config: Config,
+	/// The [`Communicator`] providing the sessions
+	communicator: Arc<dyn Communicator>,
+	/// The agents added to the group
+	agents: Vec<AgentFuture>,
+}
+
+impl Debug for AgentGroup {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("AgentGroup")
+			.field("agents", &self.agents.len())
+			.finish_non_exhaustive()
+	}
+}
+
+impl AgentGroup {
+	/// Create a group opening the sessions given by the [`Config`]
+	/// # Errors
+	/// if the sessions can not be opened
+	pub fn new(config: &Config) -> Result<Self> {
+		let communicator = dimas_com::communicator::from(config)?;
+		Ok(Self {
+			config: config.clone(),
+			communicator,
+			agents: Vec::new(),
+		})
+	}
+
+	/// Get the [`Config`] of the group
+	#[must_use]
+	pub const fn config(&self) -> &Config {
+		&self.config
+	}
+
+	/// Get the [`Communicator`] providing the shared sessions
+	#[must_use]
+	pub fn communicator(&self) -> Arc<dyn Communicator> {
+		self.communicator.clone()
+	}
+
+	/// Add a configured [`Agent`] to be started with [`AgentGroup::start_all`]
+	pub fn add<P>(&mut self, agent: Agent<P>)
+	where
+		P: Debug + Send + Sync + 'static,
+	{
+		self.agents.push(Box::pin(async move {
+			// the process continues, so the stopped agent must not respond any longer
+			agent.start().await?.retire()
+		}));
+	}
+
+	/// Get the number of added agents
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.agents.len()
+	}
+
+	/// Check whether there are no added agents
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.agents.is_empty()
+	}
+
+	/// Start all added agents and run them until all of them are stopped.
+	///
+	/// # Errors
+	/// the first error of an agent
+	pub async fn start_all(self) -> Result<()> {
+		futures::future::join_all(self.agents)
+			.await
+			.into_iter()
+			.collect()
+	}
+}
+// endregion:	--- AgentGroup
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<AgentGroup>();
+	}
+}
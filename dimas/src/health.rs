@@ -0,0 +1,261 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Health checks of an [`Agent`], created with the [`HealthCheckBuilder`].
+//!
+//! A check is either evaluated by a callback or is a heartbeat,
+//! which fails if `Context::heartbeat` was not called within its allowed age.
+//! While the agent is running, the checks are evaluated periodically and the aggregated [`Health`]
+//! is published on `<prefix>/<name>/health` together with the `About` information.
+//! A watchdog configured with `UnconfiguredAgent::watchdog` drops the agent
+//! to a lower [`OperationState`] when the health is [`HealthStatus::Failed`].
+//!
+//! # Examples
+//! ```rust,no_run
+//! # use dimas::prelude::*;
+//! # #[derive(Debug)]
+//! # struct AgentProps { battery: f64 }
+//! # #[dimas::main]
+//! # async fn main() -> Result<()> {
+//! # let agent = Agent::new(AgentProps { battery: 1.0 })
+//! #   .watchdog(OperationState::Standby)
+//! #   .config(&Config::default())?;
+//! // fails if no sensor data arrived within 200ms
+//! agent.heartbeat("sensor", Duration::from_millis(200)).add()?;
+//! agent
+//!   .subscriber()
+//!   .topic("sensor")
+//!   .put_callback(|ctx, _message| async move {
+//!     ctx.heartbeat("sensor");
+//!     Ok(())
+//!   })
+//!   .add()?;
+//!
+//! // degraded with a low battery
+//! agent
+//!   .health_check("battery", |ctx| {
+//!     let status = if ctx.read()?.battery < 0.2 {
+//!       HealthStatus::Degraded
+//!     } else {
+//!       HealthStatus::Ok
+//!     };
+//!     Ok(status)
+//!   })
+//!   .add()?;
+//! # Ok(())
+//! # }
+//! ```
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use crate::error::Error;
+use core::time::Duration;
+use dimas_core::{
+	Result,
+	health::{CheckReport, HealthStatus},
+	traits::Context,
+};
+#[cfg(doc)]
+use dimas_core::{enums::OperationState, health::Health};
+use std::{
+	collections::HashMap,
+	fmt::Debug,
+	sync::{Arc, RwLock},
+};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for a health check callback
+#[allow(clippy::module_name_repetitions)]
+pub type ArcHealthCallback<P> =
+	Arc<dyn Fn(Context<P>) -> Result<HealthStatus> + Send + Sync + 'static>;
+
+/// The way a check determines its status
+pub(crate) enum Probe<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// A callback returning the status, an error counts as failure
+	Callback(ArcHealthCallback<P>),
+	/// A heartbeat with the time of the last beat
+	Heartbeat {
+		/// the allowed age of the last beat
+		max_age: Duration,
+		/// the time of the last beat
+		last: Duration,
+	},
+}
+
+impl<P> Clone for Probe<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn clone(&self) -> Self {
+		match self {
+			Self::Callback(callback) => Self::Callback(callback.clone()),
+			Self::Heartbeat { max_age, last } => Self::Heartbeat {
+				max_age: *max_age,
+				last: *last,
+			},
+		}
+	}
+}
+// endregion:	--- types
+
+// region:		--- RegisteredCheck
+/// A health check as stored in the agents context
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisteredCheck<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// The context handed to the callback
+	pub(crate) context: Context<P>,
+	/// The way to determine the status
+	pub(crate) probe: Probe<P>,
+	/// The status reported when the check fails
+	pub(crate) severity: HealthStatus,
+}
+
+impl<P> Clone for RegisteredCheck<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn clone(&self) -> Self {
+		Self {
+			context: self.context.clone(),
+			probe: self.probe.clone(),
+			severity: self.severity,
+		}
+	}
+}
+
+impl<P> Debug for RegisteredCheck<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RegisteredCheck")
+			.field("severity", &self.severity)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> RegisteredCheck<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Record a heartbeat at time `now`, checks without heartbeat are not affected
+	pub(crate) const fn beat(&mut self, now: Duration) {
+		if let Probe::Heartbeat { last, .. } = &mut self.probe {
+			*last = now;
+		}
+	}
+
+	/// Evaluate the check named `name` at time `now`
+	pub(crate) fn evaluate(&self, name: &str, now: Duration) -> CheckReport {
+		let (status, reason) = match &self.probe {
+			Probe::Callback(callback) => match callback(self.context.clone()) {
+				Ok(status) => (status, None),
+				Err(error) => (self.severity, Some(error.to_string())),
+			},
+			Probe::Heartbeat { max_age, last } => {
+				let age = now.saturating_sub(*last);
+				if age > *max_age {
+					(
+						self.severity,
+						Some(format!(
+							"last heartbeat {age:?} ago, allowed are {max_age:?}"
+						)),
+					)
+				} else {
+					(HealthStatus::Ok, None)
+				}
+			}
+		};
+		CheckReport::new(name.into(), status, reason)
+	}
+}
+// endregion:	--- RegisteredCheck
+
+// region:		--- HealthCheckBuilder
+/// A builder for a health check
+#[allow(clippy::module_name_repetitions)]
+pub struct HealthCheckBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	context: Context<P>,
+	storage: Arc<RwLock<HashMap<String, RegisteredCheck<P>>>>,
+	name: String,
+	probe: Probe<P>,
+	severity: HealthStatus,
+}
+
+impl<P> HealthCheckBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Construct a [`HealthCheckBuilder`] for the check `name` determining its status with `probe`
+	#[must_use]
+	pub(crate) fn new(
+		context: Context<P>,
+		storage: Arc<RwLock<HashMap<String, RegisteredCheck<P>>>>,
+		name: impl Into<String>,
+		probe: Probe<P>,
+	) -> Self {
+		Self {
+			context,
+			storage,
+			name: name.into(),
+			probe,
+			severity: HealthStatus::Failed,
+		}
+	}
+
+	/// Set the status reported when the callback returns an error
+	/// or the heartbeat is too old, default is [`HealthStatus::Failed`]
+	#[must_use]
+	pub const fn severity(mut self, severity: HealthStatus) -> Self {
+		self.severity = severity;
+		self
+	}
+
+	/// Build and add the check to the agents context, replacing a check with the same name.
+	/// The age of a heartbeat is counted from now until the agent is started.
+	/// # Errors
+	/// - `MutexPoison`: the lock of the agents health checks is poisoned
+	pub fn add(self) -> Result<()> {
+		let now = self.context.now();
+		let mut check = RegisteredCheck {
+			context: self.context,
+			probe: self.probe,
+			severity: self.severity,
+		};
+		check.beat(now);
+		self.storage
+			.write()
+			.map_err(|_| Error::MutexPoison(String::from("HealthCheckBuilder")))?
+			.insert(self.name, check);
+		Ok(())
+	}
+}
+// endregion:	--- HealthCheckBuilder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug)]
+	struct Props {}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<RegisteredCheck<Props>>();
+		is_normal::<HealthCheckBuilder<Props>>();
+	}
+}
@@ -16,6 +16,12 @@ doc_comment::doctest!("../README.md");
 pub mod agent;
 mod context;
 pub mod error;
+pub mod group;
+pub mod health;
+pub mod parameter;
+mod persistence;
+#[cfg(feature = "testing")]
+pub mod testing;
 // macro reexport
 pub use dimas_macros::main;
 
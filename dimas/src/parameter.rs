@@ -0,0 +1,260 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Runtime parameters of an [`Agent`], created with the [`ParameterBuilder`].
+//!
+//! A parameter has a name, a type given by its default value, an optional range and a description.
+//! A value given in the `parameters` section of the [`Config`] overrides the default,
+//! a value restored from the agents persistence overrides both.
+//! Parameters can be read and changed via the [`Context`] and remotely via `dimasctl param`.
+//!
+//! # Examples
+//! ```rust,no_run
+//! # use dimas::prelude::*;
+//! # #[derive(Debug)]
+//! # struct AgentProps { speed: f64 }
+//! # #[dimas::main]
+//! # async fn main() -> Result<()> {
+//! # let agent = Agent::new(AgentProps { speed: 1.0 }).config(&Config::default())?;
+//! agent
+//!   .parameter("speed", 1_i64)
+//!   .range(0.0, 10.0)
+//!   .description("the speed in m/s")
+//!   .on_change(|ctx, value| {
+//!     ctx.write()?.speed = value.as_f64().unwrap_or_default();
+//!     Ok(())
+//!   })
+//!   .add()?;
+//! # Ok(())
+//! # }
+//! ```
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use crate::error::Error;
+#[cfg(doc)]
+use dimas_config::Config;
+use dimas_core::{
+	Result,
+	parameter::{Parameter, ParameterValue},
+	traits::Context,
+};
+use std::{
+	collections::HashMap,
+	fmt::Debug,
+	sync::{Arc, RwLock},
+};
+use tracing::warn;
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for a parameters validation and change callback
+#[allow(clippy::module_name_repetitions)]
+pub type ArcParameterCallback<P> =
+	Arc<dyn Fn(Context<P>, &ParameterValue) -> Result<()> + Send + Sync + 'static>;
+// endregion:	--- types
+
+// region:		--- RegisteredParameter
+/// A [`Parameter`] together with its callbacks as stored in the agents context
+#[allow(clippy::module_name_repetitions)]
+pub struct RegisteredParameter<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// The parameter
+	pub(crate) parameter: Parameter,
+	/// The context handed to the callbacks
+	pub(crate) context: Context<P>,
+	/// Callback to reject a new value by returning an error
+	pub(crate) validation: Option<ArcParameterCallback<P>>,
+	/// Callback called after the value has changed
+	pub(crate) on_change: Option<ArcParameterCallback<P>>,
+}
+
+impl<P> Debug for RegisteredParameter<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RegisteredParameter")
+			.field("parameter", &self.parameter)
+			.finish_non_exhaustive()
+	}
+}
+// endregion:	--- RegisteredParameter
+
+// region:		--- ParameterBuilder
+/// A builder for a [`Parameter`]
+pub struct ParameterBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	context: Context<P>,
+	storage: Arc<RwLock<HashMap<String, RegisteredParameter<P>>>>,
+	configured: Option<String>,
+	restored: Option<ParameterValue>,
+	name: String,
+	default: ParameterValue,
+	range: Option<(f64, f64)>,
+	description: String,
+	validation: Option<ArcParameterCallback<P>>,
+	on_change: Option<ArcParameterCallback<P>>,
+}
+
+impl<P> ParameterBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Construct a [`ParameterBuilder`] for the parameter `name`,
+	/// whose type is given by the `default` value.
+	/// A `configured` value in its text representation overrides the default,
+	/// a `restored` value overrides both.
+	#[must_use]
+	pub fn new(
+		context: Context<P>,
+		storage: Arc<RwLock<HashMap<String, RegisteredParameter<P>>>>,
+		configured: Option<String>,
+		restored: Option<ParameterValue>,
+		name: impl Into<String>,
+		default: impl Into<ParameterValue>,
+	) -> Self {
+		Self {
+			context,
+			storage,
+			configured,
+			restored,
+			name: name.into(),
+			default: default.into(),
+			range: None,
+			description: String::new(),
+			validation: None,
+			on_change: None,
+		}
+	}
+
+	/// Set the allowed range for a numeric parameter
+	#[must_use]
+	pub const fn range(mut self, min: f64, max: f64) -> Self {
+		self.range = Some((min, max));
+		self
+	}
+
+	/// Set the description
+	#[must_use]
+	pub fn description(mut self, description: impl Into<String>) -> Self {
+		self.description = description.into();
+		self
+	}
+
+	/// Set a validation callback, which rejects a new value by returning an error
+	#[must_use]
+	pub fn validation<F>(mut self, callback: F) -> Self
+	where
+		F: Fn(Context<P>, &ParameterValue) -> Result<()> + Send + Sync + 'static,
+	{
+		self.validation = Some(Arc::new(callback));
+		self
+	}
+
+	/// Set a callback, which is called after the value has changed.
+	/// It is also called once when adding the parameter,
+	/// if a configured or restored value overrides the default.
+	#[must_use]
+	pub fn on_change<F>(mut self, callback: F) -> Self
+	where
+		F: Fn(Context<P>, &ParameterValue) -> Result<()> + Send + Sync + 'static,
+	{
+		self.on_change = Some(Arc::new(callback));
+		self
+	}
+
+	/// Build and add the parameter to the agents context.
+	/// A restored value not matching the parameters type or range is ignored.
+	/// # Errors
+	/// if the configured value does not match the parameters type or range
+	/// or the change callback for an overriding value fails
+	pub fn add(self) -> Result<()> {
+		let mut parameter = Parameter::new(self.name, self.default, self.range, self.description);
+		parameter.check(parameter.default())?;
+		if let Some(text) = self.configured {
+			let value = parameter.default().parse_as(&text)?;
+			parameter.check(&value)?;
+			parameter.set_value(value);
+		}
+		if let Some(value) = self.restored {
+			match parameter.check(&value) {
+				Ok(()) => parameter.set_value(value),
+				Err(error) => warn!("ignoring restored value: {error}"),
+			}
+		}
+
+		// the callback is called without holding the lock, so it may access the parameters
+		let changed = (parameter.value() != parameter.default()).then(|| parameter.value().clone());
+		self.storage
+			.write()
+			.map_err(|_| Error::MutexPoison(String::from("ParameterBuilder")))?
+			.insert(
+				parameter.name().to_string(),
+				RegisteredParameter {
+					parameter,
+					context: self.context.clone(),
+					validation: self.validation,
+					on_change: self.on_change.clone(),
+				},
+			);
+		match (changed, self.on_change) {
+			(Some(value), Some(on_change)) => on_change(self.context, &value),
+			_ => Ok(()),
+		}
+	}
+}
+// endregion:	--- ParameterBuilder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug)]
+	struct Props {}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<RegisteredParameter<Props>>();
+		is_normal::<ParameterBuilder<Props>>();
+	}
+
+	#[derive(Debug)]
+	struct Speed {
+		speed: i64,
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn configured_value() -> Result<()> {
+		let loopback = dimas_com::communicator::Loopback::new()?;
+		let mut config = dimas_config::Config::minimal();
+		config.set("parameters/speed", "3")?;
+		let agent = crate::agent::Agent::new(Speed { speed: 0 }).testing(&loopback, &config)?;
+		let on_change = |ctx: Context<Speed>, value: &ParameterValue| {
+			ctx.write()?.speed = value.as_i64().unwrap_or_default();
+			Ok(())
+		};
+		agent
+			.parameter("speed", 1_i64)
+			.on_change(on_change)
+			.add()?;
+		assert_eq!(agent.read()?.speed, 3);
+
+		// the callback is not called for the default value
+		agent.write()?.speed = 0;
+		agent
+			.parameter("acceleration", 1_i64)
+			.on_change(on_change)
+			.add()?;
+		assert_eq!(agent.read()?.speed, 0);
+		Ok(())
+	}
+}
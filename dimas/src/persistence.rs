@@ -0,0 +1,182 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Persistence of an [`Agent`]s parameters and properties.
+//!
+//! The state is stored bitcode encoded in a file,
+//! which is resolved with [`dimas_config::persistence_file`] or given as path.
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use crate::error::Error;
+use bitcode::{Decode, Encode};
+use dimas_core::{Result, parameter::ParameterValue};
+use std::{collections::HashMap, fmt::Debug, path::PathBuf};
+// endregion:	--- modules
+
+// region:		--- PersistedState
+/// The content of a persistence file
+#[derive(Debug, Default, Encode, Decode)]
+struct PersistedState {
+	/// The current values of the parameters
+	parameters: Vec<(String, ParameterValue)>,
+	/// The encoded properties
+	props: Vec<u8>,
+}
+// endregion:	--- PersistedState
+
+// region:		--- Persistence
+/// Stores and restores the state of an [`Agent`].
+///
+/// The coding functions for the properties are captured when the persistence is set up,
+/// so the properties type only needs to implement [`Encode`] and [`Decode`] there.
+pub struct Persistence<P> {
+	/// The name of the file containing the state
+	filename: String,
+	/// The path of the file, available after restoring or when given
+	path: Option<PathBuf>,
+	/// Encoder for the properties
+	encode: fn(&P) -> Vec<u8>,
+	/// Decoder for the properties
+	decode: fn(&[u8]) -> Result<P>,
+	/// The restored parameter values, applied when a parameter is added
+	parameters: HashMap<String, ParameterValue>,
+}
+
+impl<P> Debug for Persistence<P> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Persistence")
+			.field("filename", &self.filename)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> Persistence<P>
+where
+	P: Encode + for<'a> Decode<'a>,
+{
+	/// Constructor for the persistence in the file named `filename`
+	pub fn new(filename: impl Into<String>) -> Self {
+		Self {
+			filename: filename.into(),
+			path: None,
+			encode: |props| bitcode::encode(props),
+			decode: |bytes| Ok(bitcode::decode(bytes)?),
+			parameters: HashMap::new(),
+		}
+	}
+
+	/// Constructor for the persistence in the file at `path`, which is used without resolving
+	pub fn with_path(path: impl Into<PathBuf>) -> Self {
+		let path = path.into();
+		Self {
+			filename: path.display().to_string(),
+			path: Some(path),
+			..Self::new("")
+		}
+	}
+}
+
+impl<P> Persistence<P> {
+	/// Resolve the file, if not given as path, and restore the stored state, returning the stored properties.
+	/// The stored parameter values are kept until their parameters are added.
+	/// Without a stored state nothing is restored.
+	/// # Errors
+	/// if the file can not be resolved, is not readable or its content is invalid
+	pub fn restore(&mut self) -> Result<Option<P>> {
+		let path = if let Some(path) = &self.path {
+			path.clone()
+		} else {
+			let path = dimas_config::persistence_file(&self.filename)?;
+			self.path = Some(path.clone());
+			path
+		};
+		if !path.is_file() {
+			return Ok(None);
+		}
+		let content = std::fs::read(&path)?;
+		let state: PersistedState = bitcode::decode(&content)?;
+		self.parameters = state.parameters.into_iter().collect();
+		Ok(Some((self.decode)(&state.props)?))
+	}
+
+	/// Get the restored value of a parameter
+	pub fn parameter(&self, name: &str) -> Option<ParameterValue> {
+		self.parameters.get(name).cloned()
+	}
+
+	/// Store the `props` and the `parameters`.
+	/// The file is replaced atomically, so a failure never leaves a broken state behind.
+	/// # Errors
+	/// if the file has neither been given nor resolved by restoring or is not writable
+	pub fn store(&self, props: &P, parameters: Vec<(String, ParameterValue)>) -> Result<()> {
+		let path = self
+			.path
+			.as_ref()
+			.ok_or_else(|| Error::Get("persistence file".into()))?;
+		let state = PersistedState {
+			parameters,
+			props: (self.encode)(props),
+		};
+		let mut temp = path.clone().into_os_string();
+		temp.push(".tmp");
+		std::fs::write(&temp, bitcode::encode(&state))?;
+		std::fs::rename(&temp, path)?;
+		Ok(())
+	}
+}
+// endregion:	--- Persistence
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[derive(Debug, PartialEq, Encode, Decode)]
+	struct Props {
+		counter: u32,
+	}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Persistence<Props>>();
+	}
+
+	#[test]
+	fn unresolved() {
+		let persistence = Persistence::<Props>::new("dimas-persistence-test.state");
+		assert!(
+			persistence
+				.store(&Props { counter: 0 }, Vec::new())
+				.is_err()
+		);
+	}
+
+	#[test]
+	fn store_and_restore() -> Result<()> {
+		let path = std::env::temp_dir().join(format!(
+			"dimas-persistence-test-{}.state",
+			std::process::id()
+		));
+		let mut persistence = Persistence::<Props>::with_path(&path);
+		assert!(persistence.restore()?.is_none());
+
+		persistence.store(
+			&Props { counter: 42 },
+			vec![(String::from("speed"), ParameterValue::Float(2.5))],
+		)?;
+		let mut restored = Persistence::<Props>::with_path(&path);
+		assert_eq!(restored.restore()?, Some(Props { counter: 42 }));
+		assert_eq!(
+			restored.parameter("speed"),
+			Some(ParameterValue::Float(2.5))
+		);
+		assert!(restored.parameter("other").is_none());
+
+		std::fs::remove_file(path)?;
+		Ok(())
+	}
+}
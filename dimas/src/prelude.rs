@@ -28,12 +28,23 @@ pub use zenoh::sample::Locality;
 
 // dimas stuff
 pub use crate::agent::Agent;
+pub use crate::group::AgentGroup;
+#[cfg(feature = "testing")]
+pub use crate::testing::{TestAgent, settle};
+#[cfg(feature = "testing")]
+pub use dimas_com::communicator::{Fault, FaultKind, Loopback, Traffic, TrafficKind};
+pub use dimas_com::zenoh::BridgeDirection;
 pub use dimas_config::Config;
 pub use dimas_core::Result;
+pub use dimas_core::enums::OperationState;
+pub use dimas_core::health::{CheckReport, Health, HealthStatus};
 pub use dimas_core::message_types::{
-	ControlResponse, Message, ObservableResponse, QueryMsg, QueryableMsg,
+	Attachments, ControlResponse, Message, ObservableResponse, QueryMsg, QueryableMsg, SampleInfo,
 };
+pub use dimas_core::metrics::{Metric, MetricValue, Metrics};
 pub use dimas_core::traits::Context;
-pub use dimas_core::utils::init_tracing;
+#[cfg(feature = "otlp")]
+pub use dimas_core::utils::init_tracing_otlp;
+pub use dimas_core::utils::{TracingHandle, init_tracing, init_tracing_from};
 pub use dimas_macros::main;
-pub use dimas_time::Timer;
+pub use dimas_time::{Clock, Timer};
@@ -0,0 +1,476 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `testing` provides the [`TestAgent`] for driving an [`Agent`] deterministically in tests.
+//!
+//! A [`TestAgent`] communicates via a [`Loopback`] and does not run its [`Timer`]s on their own.
+//! Instead they are fired in order of their due time by advancing a virtual clock,
+//! which is the agents manual [`Clock`] giving the time of `Context::now`.
+//! Messages can be injected into the agents subscribers, its queryables can be queried
+//! and the messages it publishes can be awaited.
+//!
+//! # Examples
+//! ```rust
+//! use dimas::prelude::*;
+//!
+//! #[derive(Debug)]
+//! struct Props {
+//!     count: u32,
+//! }
+//!
+//! #[tokio::main(flavor = "multi_thread")]
+//! async fn main() -> Result<()> {
+//!     let loopback = Loopback::new()?;
+//!     let mut agent = Agent::new(Props { count: 0 })
+//!         .prefix("test")
+//!         .name("counter")
+//!         .testing(&loopback, &Config::minimal())?;
+//!     agent.publisher().topic("count").add()?;
+//!     agent
+//!         .timer()
+//!         .name("count")
+//!         .delay(Duration::from_millis(100))
+//!         .interval(Duration::from_millis(100))
+//!         .callback(|ctx| -> Result<()> {
+//!             ctx.write()?.count += 1;
+//!             let count = ctx.read()?.count;
+//!             ctx.put("count", Message::encode(&count))
+//!         })
+//!         .add()?;
+//!
+//!     agent.start().await?;
+//!     agent.advance(Duration::from_millis(250)).await?;
+//!     assert_eq!(agent.read()?.count, 2);
+//!     let message = agent
+//!         .expect_put("count", Duration::from_millis(100))
+//!         .await?;
+//!     assert_eq!(message.decode::<u32>()?, 1);
+//!     agent.stop()
+//! }
+//! ```
+
+// region:		--- modules
+use crate::agent::{Agent, supervise};
+use crate::context::ContextImpl;
+use crate::error::Error;
+use core::{fmt::Debug, ops::Deref, time::Duration};
+use dimas_com::communicator::{FaultKind, Loopback, TrafficKind};
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	health::Health,
+	message_types::{Attachments, Message, QueryableMsg},
+	traits::ContextAbstraction,
+	utils::selector_from,
+};
+#[cfg(doc)]
+use dimas_time::{Clock, Timer};
+use std::{
+	collections::HashMap,
+	sync::{Arc, RwLockReadGuard, RwLockWriteGuard},
+};
+use zenoh::sample::SampleKind;
+// endregion:	--- modules
+
+// region:		--- types
+/// the time the agents tasks are given at most for declaring their responders
+/// and for handling the messages caused by an action
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(5);
+/// the interval for looking up the agent and the recorded traffic while awaiting an effect
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+// endregion:	--- types
+
+// region:		--- TestAgent
+/// An [`Agent`] whose [`Timer`]s are fired by advancing a virtual clock,
+/// created with `UnconfiguredAgent::testing`.
+///
+/// The [`Agent`]s builder methods are available via [`Deref`].
+/// Timer callbacks are called on the tests task, so a test using queries
+/// needs a `multi_thread` runtime.
+pub struct TestAgent<P>
+where
+	P: Debug + Send + Sync + 'static,
+{
+	/// The driven agent
+	agent: Agent<P>,
+	/// The agents context
+	context: Arc<ContextImpl<P>>,
+	/// The [`Loopback`] the agent communicates with
+	loopback: Loopback,
+	/// The virtual time since the start
+	now: Duration,
+	/// The virtual time of the next firing per timer
+	schedule: HashMap<String, Duration>,
+	/// The position in the recorded traffic following the last message handed out per selector
+	cursors: HashMap<String, usize>,
+}
+
+impl<P> Debug for TestAgent<P>
+where
+	P: Debug + Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TestAgent")
+			.field("agent", &self.agent)
+			.field("now", &self.now)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> Deref for TestAgent<P>
+where
+	P: Debug + Send + Sync + 'static,
+{
+	type Target = Agent<P>;
+
+	fn deref(&self) -> &Self::Target {
+		&self.agent
+	}
+}
+
+impl<P> TestAgent<P>
+where
+	P: Debug + Send + Sync + 'static,
+{
+	/// Constructor for a [`TestAgent`]
+	pub(crate) fn new(context: Arc<ContextImpl<P>>, agent: Agent<P>, loopback: Loopback) -> Self {
+		loopback.attach(context.metrics().clone(), &context.responders());
+		Self {
+			agent,
+			context,
+			loopback,
+			now: Duration::ZERO,
+			schedule: HashMap::new(),
+			cursors: HashMap::new(),
+		}
+	}
+
+	/// Get the [`Loopback`] the agent communicates with
+	#[must_use]
+	pub const fn loopback(&self) -> &Loopback {
+		&self.loopback
+	}
+
+	/// Get the virtual time passed since the start, which is also the time of the agents [`Clock`]
+	#[must_use]
+	pub const fn now(&self) -> Duration {
+		self.now
+	}
+
+	/// Get the current [`OperationState`]
+	#[must_use]
+	pub fn state(&self) -> OperationState {
+		self.context.state()
+	}
+
+	/// Set the [`OperationState`], timers are not affected.
+	/// # Errors
+	pub fn set_state(&self, state: OperationState) -> Result<()> {
+		self.context.set_state(state)
+	}
+
+	/// Gives read access to the agents properties
+	/// # Errors
+	pub fn read(&self) -> Result<RwLockReadGuard<'_, P>> {
+		self.context.read()
+	}
+
+	/// Gives write access to the agents properties
+	/// # Errors
+	pub fn write(&self) -> Result<RwLockWriteGuard<'_, P>> {
+		self.context.write()
+	}
+
+	/// Start the agent by setting its [`OperationState`] to `Active`
+	/// and await the declaration of its subscribers and queryables.
+	/// Timers without a delay are fired immediately, like running timers.
+	/// # Errors
+	/// if a state transition or a timer callback fails
+	/// or a subscriber or queryable is not declared in time
+	pub async fn start(&mut self) -> Result<()> {
+		self.context.set_state(OperationState::Active)?;
+		self.declared().await?;
+		self.now = Duration::ZERO;
+		self.schedule.clear();
+		self.context.clock().set(Duration::ZERO)?;
+		self.context.reset_heartbeats()?;
+		self.advance(Duration::ZERO).await
+	}
+
+	/// Evaluate the health checks at the current virtual time like a running agent does periodically,
+	/// including the watchdog and the publication on `<prefix>/<name>/health`.
+	#[must_use]
+	pub fn supervise(&self) -> Health {
+		supervise(&self.context, self.agent.watchdog_state())
+	}
+
+	/// Stop the agent by setting its [`OperationState`] to `Created`.
+	/// # Errors
+	pub fn stop(&self) -> Result<()> {
+		self.context.set_state(OperationState::Created)
+	}
+
+	/// Advance the virtual clock by `duration`, firing the due timers in order of
+	/// their due time and name. After each firing the messages put meanwhile are awaited
+	/// to be handled by the subscribers of the agents attached to the [`Loopback`].
+	/// Timers added meanwhile start at the current virtual time.
+	/// # Errors
+	/// stops at the first failing timer callback and returns its error,
+	/// or if the messages are not handled in time
+	pub async fn advance(&mut self, duration: Duration) -> Result<()> {
+		let until = self.now + duration;
+		while let Some((name, time)) = self.next_due(until)? {
+			self.now = time;
+			self.context.clock().set(time)?;
+			let position = self.loopback.recorded();
+			let handled = self.loopback.handled();
+			self.fire(&name)?;
+			self.settled(&name, position, None, handled)
+				.await?;
+		}
+		self.now = until;
+		self.context.clock().set(until)
+	}
+
+	/// Find the timer due next until `until`
+	fn next_due(&mut self, until: Duration) -> Result<Option<(String, Duration)>> {
+		let timers = self.context.timers();
+		let timers = timers.read().map_err(|_| Error::ReadAccess)?;
+		let now = self.now;
+		self.schedule
+			.retain(|name, _| timers.contains_key(name));
+		for (name, timer) in timers.iter() {
+			self.schedule
+				.entry(name.clone())
+				.or_insert_with(|| now + timer.delay().unwrap_or_default());
+		}
+		drop(timers);
+
+		Ok(self
+			.schedule
+			.iter()
+			.filter(|(_, time)| **time <= until)
+			.min_by(|a, b| a.1.cmp(b.1).then_with(|| a.0.cmp(b.0)))
+			.map(|(name, time)| (name.clone(), *time)))
+	}
+
+	/// Fire the timer `name` and schedule its next firing
+	fn fire(&mut self, name: &str) -> Result<()> {
+		let timers = self.context.timers();
+		let timers = timers.read().map_err(|_| Error::ReadAccess)?;
+		let timer = timers
+			.get(name)
+			.ok_or_else(|| Error::Get("timers".into()))?;
+		let interval = timer.interval();
+		if interval.is_zero() {
+			// such a timer would fire endlessly
+			self.schedule.remove(name);
+		} else {
+			self.schedule
+				.insert(name.into(), self.now + interval);
+		}
+		let result = timer.fire();
+		drop(timers);
+		result
+	}
+
+	/// Inject a put [`Message`] with its [`Attachments`] into the subscribers of `topic`
+	/// and await the subscribers of the agents attached to the [`Loopback`] handling it
+	/// and the messages put meanwhile.
+	/// # Errors
+	/// if the message can not be put or is not handled in time
+	pub async fn inject(&self, topic: &str, message: Message) -> Result<()> {
+		let selector = selector_from(topic, self.context.prefix());
+		let position = self.loopback.recorded();
+		let handled = self.loopback.handled();
+		self.loopback
+			.session()
+			.put(&selector, message.to_bytes())
+			.attachment(message.attachments().map(Attachments::to_bytes))
+			.await
+			.map_err(|source| Error::Inject { source })?;
+		self.settled(&selector, position, Some(&selector), handled)
+			.await
+	}
+
+	/// Await the declaration of the agents responders,
+	/// detected by the matching status of a publisher respectively a querier on the [`Loopback`]
+	async fn declared(&self) -> Result<()> {
+		let responders: Vec<(String, bool)> = self
+			.context
+			.responders()
+			.read()
+			.map_err(|_| Error::ReadAccess)?
+			.values()
+			.map(|responder| (responder.selector().to_string(), responder.is_subscribed()))
+			.collect();
+		let session = self.loopback.session();
+		for (selector, subscribed) in responders {
+			if subscribed {
+				let publisher = session
+					.declare_publisher(selector.clone())
+					.await
+					.map_err(|source| Error::Inject { source })?;
+				settle(&selector, async || {
+					publisher
+						.matching_status()
+						.await
+						.is_ok_and(|status| status.matching())
+				})
+				.await?;
+			} else {
+				let querier = session
+					.declare_querier(selector.clone())
+					.await
+					.map_err(|source| Error::Query { source })?;
+				settle(&selector, async || {
+					querier
+						.matching_status()
+						.await
+						.is_ok_and(|status| status.matching())
+				})
+				.await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Await the running subscribers of the agents attached to the [`Loopback`] handling
+	/// the message put to `injected` and the ones put from the traffic `position` on,
+	/// including the ones put by the handling.
+	/// `handled` is the number of messages handled before.
+	async fn settled(
+		&self,
+		action: &str,
+		position: usize,
+		injected: Option<&str>,
+		handled: u64,
+	) -> Result<()> {
+		let injected = injected.map_or(0, |selector| self.loopback.deliveries(selector));
+		settle(action, async || {
+			let expected: u64 = self
+				.loopback
+				.traffic_since(position)
+				.iter()
+				.filter(|(_, traffic)| {
+					traffic.kind == TrafficKind::Put
+						&& !matches!(traffic.fault, Some(FaultKind::Drop | FaultKind::Fail))
+				})
+				.map(|(_, traffic)| self.loopback.deliveries(&traffic.selector))
+				.sum();
+			self.loopback.handled() >= handled + injected + expected
+		})
+		.await
+	}
+
+	/// Issue a query to `topic` with an optional [`Message`] and its [`Attachments`]
+	/// and collect the replies.
+	/// # Errors
+	pub async fn get(&self, topic: &str, message: Option<Message>) -> Result<Vec<QueryableMsg>> {
+		let selector = selector_from(topic, self.context.prefix());
+		let session = self.loopback.session();
+		let replies = match message {
+			Some(message) => {
+				session
+					.get(&selector)
+					.payload(message.to_bytes())
+					.attachment(message.attachments().map(Attachments::to_bytes))
+					.await
+			}
+			None => session.get(&selector).await,
+		}
+		.map_err(|source| Error::Query { source })?;
+
+		let mut result = Vec::new();
+		while let Ok(reply) = replies.recv_async().await {
+			match reply.result() {
+				Ok(sample) if sample.kind() == SampleKind::Put => {
+					let attachments = sample.attachment().and_then(|attachment| {
+						Attachments::from_bytes(&attachment.to_bytes()).ok()
+					});
+					result.push(
+						QueryableMsg::new(sample.payload().to_bytes().into_owned())
+							.with_attachments(attachments),
+					);
+				}
+				Ok(_) => {}
+				Err(error) => {
+					return Err(Error::Query {
+						source: error.payload().try_to_string()?.into(),
+					}
+					.into());
+				}
+			}
+		}
+		Ok(result)
+	}
+
+	/// Await the next [`Message`] the agent puts to `topic`,
+	/// messages dropped or failed by an injected fault are skipped,
+	/// as well as the ones forgotten by clearing the traffic of the [`Loopback`].
+	/// # Errors
+	/// - `Timeout`: no message arrives within `timeout`
+	pub async fn expect_put(&mut self, topic: &str, timeout: Duration) -> Result<Message> {
+		let selector = selector_from(topic, self.context.prefix());
+		let uuid = self.context.uuid();
+		let result = tokio::time::timeout(timeout, async {
+			loop {
+				let cursor = self
+					.cursors
+					.get(&selector)
+					.copied()
+					.unwrap_or_default();
+				let found = self
+					.loopback
+					.traffic_since(cursor)
+					.into_iter()
+					.find(|(_, traffic)| {
+						traffic.uuid == uuid
+							&& traffic.kind == TrafficKind::Put
+							&& traffic.selector == selector
+							&& !matches!(traffic.fault, Some(FaultKind::Drop | FaultKind::Fail))
+					});
+				if let Some((position, Some(message))) =
+					found.map(|(position, traffic)| (position, traffic.message))
+				{
+					self.cursors
+						.insert(selector.clone(), position + 1);
+					return message;
+				}
+				tokio::time::sleep(POLL_INTERVAL).await;
+			}
+		})
+		.await;
+		result.map_err(|_| Error::Timeout(selector.clone()).into())
+	}
+}
+
+/// Poll `done` until it holds, at most for 5 seconds,
+/// to await an effect instead of sleeping for a guessed time.
+/// # Errors
+/// - `Unsettled`: `done` does not hold in time, the error names the `action`
+pub async fn settle(action: &str, mut done: impl AsyncFnMut() -> bool) -> Result<()> {
+	tokio::time::timeout(SETTLE_TIMEOUT, async {
+		while !done().await {
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+	})
+	.await
+	.map_err(|_| Error::Unsettled(action.into()).into())
+}
+// endregion:	--- TestAgent
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[derive(Debug)]
+	struct Props {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<TestAgent<Props>>();
+	}
+}
@@ -1 +1,838 @@
 //! Copyright © 2023 Stephan Kunz
+
+//! Integration tests of agents communicating via a [`Loopback`]
+
+use dimas::prelude::*;
+use dimas_commands::messages::{AboutEntity, MetricsEntity};
+use dimas_core::{
+	enums::Signal,
+	metrics::{
+		BYTES_PUBLISHED, BYTES_RECEIVED, CALLBACK_DURATION, MESSAGES_PUBLISHED, MESSAGES_RECEIVED,
+		STATE_TRANSITIONS, TIMER_OVERRUNS,
+	},
+};
+use std::sync::{Arc, Mutex};
+
+/// Type definition for the values collected by an agent
+type Values = Arc<Mutex<Vec<u32>>>;
+
+#[derive(Debug)]
+struct Sensor {
+	count: u32,
+	answers: Values,
+}
+
+#[derive(Debug)]
+struct Controller {
+	received: Values,
+}
+
+async fn value_callback(ctx: Context<Controller>, message: Message) -> Result<()> {
+	let value: u32 = message.decode()?;
+	ctx.read()?
+		.received
+		.lock()
+		.map_err(|_| "poisoned")?
+		.push(value);
+	Ok(())
+}
+
+async fn query_callback(_ctx: Context<Controller>, request: QueryMsg) -> Result<()> {
+	request.reply(42_u32)
+}
+
+/// Await a subscriber for `selector` on the `loopback`
+async fn subscribed(loopback: &Loopback, selector: &str) -> Result<()> {
+	let publisher = loopback
+		.session()
+		.declare_publisher(selector.to_string())
+		.await?;
+	settle(selector, async || {
+		publisher
+			.matching_status()
+			.await
+			.is_ok_and(|status| status.matching())
+	})
+	.await
+}
+
+/// Await a queryable for `selector` on the `loopback`
+async fn answered(loopback: &Loopback, selector: &str) -> Result<()> {
+	let querier = loopback
+		.session()
+		.declare_querier(selector.to_string())
+		.await?;
+	settle(selector, async || {
+		querier
+			.matching_status()
+			.await
+			.is_ok_and(|status| status.matching())
+	})
+	.await
+}
+
+/// Count the puts on `selector` recorded by the `loopback`
+fn puts(loopback: &Loopback, selector: &str) -> usize {
+	loopback
+		.traffic()
+		.iter()
+		.filter(|traffic| traffic.kind == TrafficKind::Put && traffic.selector == selector)
+		.count()
+}
+
+/// Create a sensor publishing and querying and a controller subscribing and answering
+fn agents(
+	loopback: &Loopback,
+	query: bool,
+) -> Result<(Agent<Sensor>, Agent<Controller>, Values, Values)> {
+	let answers = Arc::new(Mutex::new(Vec::new()));
+	let received = Arc::new(Mutex::new(Vec::new()));
+
+	let sensor = Agent::new(Sensor {
+		count: 0,
+		answers: answers.clone(),
+	})
+	.prefix("test")
+	.name("sensor")
+	.loopback(loopback)?;
+	sensor.publisher().topic("value").add()?;
+	sensor
+		.timer()
+		.name("publish")
+		.interval(Duration::from_millis(50))
+		.callback(move |ctx| -> Result<()> {
+			let count = ctx.read()?.count;
+			ctx.put("value", Message::encode(&count))?;
+			ctx.write()?.count += 1;
+			if query {
+				let answers = ctx.read()?.answers.clone();
+				let mut callback = |response: QueryableMsg| -> Result<()> {
+					answers
+						.lock()
+						.map_err(|_| "poisoned")?
+						.push(response.decode()?);
+					Ok(())
+				};
+				ctx.get("query", None, Some(&mut callback))?;
+			}
+			Ok(())
+		})
+		.add()?;
+
+	let controller = Agent::new(Controller {
+		received: received.clone(),
+	})
+	.prefix("test")
+	.name("controller")
+	.loopback(loopback)?;
+	controller
+		.subscriber()
+		.topic("value")
+		.put_callback(value_callback)
+		.add()?;
+	controller
+		.queryable()
+		.topic("query")
+		.callback(query_callback)
+		.add()?;
+
+	Ok((sensor, controller, answers, received))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn loopback_communication() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let (sensor, controller, answers, received) = agents(&loopback, true)?;
+	let controller = tokio::spawn(controller.start());
+	subscribed(&loopback, "test/value").await?;
+	answered(&loopback, "test/query").await?;
+	let sensor = tokio::spawn(sensor.start());
+	settle("communication", async || {
+		received
+			.lock()
+			.is_ok_and(|received| received.len() >= 3)
+			&& answers
+				.lock()
+				.is_ok_and(|answers| !answers.is_empty())
+	})
+	.await?;
+	sensor.abort();
+	controller.abort();
+
+	let received = received.lock().map_err(|_| "poisoned")?.clone();
+	assert!(!received.is_empty());
+	assert!(received.windows(2).all(|pair| pair[0] < pair[1]));
+	let answers = answers.lock().map_err(|_| "poisoned")?.clone();
+	assert!(!answers.is_empty());
+	assert!(answers.iter().all(|answer| *answer == 42));
+
+	assert!(puts(&loopback, "test/value") >= received.len());
+	let traffic = loopback.traffic();
+	assert!(
+		traffic
+			.iter()
+			.any(|traffic| traffic.kind == TrafficKind::Reply && traffic.selector == "test/query")
+	);
+	Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn loopback_faults() -> Result<()> {
+	let loopback = Loopback::new()?;
+	loopback.inject(Fault::drop("test/value"))?;
+	let (sensor, controller, _answers, received) = agents(&loopback, false)?;
+	let controller = tokio::spawn(controller.start());
+	subscribed(&loopback, "test/value").await?;
+	let sensor = tokio::spawn(sensor.start());
+	settle("dropped puts", async || puts(&loopback, "test/value") >= 3).await?;
+	sensor.abort();
+	controller.abort();
+
+	assert!(
+		received
+			.lock()
+			.map_err(|_| "poisoned")?
+			.is_empty()
+	);
+	let traffic = loopback.traffic();
+	assert!(!traffic.is_empty());
+	assert!(
+		traffic
+			.iter()
+			.all(|traffic| traffic.fault == Some(FaultKind::Drop))
+	);
+	Ok(())
+}
+
+#[derive(Debug)]
+struct Counter {
+	ticks: Vec<(&'static str, u32)>,
+	received: Vec<u32>,
+}
+
+async fn counter_callback(ctx: Context<Counter>, message: Message) -> Result<()> {
+	let value: u32 = message.decode()?;
+	ctx.write()?.received.push(value);
+	ctx.put("echo", Message::encode(&(value * 2)))
+}
+
+async fn counter_query(ctx: Context<Counter>, request: QueryMsg) -> Result<()> {
+	let count = u32::try_from(ctx.read()?.ticks.len())?;
+	request.reply(count)
+}
+
+/// Create a test agent with a fast and a delayed slow timer
+fn counter(loopback: &Loopback) -> Result<TestAgent<Counter>> {
+	let agent = Agent::new(Counter {
+		ticks: Vec::new(),
+		received: Vec::new(),
+	})
+	.prefix("test")
+	.name("counter")
+	.testing(loopback, &Config::minimal())?;
+	agent.publisher().topic("echo").add()?;
+	agent
+		.subscriber()
+		.topic("value")
+		.put_callback(counter_callback)
+		.add()?;
+	agent
+		.queryable()
+		.topic("ticks")
+		.callback(counter_query)
+		.add()?;
+	for (name, delay, interval) in [("fast", None, 100), ("slow", Some(150), 200)] {
+		let mut builder = agent
+			.timer()
+			.name(name)
+			.interval(Duration::from_millis(interval));
+		if let Some(delay) = delay {
+			builder = builder.delay(Duration::from_millis(delay));
+		}
+		builder
+			.callback(move |ctx| -> Result<()> {
+				let count = u32::try_from(ctx.read()?.ticks.len())?;
+				ctx.write()?.ticks.push((name, count));
+				Ok(())
+			})
+			.add()?;
+	}
+	Ok(agent)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_agent_timers() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut agent = counter(&loopback)?;
+	assert_eq!(agent.state(), OperationState::Created);
+	agent.start().await?;
+	assert_eq!(agent.state(), OperationState::Active);
+	// timers without delay fire at the start
+	assert_eq!(agent.read()?.ticks, [("fast", 0)]);
+
+	agent.advance(Duration::from_millis(360)).await?;
+	assert_eq!(agent.now(), Duration::from_millis(360));
+	assert_eq!(
+		agent.read()?.ticks,
+		[
+			("fast", 0),
+			("fast", 1),
+			("slow", 2),
+			("fast", 3),
+			("fast", 4),
+			("slow", 5)
+		]
+	);
+	// nothing fires before the next due time
+	agent.advance(Duration::from_millis(39)).await?;
+	assert_eq!(agent.read()?.ticks.len(), 6);
+	agent.stop()?;
+	assert_eq!(agent.state(), OperationState::Created);
+	Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_agent_messages() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut agent = counter(&loopback)?;
+	agent.start().await?;
+
+	agent
+		.inject("value", Message::encode(&21_u32))
+		.await?;
+	agent
+		.inject("value", Message::encode(&4_u32))
+		.await?;
+	assert_eq!(agent.read()?.received, [21, 4]);
+	let echo: u32 = agent
+		.expect_put("echo", Duration::from_millis(100))
+		.await?
+		.decode()?;
+	assert_eq!(echo, 42);
+	let echo: u32 = agent
+		.expect_put("echo", Duration::from_millis(100))
+		.await?
+		.decode()?;
+	assert_eq!(echo, 8);
+	assert!(
+		agent
+			.expect_put("echo", Duration::from_millis(50))
+			.await
+			.is_err()
+	);
+	// clearing the traffic does not skip the following messages
+	agent.loopback().clear_traffic();
+	agent
+		.inject("value", Message::encode(&5_u32))
+		.await?;
+	let echo: u32 = agent
+		.expect_put("echo", Duration::from_millis(100))
+		.await?
+		.decode()?;
+	assert_eq!(echo, 10);
+
+	agent.advance(Duration::from_millis(100)).await?;
+	let mut replies = agent.get("ticks", None).await?;
+	assert_eq!(replies.len(), 1);
+	assert_eq!(replies.remove(0).decode::<u32>()?, 2);
+	agent.stop()
+}
+
+#[derive(Debug)]
+struct Simulated {
+	ticks: Values,
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn external_clock() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let ticks = Arc::new(Mutex::new(Vec::new()));
+	let agent = Agent::new(Simulated {
+		ticks: ticks.clone(),
+	})
+	.prefix("test")
+	.name("simulated")
+	.clock(Clock::external("sim/clock"))
+	.loopback(&loopback)?;
+	agent
+		.timer()
+		.name("tick")
+		.delay(Duration::from_secs(1))
+		.interval(Duration::from_secs(1))
+		.callback(|ctx| -> Result<()> {
+			let now = u32::try_from(ctx.now().as_secs())?;
+			ctx.read()?
+				.ticks
+				.lock()
+				.map_err(|_| "poisoned")?
+				.push(now);
+			Ok(())
+		})
+		.add()?;
+	let agent = tokio::spawn(agent.start());
+	subscribed(&loopback, "sim/clock").await?;
+	// without a published time the timer does not fire
+	assert!(ticks.lock().map_err(|_| "poisoned")?.is_empty());
+
+	// the simulator publishes its time in nanoseconds
+	let session = loopback.session();
+	for (nanos, fired) in [
+		(500_000_000_u64, 0),
+		(1_000_000_000, 1),
+		(2_500_000_000, 2),
+		(3_000_000_000, 3),
+	] {
+		session
+			.put("sim/clock", Message::encode(&nanos).to_bytes())
+			.await?;
+		if fired > 0 {
+			settle("sim/clock", async || {
+				ticks
+					.lock()
+					.is_ok_and(|ticks| ticks.len() >= fired)
+			})
+			.await?;
+		}
+	}
+	agent.abort();
+
+	assert_eq!(*ticks.lock().map_err(|_| "poisoned")?, [1, 2, 3]);
+	Ok(())
+}
+
+#[derive(Debug)]
+struct Monitor {
+	infos: Vec<SampleInfo>,
+}
+
+async fn monitor_callback(ctx: Context<Monitor>, message: Message) -> Result<()> {
+	let info = message
+		.info()
+		.cloned()
+		.ok_or("missing sample info")?;
+	ctx.write()?.infos.push(info);
+	Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sample_info() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut monitor = Agent::new(Monitor { infos: Vec::new() })
+		.prefix("test")
+		.name("monitor")
+		.testing(&loopback, &Config::minimal())?;
+	monitor
+		.subscriber()
+		.topic("sensor/*")
+		.put_callback(monitor_callback)
+		.add()?;
+	monitor.start().await?;
+
+	let mut sensor = Agent::new(Controller {
+		received: Arc::new(Mutex::new(Vec::new())),
+	})
+	.prefix("test")
+	.name("sensor")
+	.testing(&loopback, &Config::minimal())?;
+	sensor.publisher().topic("sensor/remote").add()?;
+	sensor
+		.publisher()
+		.topic("sensor/local")
+		.intra_process(true)
+		.add()?;
+	sensor
+		.timer()
+		.name("publish")
+		.interval(Duration::from_millis(100))
+		.callback(|ctx| -> Result<()> {
+			ctx.put("sensor/remote", Message::encode(&1_u32))?;
+			ctx.put("sensor/local", Message::encode(&2_u32))
+		})
+		.add()?;
+	sensor.start().await?;
+	sensor.advance(Duration::from_millis(200)).await?;
+
+	let infos = monitor.read()?.infos.clone();
+	let remote: Vec<&SampleInfo> = infos
+		.iter()
+		.filter(|info| info.key_expr == "test/sensor/remote")
+		.collect();
+	let local: Vec<&SampleInfo> = infos
+		.iter()
+		.filter(|info| info.key_expr == "test/sensor/local")
+		.collect();
+	assert_eq!(remote.len(), 3);
+	assert_eq!(local.len(), 3);
+	for info in &infos {
+		assert!(info.timestamp.is_some());
+		assert!(info.source_id.is_some());
+		assert!(info.attachment.is_none());
+	}
+	// the timestamps are increasing
+	assert!(
+		remote
+			.windows(2)
+			.all(|pair| pair[0].timestamp < pair[1].timestamp)
+	);
+	// the sequence numbers are known with and without feature `unstable`
+	for infos in [&remote, &local] {
+		let sequence: Vec<Option<u32>> = infos
+			.iter()
+			.map(|info| info.sequence_number)
+			.collect();
+		assert_eq!(sequence, [Some(0), Some(1), Some(2)]);
+	}
+	Ok(())
+}
+
+#[derive(Debug)]
+struct Tracer {
+	received: Vec<Attachments>,
+}
+
+async fn tracer_callback(ctx: Context<Tracer>, message: Message) -> Result<()> {
+	let attachments = message
+		.attachments()
+		.cloned()
+		.ok_or("missing attachments")?;
+	ctx.write()?.received.push(attachments);
+	Ok(())
+}
+
+async fn tracer_query(_ctx: Context<Tracer>, request: QueryMsg) -> Result<()> {
+	let attachments = request
+		.attachments()
+		.ok_or("missing attachments")?
+		.with("replied", "true");
+	request.reply_with_attachments(7_u32, &attachments)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn attachments() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut tracer = Agent::new(Tracer {
+		received: Vec::new(),
+	})
+	.prefix("test")
+	.name("tracer")
+	.testing(&loopback, &Config::minimal())?;
+	tracer
+		.subscriber()
+		.topic("traced/*")
+		.put_callback(tracer_callback)
+		.add()?;
+	tracer
+		.queryable()
+		.topic("traced")
+		.callback(tracer_query)
+		.add()?;
+	tracer.start().await?;
+
+	let mut sensor = Agent::new(Controller {
+		received: Arc::new(Mutex::new(Vec::new())),
+	})
+	.prefix("test")
+	.name("sensor")
+	.testing(&loopback, &Config::minimal())?;
+	sensor.publisher().topic("traced/remote").add()?;
+	sensor
+		.publisher()
+		.topic("traced/local")
+		.intra_process(true)
+		.add()?;
+	sensor
+		.timer()
+		.name("publish")
+		.interval(Duration::from_secs(1))
+		.callback(|ctx| -> Result<()> {
+			let attachments = Attachments::new().with("frame-id", "base_link");
+			ctx.put(
+				"traced/remote",
+				Message::encode(&1_u32).with_attachments(attachments.clone()),
+			)?;
+			ctx.put(
+				"traced/local",
+				Message::encode(&2_u32).with_attachments(attachments),
+			)
+		})
+		.add()?;
+	sensor.start().await?;
+
+	tracer
+		.inject(
+			"traced/injected",
+			Message::encode(&3_u32).with_attachments(Attachments::new().with("trace-id", "42")),
+		)
+		.await?;
+	let received = tracer.read()?.received.clone();
+	assert_eq!(received.len(), 3);
+	assert_eq!(
+		received
+			.iter()
+			.filter(
+				|attachments| attachments.get("frame-id").map(String::as_str) == Some("base_link")
+			)
+			.count(),
+		2
+	);
+	assert_eq!(received[2].get("trace-id").map(String::as_str), Some("42"));
+
+	let query = Message::encode(&0_u32).with_attachments(Attachments::new().with("trace-id", "43"));
+	let mut replies = tracer.get("traced", Some(query)).await?;
+	assert_eq!(replies.len(), 1);
+	let reply = replies.remove(0);
+	let attachments = reply
+		.attachments()
+		.cloned()
+		.ok_or("missing attachments")?;
+	assert_eq!(
+		attachments,
+		Attachments::new()
+			.with("trace-id", "43")
+			.with("replied", "true")
+	);
+	assert_eq!(reply.decode::<u32>()?, 7);
+	Ok(())
+}
+
+/// Find the metric `name` having the label `key` with `value`
+fn metric<'a>(metrics: &'a [Metric], name: &str, key: &str, value: &str) -> Option<&'a Metric> {
+	metrics
+		.iter()
+		.find(|metric| metric.name() == name && metric.label(key) == Some(value))
+}
+
+/// Get the number of observations of `metric`
+fn count(metric: Option<&Metric>) -> u64 {
+	match metric.map(Metric::value) {
+		Some(MetricValue::Counter(count) | MetricValue::Histogram { count, .. }) => *count,
+		None => 0,
+	}
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metrics() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut agent = counter(&loopback)?;
+	agent.start().await?;
+	agent
+		.inject("value", Message::encode(&21_u32))
+		.await?;
+	agent
+		.inject("value", Message::encode(&4_u32))
+		.await?;
+	// a failing put is not counted
+	agent
+		.loopback()
+		.inject(Fault::fail("test/echo").times(1))?;
+	agent
+		.inject("value", Message::encode(&5_u32))
+		.await?;
+	agent.advance(Duration::from_millis(100)).await?;
+
+	let signal = Message::encode(&Signal::Metrics);
+	let mut replies = agent.get("counter/metrics", Some(signal)).await?;
+	assert_eq!(replies.len(), 1);
+	let entity: MetricsEntity = replies.remove(0).decode()?;
+	assert_eq!(entity.name(), "test/counter");
+	let metrics = entity.metrics();
+
+	assert_eq!(
+		count(metric(metrics, MESSAGES_RECEIVED, "topic", "test/value")),
+		3
+	);
+	let size = Message::encode(&21_u32).value().len() as u64;
+	assert_eq!(
+		count(metric(metrics, BYTES_RECEIVED, "topic", "test/value")),
+		3 * size
+	);
+	assert_eq!(
+		count(metric(metrics, MESSAGES_PUBLISHED, "topic", "test/echo")),
+		2
+	);
+	assert_eq!(
+		count(metric(metrics, BYTES_PUBLISHED, "topic", "test/echo")),
+		2 * size
+	);
+	let callbacks: Vec<&Metric> = metrics
+		.iter()
+		.filter(|metric| metric.name() == CALLBACK_DURATION)
+		.collect();
+	assert_eq!(
+		count(
+			callbacks
+				.iter()
+				.copied()
+				.find(|metric| metric.label("kind") == Some("subscriber"))
+		),
+		3
+	);
+	assert_eq!(
+		count(callbacks.iter().copied().find(|metric| {
+			metric.label("kind") == Some("timer")
+				&& metric
+					.label("topic")
+					.is_some_and(|topic| topic.ends_with("fast"))
+		})),
+		2
+	);
+	assert_eq!(
+		count(metric(metrics, STATE_TRANSITIONS, "state", "Active")),
+		1
+	);
+	assert_eq!(count(metric(metrics, TIMER_OVERRUNS, "timer", "fast")), 0);
+	agent.stop()
+}
+
+/// Send a HTTP GET request for `path` to `address` and return the response
+async fn http_get(address: &str, path: &str) -> Result<String> {
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+	let mut stream = tokio::net::TcpStream::connect(address).await?;
+	stream
+		.write_all(format!("GET {path} HTTP/1.1\r\nhost: {address}\r\n\r\n").as_bytes())
+		.await?;
+	let mut response = String::new();
+	stream.read_to_string(&mut response).await?;
+	Ok(response)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn metrics_endpoint() -> Result<()> {
+	// find a free port
+	let address = std::net::TcpListener::bind("127.0.0.1:0")?
+		.local_addr()?
+		.to_string();
+
+	let loopback = Loopback::new()?;
+	let agent = Agent::new(Controller {
+		received: Arc::new(Mutex::new(Vec::new())),
+	})
+	.prefix("test")
+	.name("monitored")
+	.metrics_listener(&address)
+	.loopback(&loopback)?;
+	let agent = tokio::spawn(agent.start());
+	settle(&address, async || {
+		tokio::net::TcpStream::connect(&address)
+			.await
+			.is_ok()
+	})
+	.await?;
+
+	let response = http_get(&address, "/metrics").await?;
+	assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+	assert!(response.contains("# TYPE dimas_state_transitions_total counter\n"));
+	assert!(response.contains("dimas_state_transitions_total{state=\"Active\"} 1\n"));
+	let response = http_get(&address, "/other").await?;
+	assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+	// an overlong request line is cut
+	let response = http_get(&address, &format!("/metrics{}", "x".repeat(10_000))).await?;
+	assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+	// a client not sending a request is disconnected
+	let mut idle = tokio::net::TcpStream::connect(&address).await?;
+	let mut response = String::new();
+	tokio::time::timeout(
+		Duration::from_secs(10),
+		tokio::io::AsyncReadExt::read_to_string(&mut idle, &mut response),
+	)
+	.await??;
+	assert!(response.is_empty());
+	agent.abort();
+	Ok(())
+}
+
+#[derive(Debug)]
+struct Watched {
+	battery: f64,
+}
+
+/// Create a test agent with a heartbeat for sensor data, a battery check and a watchdog
+fn watched(loopback: &Loopback) -> Result<TestAgent<Watched>> {
+	let agent = Agent::new(Watched { battery: 1.0 })
+		.prefix("test")
+		.name("watched")
+		.watchdog(OperationState::Standby)
+		.testing(loopback, &Config::minimal())?;
+	agent
+		.heartbeat("sensor", Duration::from_millis(200))
+		.add()?;
+	agent
+		.health_check("battery", |ctx| {
+			let status = if ctx.read()?.battery < 0.2 {
+				HealthStatus::Degraded
+			} else {
+				HealthStatus::Ok
+			};
+			Ok(status)
+		})
+		.add()?;
+	agent
+		.subscriber()
+		.topic("sensor")
+		.put_callback(|ctx, _message| async move {
+			ctx.heartbeat("sensor");
+			Ok(())
+		})
+		.add()?;
+	Ok(agent)
+}
+
+/// Await the health published by `agent`
+async fn published_health(agent: &mut TestAgent<Watched>) -> Result<Health> {
+	let entity: AboutEntity = agent
+		.expect_put("watched/health", Duration::from_millis(100))
+		.await?
+		.decode()?;
+	Ok(entity.health().clone())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn health() -> Result<()> {
+	let loopback = Loopback::new()?;
+	let mut agent = watched(&loopback)?;
+	agent.start().await?;
+
+	agent.advance(Duration::from_millis(150)).await?;
+	assert_eq!(agent.supervise().status(), HealthStatus::Ok);
+	assert_eq!(
+		published_health(&mut agent).await?.status(),
+		HealthStatus::Ok
+	);
+
+	// the heartbeat keeps the sensor check alive
+	agent
+		.inject("sensor", Message::encode(&1_u32))
+		.await?;
+	agent.advance(Duration::from_millis(150)).await?;
+	agent.write()?.battery = 0.1;
+	let health = agent.supervise();
+	assert_eq!(health.status(), HealthStatus::Degraded);
+	let problems: Vec<&str> = health.problems().map(CheckReport::name).collect();
+	assert_eq!(problems, ["battery"]);
+	assert_eq!(published_health(&mut agent).await?, health);
+	assert_eq!(agent.state(), OperationState::Active);
+
+	// the about information contains the health
+	let signal = Message::encode(&Signal::About);
+	let mut replies = agent.get("watched/about", Some(signal)).await?;
+	assert_eq!(replies.len(), 1);
+	let entity: AboutEntity = replies.remove(0).decode()?;
+	assert_eq!(entity.health().status(), HealthStatus::Degraded);
+
+	// without heartbeat the watchdog drops the agent to standby
+	agent.advance(Duration::from_millis(300)).await?;
+	let health = agent.supervise();
+	assert_eq!(health.status(), HealthStatus::Failed);
+	let sensor = &health.checks()[1];
+	assert_eq!(sensor.name(), "sensor");
+	assert!(
+		sensor
+			.reason()
+			.is_some_and(|reason| reason.starts_with("last heartbeat"))
+	);
+	assert_eq!(agent.state(), OperationState::Standby);
+	assert_eq!(published_health(&mut agent).await?, health);
+	agent.stop()
+}
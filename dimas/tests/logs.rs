@@ -0,0 +1,92 @@
+//! Copyright © 2025 Stephan Kunz
+
+//! Integration test of the log streaming of several agents in one process,
+//! in a test binary of its own as it installs a global tracing subscriber
+
+use dimas::prelude::*;
+use dimas_commands::messages::LogEntity;
+use dimas_core::{
+	enums::LogLevel,
+	utils::{LogFilter, LogLayer},
+};
+use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+#[derive(Debug)]
+struct Props {}
+
+/// An agent logging its name every few milliseconds
+fn chatty(loopback: &Loopback, name: &'static str) -> Result<Agent<Props>> {
+	let agent = Agent::new(Props {})
+		.prefix("test")
+		.name(name)
+		.log_level(LogLevel::Info)
+		.loopback(loopback)?;
+	agent
+		.timer()
+		.name("chat")
+		.interval(Duration::from_millis(10))
+		.callback(move |_ctx| -> Result<()> {
+			tracing::info!("hello from {name}");
+			Ok(())
+		})
+		.add()?;
+	Ok(agent)
+}
+
+/// The messages of the log records published on `selector`
+fn published(loopback: &Loopback, selector: &str) -> Result<Vec<String>> {
+	loopback
+		.traffic()
+		.into_iter()
+		.filter(|traffic| traffic.selector == selector)
+		.filter_map(|traffic| traffic.message)
+		.map(|message| {
+			Ok(message
+				.decode::<LogEntity>()?
+				.message()
+				.to_string())
+		})
+		.collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn agents_in_one_process() -> Result<()> {
+	let subscriber = tracing_subscriber::registry().with(LogLayer.with_filter(LogFilter));
+	tracing::subscriber::set_global_default(subscriber)?;
+
+	let loopback = Loopback::new()?;
+	let alpha = tokio::spawn(chatty(&loopback, "alpha")?.start());
+	let beta = tokio::spawn(chatty(&loopback, "beta")?.start());
+
+	let (alpha_logs, beta_logs) = tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			let alpha_logs = published(&loopback, "test/alpha/log")?;
+			let beta_logs = published(&loopback, "test/beta/log")?;
+			if alpha_logs
+				.iter()
+				.any(|message| message == "hello from alpha")
+				&& beta_logs
+					.iter()
+					.any(|message| message == "hello from beta")
+			{
+				return Ok::<_, Box<dyn core::error::Error + Send + Sync>>((alpha_logs, beta_logs));
+			}
+			tokio::time::sleep(Duration::from_millis(5)).await;
+		}
+	})
+	.await??;
+	alpha.abort();
+	beta.abort();
+
+	assert!(
+		alpha_logs
+			.iter()
+			.all(|message| !message.contains("beta"))
+	);
+	assert!(
+		beta_logs
+			.iter()
+			.all(|message| !message.contains("alpha"))
+	);
+	Ok(())
+}
@@ -0,0 +1,100 @@
+//! Copyright © 2025 Stephan Kunz
+
+//! Integration test of the trace context propagation between agents,
+//! in a test binary of its own as it installs a global tracing subscriber
+
+use dimas::prelude::*;
+use opentelemetry::trace::{TraceId, TracerProvider};
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use tracing_subscriber::layer::SubscriberExt;
+
+#[derive(Debug)]
+struct Props {}
+
+async fn put_callback(_ctx: Context<Props>, _message: Message) -> Result<()> {
+	Ok(())
+}
+
+async fn query_callback(_ctx: Context<Props>, request: QueryMsg) -> Result<()> {
+	request.reply(1_u32)
+}
+
+/// Check that the span `name` is linked to the trace `trace_id`
+fn linked(spans: &[SpanData], name: &str, trace_id: TraceId) -> bool {
+	spans
+		.iter()
+		.filter(|span| span.name == name)
+		.any(|span| {
+			span.links
+				.links
+				.iter()
+				.any(|link| link.span_context.trace_id() == trace_id)
+		})
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn trace_propagation() -> Result<()> {
+	let exporter = InMemorySpanExporter::default();
+	let provider = SdkTracerProvider::builder()
+		.with_simple_exporter(exporter.clone())
+		.build();
+	let subscriber = tracing_subscriber::registry()
+		.with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+	tracing::subscriber::set_global_default(subscriber)?;
+
+	let loopback = Loopback::new()?;
+	let mut receiver = Agent::new(Props {})
+		.prefix("test")
+		.name("receiver")
+		.testing(&loopback, &Config::minimal())?;
+	receiver
+		.subscriber()
+		.topic("traced")
+		.put_callback(put_callback)
+		.add()?;
+	receiver
+		.queryable()
+		.topic("traced/query")
+		.callback(query_callback)
+		.add()?;
+	receiver.start().await?;
+
+	let mut sender = Agent::new(Props {})
+		.prefix("test")
+		.name("sender")
+		.testing(&loopback, &Config::minimal())?;
+	sender.publisher().topic("traced").add()?;
+	sender
+		.timer()
+		.name("send")
+		.interval(Duration::from_secs(1))
+		.callback(|ctx| -> Result<()> {
+			let span = tracing::error_span!("send");
+			let _entered = span.enter();
+			ctx.put("traced", Message::encode(&1_u32))?;
+			let mut callback = |_: QueryableMsg| -> Result<()> { Ok(()) };
+			ctx.get("traced/query", None, Some(&mut callback))
+		})
+		.add()?;
+	sender.start().await?;
+	sender.stop()?;
+	receiver.stop()?;
+	provider.force_flush()?;
+
+	let spans = exporter.get_finished_spans()?;
+	let send = spans
+		.iter()
+		.find(|span| span.name == "send")
+		.ok_or("missing span 'send'")?;
+	let trace_id = send.span_context.trace_id();
+	assert!(linked(&spans, "put", trace_id));
+	assert!(linked(&spans, "query", trace_id));
+	// the receiving spans belong to the receivers trace
+	assert!(
+		spans
+			.iter()
+			.filter(|span| span.name == "put" || span.name == "query")
+			.all(|span| span.span_context.trace_id() != trace_id)
+	);
+	Ok(())
+}
@@ -0,0 +1,564 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Launching and supervising a system of agents
+//!
+//! A launch file is a json5 file with the same syntax as a config file, including comments and `#include`s:
+//! ```json5
+//! {
+//!   // configuration file for all agents without an own one (optional)
+//!   config: "local.json5",
+//!   // environment for all agents (optional)
+//!   env: { RUST_LOG: "info" },
+//!   // seconds to wait for an agent to be ready or to stop (optional, default 10)
+//!   timeout: 10,
+//!   agents: [
+//!     {
+//!       name: "subscriber",
+//!       prefix: "examples",
+//!       binary: "target/debug/examples/subscriber",
+//!       // all following fields are optional
+//!       args: [],
+//!       config: "peer.json5",
+//!       env: {},
+//!       // one of "never" (default), "on-failure" or "always"
+//!       restart: "on-failure",
+//!       // unlimited if not given
+//!       max_restarts: 3,
+//!       // agents that have to be ready before this agent is started
+//!       depends_on: [],
+//!     },
+//!   ],
+//! }
+//! ```
+//! The agents get their prefix, name and configuration file via the environment variables
+//! `DIMAS_PREFIX`, `DIMAS_NAME` and `DIMAS_CONFIG`.
+//! They are stopped with `Signal::Shutdown` in reverse start order, if necessary killed after the timeout.
+
+// region:		--- modules
+use crate::ctrl_c_flag;
+use core::time::Duration;
+use dimas_com::zenoh::Communicator;
+use dimas_config::Config;
+use dimas_core::Result;
+use serde::Deserialize;
+use std::{
+	collections::{BTreeMap, HashSet},
+	io::{BufRead, BufReader, Read},
+	path::Path,
+	process::{Child, Command, ExitStatus, Stdio},
+	sync::atomic::Ordering,
+	thread,
+	time::Instant,
+};
+// endregion:	--- modules
+
+// region:		--- constants
+/// Interval for checking the processes
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Delay before restarting an agent
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+// endregion:	--- constants
+
+// region:		--- LaunchDescription
+/// When to restart an agent
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum RestartPolicy {
+	/// Never restart
+	#[default]
+	Never,
+	/// Restart if the agent exited with an error
+	OnFailure,
+	/// Always restart
+	Always,
+}
+
+/// Description of an agent to launch
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AgentDescription {
+	name: String,
+	prefix: Option<String>,
+	binary: String,
+	#[serde(default)]
+	args: Vec<String>,
+	config: Option<String>,
+	#[serde(default)]
+	env: BTreeMap<String, String>,
+	#[serde(default)]
+	restart: RestartPolicy,
+	max_restarts: Option<u32>,
+	#[serde(default)]
+	depends_on: Vec<String>,
+}
+
+impl AgentDescription {
+	/// The fully qualified name of the agent
+	fn fq_name(&self) -> String {
+		self.prefix.as_ref().map_or_else(
+			|| self.name.clone(),
+			|prefix| format!("{prefix}/{}", self.name),
+		)
+	}
+}
+
+/// Description of a system of agents
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LaunchDescription {
+	config: Option<String>,
+	#[serde(default)]
+	env: BTreeMap<String, String>,
+	timeout: Option<u64>,
+	agents: Vec<AgentDescription>,
+}
+
+impl LaunchDescription {
+	/// Read the launch description from `file`
+	fn from_file(file: &str) -> Result<Self> {
+		let content = dimas_config::read_config_file(Path::new(file))?;
+		let description: Self = json5::from_str(&content)
+			.map_err(|error| format!("invalid launch file {file}: {error}"))?;
+		Ok(description)
+	}
+
+	/// Calculate the start order, so that every agent is started after its dependencies
+	fn start_order(&self) -> Result<Vec<usize>> {
+		let mut names = HashSet::new();
+		for agent in &self.agents {
+			if !names.insert(agent.name.as_str()) {
+				return Err(format!("agent '{}' is defined twice", agent.name).into());
+			}
+			for dependency in &agent.depends_on {
+				if !self
+					.agents
+					.iter()
+					.any(|other| &other.name == dependency)
+				{
+					return Err(format!(
+						"agent '{}' depends on unknown agent '{dependency}'",
+						agent.name
+					)
+					.into());
+				}
+			}
+		}
+
+		let mut order: Vec<usize> = Vec::with_capacity(self.agents.len());
+		while order.len() < self.agents.len() {
+			let next = self
+				.agents
+				.iter()
+				.enumerate()
+				.position(|(index, agent)| {
+					!order.contains(&index)
+						&& agent.depends_on.iter().all(|dependency| {
+							order
+								.iter()
+								.any(|&started| &self.agents[started].name == dependency)
+						})
+				});
+			match next {
+				Some(index) => order.push(index),
+				None => return Err("agents have cyclic dependencies".into()),
+			}
+		}
+		Ok(order)
+	}
+}
+// endregion:	--- LaunchDescription
+
+// region:		--- Process
+/// A supervised agent process
+struct Process<'a> {
+	description: &'a AgentDescription,
+	child: Option<Child>,
+	restarts: u32,
+	restart_at: Option<Instant>,
+}
+
+impl<'a> Process<'a> {
+	/// Constructor
+	const fn new(description: &'a AgentDescription) -> Self {
+		Self {
+			description,
+			child: None,
+			restarts: 0,
+			restart_at: None,
+		}
+	}
+
+	/// Spawn the agents process, its output is printed prefixed with the agents name
+	fn spawn(&mut self, launch: &LaunchDescription, label: &str) -> Result<()> {
+		let description = self.description;
+		let mut command = Command::new(&description.binary);
+		command
+			.args(&description.args)
+			.envs(&launch.env)
+			.envs(&description.env)
+			.env("DIMAS_NAME", &description.name)
+			.stdin(Stdio::null())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::piped());
+		if let Some(prefix) = &description.prefix {
+			command.env("DIMAS_PREFIX", prefix);
+		}
+		if let Some(config) = description
+			.config
+			.as_ref()
+			.or(launch.config.as_ref())
+		{
+			command.env("DIMAS_CONFIG", config);
+		}
+		// the agents shall not receive the 'ctrl-c' of the launcher
+		#[cfg(unix)]
+		std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+		let mut child = command
+			.spawn()
+			.map_err(|error| format!("starting '{}' failed: {error}", description.binary))?;
+		if let Some(stdout) = child.stdout.take() {
+			print_output(stdout, label.to_string(), false);
+		}
+		if let Some(stderr) = child.stderr.take() {
+			print_output(stderr, label.to_string(), true);
+		}
+		println!("{label} started with pid {}", child.id());
+		self.child = Some(child);
+		self.restart_at = None;
+		Ok(())
+	}
+
+	/// Check the process, returns the exit status if it has exited since the last check
+	fn exited(&mut self) -> Option<ExitStatus> {
+		let status = self.child.as_mut()?.try_wait().ok()??;
+		self.child = None;
+		Some(status)
+	}
+
+	/// Whether the process shall be restarted after exiting with `status`
+	fn shall_restart(&self, status: ExitStatus) -> bool {
+		let wanted = match self.description.restart {
+			RestartPolicy::Never => false,
+			RestartPolicy::OnFailure => !status.success(),
+			RestartPolicy::Always => true,
+		};
+		wanted
+			&& self
+				.description
+				.max_restarts
+				.is_none_or(|max| self.restarts < max)
+	}
+}
+
+/// Print the lines of an output stream prefixed with `label`
+fn print_output(stream: impl Read + Send + 'static, label: String, error: bool) {
+	thread::spawn(move || {
+		for line in BufReader::new(stream)
+			.lines()
+			.map_while(core::result::Result::ok)
+		{
+			if error {
+				eprintln!("{label} {line}");
+			} else {
+				println!("{label} {line}");
+			}
+		}
+	});
+}
+// endregion:	--- Process
+
+// region:		--- launch
+/// Launch the agents described in `file` and supervise them until 'ctrl-c' is pressed
+/// or all agents have finished.
+pub fn launch(config: &Config, file: &str) -> Result<()> {
+	let description = LaunchDescription::from_file(file)?;
+	let order = description.start_order()?;
+	let timeout = Duration::from_secs(description.timeout.unwrap_or(10));
+	let com = Communicator::new(config.zenoh_config())?;
+	let stop = ctrl_c_flag();
+
+	let width = description
+		.agents
+		.iter()
+		.map(|agent| agent.name.len())
+		.max()
+		.unwrap_or_default();
+	let labels: Vec<String> = description
+		.agents
+		.iter()
+		.map(|agent| format!("[{:width$}]", agent.name))
+		.collect();
+	let mut processes: Vec<Process> = description
+		.agents
+		.iter()
+		.map(Process::new)
+		.collect();
+
+	let mut result = Ok(());
+	let mut started = Vec::with_capacity(order.len());
+	for index in order {
+		if stop.load(Ordering::Relaxed) {
+			break;
+		}
+		// the dependencies must be ready
+		for dependency in &description.agents[index].depends_on {
+			let Some(dependency) = description
+				.agents
+				.iter()
+				.position(|agent| &agent.name == dependency)
+			else {
+				continue;
+			};
+			if let Err(error) = wait_ready(&com, &mut processes[dependency], timeout) {
+				result = Err(error);
+				break;
+			}
+		}
+		if result.is_err() {
+			break;
+		}
+		if let Err(error) = processes[index].spawn(&description, &labels[index]) {
+			result = Err(error);
+			break;
+		}
+		started.push(index);
+	}
+
+	if result.is_ok() {
+		supervise(&description, &mut processes, &labels, &stop);
+	}
+	shutdown(&com, &mut processes, &started, timeout);
+	result
+}
+
+/// Wait until the agent of `process` answers or `timeout` has elapsed
+fn wait_ready(com: &Communicator, process: &mut Process, timeout: Duration) -> Result<()> {
+	let fq_name = process.description.fq_name();
+	let deadline = Instant::now() + timeout;
+	while Instant::now() < deadline {
+		if process.exited().is_some() {
+			return Err(format!("agent '{fq_name}' exited before being ready").into());
+		}
+		if dimas_commands::about_list(com, &fq_name).is_ok_and(|list| !list.is_empty()) {
+			return Ok(());
+		}
+		thread::sleep(POLL_INTERVAL);
+	}
+	Err(format!("agent '{fq_name}' not ready within {timeout:?}").into())
+}
+
+/// Supervise the processes and restart them according to their policy
+fn supervise(
+	description: &LaunchDescription,
+	processes: &mut [Process],
+	labels: &[String],
+	stop: &std::sync::atomic::AtomicBool,
+) {
+	while !stop.load(Ordering::Relaxed) {
+		let mut active = false;
+		for (index, process) in processes.iter_mut().enumerate() {
+			if let Some(status) = process.exited() {
+				println!("{} exited with {status}", labels[index]);
+				if process.shall_restart(status) {
+					process.restart_at = Some(Instant::now() + RESTART_DELAY);
+				}
+			}
+			if process
+				.restart_at
+				.is_some_and(|at| at <= Instant::now())
+			{
+				process.restarts += 1;
+				if let Err(error) = process.spawn(description, &labels[index]) {
+					eprintln!("{} {error}", labels[index]);
+				}
+			}
+			active |= process.child.is_some() || process.restart_at.is_some();
+		}
+		if !active {
+			break;
+		}
+		thread::sleep(POLL_INTERVAL);
+	}
+}
+
+/// Shut down the `started` processes in reverse order, kill those not stopping within `timeout`
+fn shutdown(com: &Communicator, processes: &mut [Process], started: &[usize], timeout: Duration) {
+	for &index in started.iter().rev() {
+		let process = &mut processes[index];
+		process.restart_at = None;
+		if process.child.is_none() || process.exited().is_some() {
+			continue;
+		}
+		let fq_name = process.description.fq_name();
+		println!("shutting down '{fq_name}'");
+		let _ = dimas_commands::shutdown(com, &fq_name);
+		let deadline = Instant::now() + timeout;
+		while process.exited().is_none() && Instant::now() < deadline {
+			thread::sleep(POLL_INTERVAL);
+		}
+		if let Some(mut child) = process.child.take() {
+			println!("killing '{fq_name}'");
+			let _ = child.kill();
+			let _ = child.wait();
+		}
+	}
+}
+// endregion:	--- launch
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Parse a launch description from json5 `content`
+	fn parse(content: &str) -> Result<LaunchDescription> {
+		Ok(json5::from_str(content)?)
+	}
+
+	/// Create a launch description with agents given by name and dependencies
+	fn system(agents: &[(&str, &[&str])]) -> LaunchDescription {
+		LaunchDescription {
+			config: None,
+			env: BTreeMap::new(),
+			timeout: None,
+			agents: agents
+				.iter()
+				.map(|(name, depends_on)| AgentDescription {
+					name: (*name).to_string(),
+					prefix: None,
+					binary: String::from("agent"),
+					args: Vec::new(),
+					config: None,
+					env: BTreeMap::new(),
+					restart: RestartPolicy::Never,
+					max_restarts: None,
+					depends_on: depends_on
+						.iter()
+						.map(ToString::to_string)
+						.collect(),
+				})
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn launch_description() -> Result<()> {
+		let launch = parse(
+			r#"{
+				config: "local.json5",
+				env: { RUST_LOG: "info" },
+				timeout: 5,
+				agents: [
+					{ name: "publisher", prefix: "examples", binary: "publisher" },
+					{
+						name: "subscriber",
+						binary: "subscriber",
+						args: ["--verbose"],
+						restart: "on-failure",
+						max_restarts: 3,
+						depends_on: ["publisher"],
+					},
+				],
+			}"#,
+		)?;
+		assert_eq!(launch.config.as_deref(), Some("local.json5"));
+		assert_eq!(launch.env.get("RUST_LOG").map(String::as_str), Some("info"));
+		assert_eq!(launch.timeout, Some(5));
+		let publisher = &launch.agents[0];
+		assert_eq!(publisher.fq_name(), "examples/publisher");
+		assert_eq!(publisher.restart, RestartPolicy::Never);
+		let subscriber = &launch.agents[1];
+		assert_eq!(subscriber.fq_name(), "subscriber");
+		assert_eq!(subscriber.args, ["--verbose"]);
+		assert_eq!(subscriber.restart, RestartPolicy::OnFailure);
+		assert_eq!(subscriber.max_restarts, Some(3));
+		assert_eq!(subscriber.depends_on, ["publisher"]);
+		Ok(())
+	}
+
+	#[test]
+	fn restart_policy() -> Result<()> {
+		for (text, policy) in [
+			("never", RestartPolicy::Never),
+			("on-failure", RestartPolicy::OnFailure),
+			("always", RestartPolicy::Always),
+		] {
+			let launch = parse(&format!(
+				"{{ agents: [{{ name: \"a\", binary: \"a\", restart: \"{text}\" }}] }}"
+			))?;
+			assert_eq!(launch.agents[0].restart, policy);
+		}
+		assert!(
+			parse(r#"{ agents: [{ name: "a", binary: "a", restart: "sometimes" }] }"#).is_err()
+		);
+		assert!(
+			parse(r#"{ agents: [{ name: "a", binary: "a", restart: "OnFailure" }] }"#).is_err()
+		);
+		Ok(())
+	}
+
+	#[test]
+	fn unknown_fields() {
+		assert!(parse("{ agents: [], timout: 5 }").is_err());
+		assert!(parse(r#"{ agents: [{ name: "a", binary: "a", dependencies: [] }] }"#).is_err());
+		assert!(parse(r#"{ agents: [{ name: "a" }] }"#).is_err());
+		assert!(parse("{ config: \"local.json5\" }").is_err());
+	}
+
+	#[test]
+	fn start_order() -> Result<()> {
+		let launch = system(&[
+			("controller", &["sensor", "actuator"]),
+			("sensor", &[]),
+			("actuator", &["sensor"]),
+		]);
+		assert_eq!(launch.start_order()?, [1, 2, 0]);
+
+		let launch = system(&[("a", &[]), ("b", &[])]);
+		assert_eq!(launch.start_order()?, [0, 1]);
+		Ok(())
+	}
+
+	#[test]
+	fn start_order_errors() {
+		let error = |launch: &LaunchDescription| {
+			launch
+				.start_order()
+				.map_err(|error| error.to_string())
+				.err()
+		};
+		assert_eq!(
+			error(&system(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])])).as_deref(),
+			Some("agents have cyclic dependencies")
+		);
+		assert_eq!(
+			error(&system(&[("a", &["a"])])).as_deref(),
+			Some("agents have cyclic dependencies")
+		);
+		assert_eq!(
+			error(&system(&[("a", &["missing"])])).as_deref(),
+			Some("agent 'a' depends on unknown agent 'missing'")
+		);
+		assert_eq!(
+			error(&system(&[("a", &[]), ("a", &[])])).as_deref(),
+			Some("agent 'a' is defined twice")
+		);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn restart_decision() {
+		use std::os::unix::process::ExitStatusExt;
+
+		let success = ExitStatus::from_raw(0);
+		let failure = ExitStatus::from_raw(1 << 8);
+		let mut launch = system(&[("a", &[])]);
+		launch.agents[0].restart = RestartPolicy::OnFailure;
+		launch.agents[0].max_restarts = Some(1);
+		let mut process = Process::new(&launch.agents[0]);
+		assert!(!process.shall_restart(success));
+		assert!(process.shall_restart(failure));
+		process.restarts = 1;
+		assert!(!process.shall_restart(failure));
+	}
+}
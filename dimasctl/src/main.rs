@@ -3,11 +3,25 @@
 //! Commandline tool for `DiMAS`
 
 // region:		--- modules
+mod launch;
+
 use clap::{Parser, Subcommand};
 use core::time::Duration;
 use dimas_com::zenoh::Communicator;
+use dimas_commands::{
+	PingSeries, PingStatistics,
+	messages::{AboutEntity, LogEntity, MetricsEntity, ParameterEntity},
+};
 use dimas_config::Config;
-use dimas_core::{Result, enums::OperationState};
+use dimas_core::{
+	Result,
+	enums::{LogLevel, OperationState},
+	metrics::{MetricValue, prometheus},
+};
+use std::sync::{
+	Arc,
+	atomic::{AtomicBool, Ordering},
+};
 // endregion:	--- modules
 
 // region:		--- Cli
@@ -17,28 +31,381 @@ struct DimasctlArgs {
 	/// Optional selector for the instances to operate on
 	selector: Option<String>,
 
+	/// Configuration file to use instead of the default configuration
+	#[arg(long, global = true)]
+	config: Option<String>,
+
+	/// Zenoh mode to use [peer, client, router]
+	#[arg(long, global = true)]
+	mode: Option<String>,
+
+	/// Endpoints to connect to
+	#[arg(long, global = true, value_delimiter = ',')]
+	connect: Vec<String>,
+
+	/// Endpoints to listen on
+	#[arg(long, global = true, value_delimiter = ',')]
+	listen: Vec<String>,
+
 	#[clap(subcommand)]
 	command: DimasctlCommand,
 }
+
+impl DimasctlArgs {
+	/// Creates the configuration with the given overrides
+	fn config(&self) -> Result<Config> {
+		let mut builder = Config::builder();
+		if let Some(file) = &self.config {
+			builder = builder.file(file);
+		}
+		if let Some(mode) = &self.mode {
+			builder = builder.mode(mode);
+		}
+		for endpoint in &self.connect {
+			builder = builder.connect(endpoint);
+		}
+		for endpoint in &self.listen {
+			builder = builder.listen(endpoint);
+		}
+		builder.build()
+	}
+}
 // endregion:	--- Cli
 
 fn operation_state_parser(s: &str) -> Result<OperationState> {
 	OperationState::try_from(s)
 }
 
+fn log_level_parser(s: &str) -> Result<LogLevel> {
+	LogLevel::try_from(s)
+}
+
+// region:		--- helper
+/// Returns a flag that is set when 'ctrl-c' is pressed
+fn ctrl_c_flag() -> Arc<AtomicBool> {
+	let flag = Arc::new(AtomicBool::new(false));
+	let clone = flag.clone();
+	std::thread::spawn(move || {
+		if let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+			.enable_all()
+			.build()
+		{
+			if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+				clone.store(true, Ordering::Relaxed);
+			}
+		}
+	});
+	flag
+}
+
+/// Prints a table of entities
+fn print_entities(list: &[AboutEntity]) {
+	let h_zid = "ZenohId";
+	let h_kind = "Kind";
+	let h_state = "State";
+	let h_health = "Health";
+	let h_name = "Prefix/Name";
+	println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_health:8}  {h_name}");
+	for item in list {
+		println!(
+			"{:32}  {:6}  {:10}  {:8}  {}",
+			item.zid(),
+			item.kind(),
+			item.state().to_string(),
+			item.health().to_string(),
+			item.name()
+		);
+	}
+}
+
+/// Prints a table with the health checks of the entities
+fn print_health(list: &[AboutEntity]) {
+	println!(
+		"{:32}  {:20}  {:8}  {:24}  Reason",
+		"ZenohId", "Check", "Status", "Prefix/Name"
+	);
+	for item in list {
+		for check in item.health().checks() {
+			println!(
+				"{:32}  {:20}  {:8}  {:24}  {}",
+				item.zid(),
+				check.name(),
+				check.status().to_string(),
+				item.name(),
+				check.reason().unwrap_or("-"),
+			);
+		}
+	}
+}
+
+/// Converts a [`Duration`] into fractional milliseconds
+fn millis(duration: Duration) -> f64 {
+	duration.as_secs_f64() * 1000.0
+}
+
+/// Formats an optional [`Duration`] as fractional milliseconds
+fn format_millis(duration: Option<Duration>) -> String {
+	duration.map_or_else(
+		|| String::from("-"),
+		|value| format!("{:.3}", millis(value)),
+	)
+}
+
+/// Pings the `target` in rounds with all given payload sizes and prints the statistics.
+/// A `count` of 0 pings continuously until 'ctrl-c' is pressed.
+fn ping(com: &Communicator, target: &String, count: u32, interval: Duration, sizes: &[usize]) {
+	let stop = ctrl_c_flag();
+	let mut series = PingSeries::new();
+	let mut round = 0u32;
+	while (count == 0 || round < count) && !stop.load(Ordering::Relaxed) {
+		round += 1;
+		for payload_size in sizes {
+			let list = match dimas_commands::ping_list(com, target, *payload_size) {
+				Ok(list) => list,
+				Err(error) => {
+					println!("no reply for {payload_size} bytes: {error}");
+					Vec::new()
+				}
+			};
+			for item in &list {
+				println!(
+					"{:32}  {:>8}B  {:>9.3}ms  {}",
+					item.0.zid(),
+					payload_size,
+					millis(item.1),
+					item.0.name(),
+				);
+			}
+			series.record(*payload_size, &list);
+		}
+		if count != round && !stop.load(Ordering::Relaxed) {
+			std::thread::sleep(interval);
+		}
+	}
+	print_ping_statistics(&series.statistics());
+}
+
+/// Prints a streamed log record
+fn print_log(entity: &LogEntity) {
+	let time = chrono::DateTime::from_timestamp_nanos(entity.timestamp())
+		.with_timezone(&chrono::Local)
+		.format("%H:%M:%S%.3f");
+	println!("{time}  {entity}");
+}
+
+/// Prints the streamed log records of the entities matching `base_selector`
+/// until 'ctrl-c' is pressed
+fn follow_logs(com: &Communicator, base_selector: &String) -> Result<()> {
+	let stop = ctrl_c_flag();
+	let _subscriber = dimas_commands::log_subscriber(com, base_selector, |entity| {
+		print_log(&entity);
+	})?;
+	while !stop.load(Ordering::Relaxed) {
+		std::thread::sleep(Duration::from_millis(100));
+	}
+	Ok(())
+}
+
+/// Prints and optionally changes the log levels of the entities matching `base_selector`,
+/// then follows their streamed log records if requested.
+/// When following without a level, the levels are kept as they are.
+fn logs(
+	config: &Config,
+	base_selector: &String,
+	level: Option<LogLevel>,
+	follow: bool,
+) -> Result<()> {
+	let com = Communicator::new(config.zenoh_config())?;
+	if level.is_some() || !follow {
+		println!("List of log levels of DiMAS entities:");
+		println!("{:32}  {:6}  Prefix/Name", "ZenohId", "Level");
+		let list = dimas_commands::set_log_level(&com, base_selector, level)?;
+		for item in list {
+			println!(
+				"{:32}  {:6}  {}",
+				item.zid(),
+				item.level().to_string(),
+				item.name()
+			);
+		}
+	}
+	if follow {
+		follow_logs(&com, base_selector)?;
+	}
+	Ok(())
+}
+
+/// Lists, gets or sets parameters of the entities matching `base_selector`
+fn param(config: &Config, base_selector: &String, command: &ParamCommand) -> Result<()> {
+	let com = Communicator::new(config.zenoh_config())?;
+	let list = match command {
+		ParamCommand::List => dimas_commands::parameter_list(&com, base_selector)?,
+		ParamCommand::Get { name } => dimas_commands::get_parameter(&com, base_selector, name)?,
+		ParamCommand::Set { name, value } => {
+			dimas_commands::set_parameter(&com, base_selector, name, value)?
+		}
+	};
+	println!("List of parameters of DiMAS entities:");
+	print_parameters(&list);
+	Ok(())
+}
+
+/// Prints a table with the parameters of the entities
+fn print_parameters(list: &[ParameterEntity]) {
+	println!(
+		"{:32}  {:20}  {:8}  {:16}  {:20}  {:24}  Description",
+		"ZenohId", "Parameter", "Type", "Value", "Range", "Prefix/Name"
+	);
+	for item in list {
+		for parameter in item.parameters() {
+			let range = parameter
+				.range()
+				.map_or_else(|| String::from("-"), |(min, max)| format!("{min} .. {max}"));
+			println!(
+				"{:32}  {:20}  {:8}  {:16}  {:20}  {:24}  {}",
+				item.zid(),
+				parameter.name(),
+				parameter.value().kind(),
+				parameter.value().to_string(),
+				range,
+				item.name(),
+				parameter.description(),
+			);
+		}
+		if let Some(error) = item.error() {
+			println!("{:32}  error: {error}  {}", item.zid(), item.name());
+		}
+	}
+}
+
+/// Prints the metrics of the entities matching `base_selector`,
+/// either as table or in the Prometheus text format
+fn metrics(config: &Config, base_selector: &String, as_prometheus: bool) -> Result<()> {
+	let com = Communicator::new(config.zenoh_config())?;
+	let list = dimas_commands::metrics_list(&com, base_selector)?;
+	if as_prometheus {
+		for item in &list {
+			println!("# {} {}", item.zid(), item.name());
+			print!("{}", prometheus(item.metrics()));
+		}
+	} else {
+		println!("List of metrics of DiMAS entities:");
+		print_metrics(&list);
+	}
+	Ok(())
+}
+
+/// Prints a table with the metrics of the entities
+fn print_metrics(list: &[MetricsEntity]) {
+	println!(
+		"{:32}  {:36}  {:40}  {:>24}  Prefix/Name",
+		"ZenohId", "Metric", "Labels", "Value"
+	);
+	for item in list {
+		for metric in item.metrics() {
+			let labels = metric
+				.labels()
+				.iter()
+				.map(|(key, value)| format!("{key}={value}"))
+				.collect::<Vec<String>>()
+				.join(",");
+			let value = match metric.value() {
+				MetricValue::Counter(count) => count.to_string(),
+				#[allow(clippy::cast_precision_loss)]
+				MetricValue::Histogram { sum, count, .. } => {
+					let avg = if *count == 0 {
+						0.0
+					} else {
+						sum * 1000.0 / *count as f64
+					};
+					format!("{count} x {avg:.3}ms")
+				}
+			};
+			println!(
+				"{:32}  {:36}  {:40}  {:>24}  {}",
+				item.zid(),
+				metric.name(),
+				labels,
+				value,
+				item.name(),
+			);
+		}
+	}
+}
+
+/// Prints a table with the statistics of a ping series
+fn print_ping_statistics(statistics: &[PingStatistics]) {
+	println!("\nPing statistics [ms]:");
+	println!(
+		"{:32}  {:>9}  {:>5}  {:>5}  {:>6}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  Prefix/Name",
+		"ZenohId", "Size", "Sent", "Recv", "Loss", "Min", "Avg", "Max", "P95", "P99", "StdDev"
+	);
+	for item in statistics {
+		println!(
+			"{:32}  {:>8}B  {:>5}  {:>5}  {:>5.1}%  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {:>8}  {}",
+			item.zid(),
+			item.payload_size(),
+			item.sent(),
+			item.received(),
+			item.loss(),
+			format_millis(item.min()),
+			format_millis(item.avg()),
+			format_millis(item.max()),
+			format_millis(item.percentile(95)),
+			format_millis(item.percentile(99)),
+			format_millis(item.stddev()),
+			item.name(),
+		);
+	}
+}
+// endregion:	--- helper
+
 // region:		--- Commands
 #[derive(Debug, Subcommand)]
 enum DimasctlCommand {
+	/// Launch and supervise the agents described in a launch file
+	Launch {
+		/// The launch file
+		file: String,
+	},
+	/// Show the health checks of entities
+	Health,
 	/// List running `DiMAS` entities
 	List,
+	/// Show & change the log streaming of entities
+	Logs {
+		/// Follow the streamed log records until 'ctrl-c'
+		#[arg(short, long)]
+		follow: bool,
+		/// The new level for streaming (off, error, warn, info, debug, trace)
+		#[arg(short, long, value_parser = log_level_parser)]
+		level: Option<LogLevel>,
+	},
+	/// Show the metrics of entities
+	Metrics {
+		/// Print in the Prometheus text format instead of a table
+		#[arg(short, long)]
+		prometheus: bool,
+	},
+	/// List, get and set parameters of entities
+	Param {
+		#[clap(subcommand)]
+		command: ParamCommand,
+	},
 	/// Ping entities
 	Ping {
 		/// Selector for the targets to ping
 		/// - will be concatenated with optional selector
 		target: String,
-		/// An optional number of ping repetitiions
+		/// Number of ping rounds, 0 pings continuously until 'ctrl-c'
 		#[arg(short, long, default_value = "1")]
-		count: u8,
+		count: u32,
+		/// Interval between ping rounds in milliseconds
+		#[arg(short, long, default_value = "1000")]
+		interval: u64,
+		/// Comma separated list of payload sizes in bytes
+		#[arg(short, long, value_delimiter = ',', default_value = "0")]
+		size: Vec<usize>,
 	},
 	/// Scout for `Zenoh` entities
 	Scout,
@@ -55,56 +422,73 @@ enum DimasctlCommand {
 		target: String,
 	},
 }
+
+#[derive(Debug, Subcommand)]
+enum ParamCommand {
+	/// List all parameters
+	List,
+	/// Get a parameter
+	Get {
+		/// Name of the parameter
+		name: String,
+	},
+	/// Set a parameter
+	Set {
+		/// Name of the parameter
+		name: String,
+		/// The new value
+		value: String,
+	},
+}
 // endregion:	--- Commands
 
 fn main() -> Result<()> {
 	let args = DimasctlArgs::parse();
-	let config = Config::default();
-	let h_zid = "ZenohId";
-	let h_kind = "Kind";
-	let h_state = "State";
-	let h_name = "Prefix/Name";
-
+	let config = args.config()?;
 	let base_selector = args
 		.selector
 		.clone()
 		.map_or_else(|| String::from("**"), |selector| selector);
 
 	match &args.command {
+		DimasctlCommand::Health => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			println!("List of health checks of DiMAS entities:");
+			print_health(&dimas_commands::about_list(&com, &base_selector)?);
+		}
 		DimasctlCommand::List => {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
 			println!("List of found DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
-			let list = dimas_commands::about_list(&com, &base_selector)?;
-			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
-			}
+			print_entities(&dimas_commands::about_list(&com, &base_selector)?);
 		}
-		DimasctlCommand::Ping { target, count } => {
+		DimasctlCommand::Logs { follow, level } => {
+			logs(&config, &base_selector, *level, *follow)?;
+		}
+		DimasctlCommand::Launch { file } => launch::launch(&config, file)?,
+		DimasctlCommand::Metrics { prometheus } => {
+			metrics(&config, &base_selector, *prometheus)?;
+		}
+		DimasctlCommand::Param { command } => param(&config, &base_selector, command)?,
+		DimasctlCommand::Ping {
+			target,
+			count,
+			interval,
+			size,
+		} => {
 			let target = args
 				.selector
 				.map_or_else(|| target.to_owned(), |value| format!("{value}/{target}"));
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
-			for _ in 0..*count {
-				let list = dimas_commands::ping_list(&com, &target)?;
-				for item in list {
-					#[allow(clippy::cast_precision_loss)]
-					let time = item.1 as f64 / 2_000_000.0;
-					println!("{:32}  {:6.2}ms  {}", item.0.zid(), time, item.0.name(),);
-				}
-				if *count > 1 {
-					println!("\r");
-					std::thread::sleep(Duration::from_millis(1000));
-				}
-			}
+			ping(
+				&com,
+				&target,
+				*count,
+				Duration::from_millis(*interval),
+				size,
+			);
 		}
 		DimasctlCommand::Scout => {
 			println!("List of scouted Zenoh entities:");
@@ -123,17 +507,11 @@ fn main() -> Result<()> {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
 			println!("List of current states of DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
-			let list = dimas_commands::set_state(&com, &base_selector, state.to_owned())?;
-			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
-			}
+			print_entities(&dimas_commands::set_state(
+				&com,
+				&base_selector,
+				state.to_owned(),
+			)?);
 		}
 		DimasctlCommand::Shutdown { target } => {
 			let target = args
@@ -142,17 +520,7 @@ fn main() -> Result<()> {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
 			println!("List of shut down DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
-			let list = dimas_commands::shutdown(&com, &target)?;
-			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
-			}
+			print_entities(&dimas_commands::shutdown(&com, &target)?);
 		}
 	}
 	Ok(())
@@ -0,0 +1,279 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Data collection layer of `dimasmon`
+//!
+//! The [`Collector`] periodically fetches the state of the `DiMAS` system
+//! and provides it as a [`Snapshot`] to the user interfaces.
+//! The log records streamed by the agents are handed over as they arrive.
+
+// region:		--- modules
+use crate::inspector::Inspector;
+use core::time::Duration;
+use dimas_com::zenoh::Communicator;
+use dimas_commands::messages::{AboutEntity, LogEntity, MetricsEntity, ScoutingEntity};
+use dimas_config::Config;
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	metrics::{Metric, MetricValue},
+};
+use std::{collections::HashMap, sync::Arc, thread::JoinHandle};
+use zenoh::pubsub::Subscriber;
+// endregion:	--- modules
+
+// region:		--- AgentInfo
+/// Collected information about a `DiMAS` agent
+#[derive(Debug, Clone)]
+pub struct AgentInfo {
+	/// The Zenoh ID
+	pub zid: String,
+	/// The fully qualified name
+	pub name: String,
+	/// The session mode
+	pub kind: String,
+	/// The current state
+	pub state: OperationState,
+	/// The roundtrip time of the last ping, if there was a reply
+	pub latency: Option<Duration>,
+}
+
+impl From<AboutEntity> for AgentInfo {
+	fn from(value: AboutEntity) -> Self {
+		Self {
+			zid: value.zid().to_string(),
+			name: value.name().to_string(),
+			kind: value.kind().to_string(),
+			state: value.state().to_owned(),
+			latency: None,
+		}
+	}
+}
+impl AgentInfo {
+	/// Get the latency in milliseconds as text, `-` if there was no reply
+	#[must_use]
+	pub fn latency_text(&self) -> String {
+		self.latency.map_or_else(
+			|| String::from("-"),
+			|latency| format!("{:.3}", latency.as_secs_f64() * 1000.0),
+		)
+	}
+}
+// endregion:	--- AgentInfo
+
+// region:		--- MetricInfo
+/// A collected metric of a `DiMAS` agent
+#[derive(Debug, Clone)]
+pub struct MetricInfo {
+	/// The fully qualified name of the agent
+	pub agent: String,
+	/// The name of the metric
+	pub name: String,
+	/// The labels as comma separated `key=value` pairs
+	pub labels: String,
+	/// The value, for a histogram the count and the average duration
+	pub value: String,
+}
+
+impl MetricInfo {
+	/// Create the [`MetricInfo`] for a `metric` of the agent named `agent`
+	fn new(agent: &str, metric: &Metric) -> Self {
+		let labels = metric
+			.labels()
+			.iter()
+			.map(|(key, value)| format!("{key}={value}"))
+			.collect::<Vec<String>>()
+			.join(",");
+		let value = match metric.value() {
+			MetricValue::Counter(count) => count.to_string(),
+			#[allow(clippy::cast_precision_loss)]
+			MetricValue::Histogram { sum, count, .. } => {
+				let avg = if *count == 0 {
+					0.0
+				} else {
+					sum * 1000.0 / *count as f64
+				};
+				format!("{count} x {avg:.3}ms")
+			}
+		};
+		Self {
+			agent: agent.to_string(),
+			name: metric.name().to_string(),
+			labels,
+			value,
+		}
+	}
+
+	/// Create the [`MetricInfo`]s for all metrics of an agent
+	fn from_entity(entity: &MetricsEntity) -> impl Iterator<Item = Self> + '_ {
+		entity
+			.metrics()
+			.iter()
+			.map(|metric| Self::new(entity.name(), metric))
+	}
+}
+// endregion:	--- MetricInfo
+
+// region:		--- Snapshot
+/// The state of the `DiMAS` system at a certain time
+#[derive(Clone, Default)]
+pub struct Snapshot {
+	/// The reachable agents, sorted by name and zid
+	pub agents: Vec<AgentInfo>,
+	/// The scouted zenoh nodes, sorted by zid
+	pub nodes: Vec<ScoutingEntity>,
+	/// The metrics of the agents, sorted by agent, name and labels
+	pub metrics: Vec<MetricInfo>,
+	/// Errors that occured while collecting
+	pub errors: Vec<String>,
+}
+// endregion:	--- Snapshot
+
+// region:		--- Collector
+/// Collects the information about the `DiMAS` system
+#[derive(Debug)]
+pub struct Collector {
+	/// The configuration, needed for scouting
+	config: Config,
+	/// The communicator used for the queries
+	com: Communicator,
+	/// The base selector restricting the scope
+	base_selector: String,
+}
+
+impl Collector {
+	/// Constructor
+	/// # Errors
+	pub fn new(config: Config, prefix: Option<&String>) -> Result<Self> {
+		let com = Communicator::new(config.zenoh_config())?;
+		let base_selector =
+			prefix.map_or_else(|| String::from("**"), |prefix| format!("{prefix}/**"));
+		Ok(Self {
+			config,
+			com,
+			base_selector,
+		})
+	}
+
+	/// Get the base selector
+	#[must_use]
+	pub fn base_selector(&self) -> &str {
+		&self.base_selector
+	}
+
+	/// Create an [`Inspector`] for the topics within the scope
+	/// # Errors
+	pub fn inspector(&self) -> Result<Inspector> {
+		Inspector::new(&self.com.session(), &self.base_selector)
+	}
+
+	/// Subscribe to the log records streamed by the agents within the scope.
+	/// The subscription ends, when the returned [`Subscriber`] is dropped.
+	/// # Errors
+	pub fn log_subscriber<F>(&self, callback: F) -> Result<Subscriber<()>>
+	where
+		F: Fn(LogEntity) + Send + Sync + 'static,
+	{
+		dimas_commands::log_subscriber(&self.com, &self.base_selector, callback)
+	}
+
+	/// Collect a [`Snapshot`] of the system
+	#[must_use]
+	pub fn collect(&self) -> Snapshot {
+		let mut snapshot = Snapshot::default();
+
+		match dimas_commands::about_list(&self.com, &self.base_selector) {
+			Ok(list) => {
+				let mut agents: HashMap<String, AgentInfo> = list
+					.into_iter()
+					.map(|item| (item.zid().to_string(), AgentInfo::from(item)))
+					.collect();
+				// only ping if there are agents, otherwise waiting for the timeout is useless
+				if !agents.is_empty() {
+					match dimas_commands::ping_list(&self.com, &self.base_selector, 0) {
+						Ok(list) => {
+							for (entity, roundtrip) in list {
+								if let Some(agent) = agents.get_mut(entity.zid()) {
+									agent.latency = Some(roundtrip);
+								}
+							}
+						}
+						Err(error) => snapshot
+							.errors
+							.push(format!("ping failed: {error}")),
+					}
+					match dimas_commands::metrics_list(&self.com, &self.base_selector) {
+						Ok(list) => {
+							let mut metrics: Vec<MetricInfo> = list
+								.iter()
+								.flat_map(MetricInfo::from_entity)
+								.collect();
+							metrics.sort_by(|a, b| {
+								a.agent
+									.cmp(&b.agent)
+									.then_with(|| a.name.cmp(&b.name))
+									.then_with(|| a.labels.cmp(&b.labels))
+							});
+							snapshot.metrics = metrics;
+						}
+						Err(error) => snapshot
+							.errors
+							.push(format!("metrics failed: {error}")),
+					}
+				}
+				let mut agents: Vec<AgentInfo> = agents.into_values().collect();
+				agents.sort_by(|a, b| {
+					a.name
+						.cmp(&b.name)
+						.then_with(|| a.zid.cmp(&b.zid))
+				});
+				snapshot.agents = agents;
+			}
+			Err(error) => snapshot
+				.errors
+				.push(format!("about failed: {error}")),
+		}
+
+		match dimas_commands::scouting_list(&self.config) {
+			Ok(mut list) => {
+				list.sort_by(|a, b| a.zid().cmp(b.zid()));
+				snapshot.nodes = list;
+			}
+			Err(error) => snapshot
+				.errors
+				.push(format!("scouting failed: {error}")),
+		}
+
+		snapshot
+	}
+
+	/// Set the [`OperationState`] of the agent with the given zid
+	/// # Errors
+	pub fn set_state(&self, zid: &String, state: OperationState) -> Result<Vec<AboutEntity>> {
+		dimas_commands::set_state(&self.com, zid, Some(state))
+	}
+
+	/// Shut down the agent with the given zid
+	/// # Errors
+	pub fn shutdown(&self, zid: &String) -> Result<Vec<AboutEntity>> {
+		dimas_commands::shutdown(&self.com, zid)
+	}
+
+	/// Spawn a thread collecting a [`Snapshot`] every `interval` and handing it to `handler`.
+	/// The thread ends, when the `handler` returns `false`.
+	pub fn spawn<F>(self: &Arc<Self>, interval: Duration, mut handler: F) -> JoinHandle<()>
+	where
+		F: FnMut(Snapshot) -> bool + Send + 'static,
+	{
+		let collector = self.clone();
+		std::thread::spawn(move || {
+			loop {
+				let snapshot = collector.collect();
+				if !handler(snapshot) {
+					break;
+				}
+				std::thread::sleep(interval);
+			}
+		})
+	}
+}
+// endregion:	--- Collector
@@ -0,0 +1,600 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Topic inspection layer of `dimasmon`
+//!
+//! The [`Inspector`] discovers the topics published within the scope,
+//! measures their rate & bandwidth and decodes the samples of a selected topic.
+
+// region:		--- modules
+use core::{fmt::Write, time::Duration};
+use dimas_core::{Result, message_types::Message};
+use std::{
+	collections::{BTreeMap, VecDeque},
+	sync::{Arc, Mutex},
+	time::Instant,
+};
+use zenoh::{Session, Wait, pubsub::Subscriber};
+// endregion:	--- modules
+
+// region:		--- constants
+/// Time window for rate & bandwidth measurement
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+/// Time window of the plotted values
+pub const PLOT_WINDOW: Duration = Duration::from_secs(60);
+/// Maximum number of kept values per plotted field
+const MAX_POINTS: usize = 1000;
+/// Maximum number of kept decoded samples
+const MAX_SAMPLES: usize = 100;
+/// Maximum number of bytes shown by the [`Codec::Hex`]
+const MAX_HEX_BYTES: usize = 64;
+// endregion:	--- constants
+
+// region:		--- Codec
+/// The codecs available to decode samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+	/// UTF-8 text, a text containing only a number is plotted
+	Utf8,
+	/// JSON, all numbers are plotted
+	Json,
+	/// Raw bytes in hexadecimal notation
+	Hex,
+	/// A bitcode encoded `String`
+	BitcodeString,
+	/// A bitcode encoded `f64`
+	BitcodeF64,
+	/// A bitcode encoded `i64`
+	BitcodeI64,
+	/// A bitcode encoded `u64`
+	BitcodeU64,
+}
+
+impl TryFrom<&str> for Codec {
+	type Error = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+	fn try_from(value: &str) -> Result<Self> {
+		Self::ALL
+			.into_iter()
+			.find(|codec| codec.name().eq_ignore_ascii_case(value))
+			.ok_or_else(|| format!("unknown codec '{value}'").into())
+	}
+}
+
+impl Codec {
+	/// All available codecs
+	pub const ALL: [Self; 7] = [
+		Self::Utf8,
+		Self::Json,
+		Self::Hex,
+		Self::BitcodeString,
+		Self::BitcodeF64,
+		Self::BitcodeI64,
+		Self::BitcodeU64,
+	];
+
+	/// Get the name
+	#[must_use]
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Utf8 => "Utf8",
+			Self::Json => "Json",
+			Self::Hex => "Hex",
+			Self::BitcodeString => "Bitcode<String>",
+			Self::BitcodeF64 => "Bitcode<f64>",
+			Self::BitcodeI64 => "Bitcode<i64>",
+			Self::BitcodeU64 => "Bitcode<u64>",
+		}
+	}
+
+	/// Decode a payload
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn decode(self, payload: &[u8]) -> Decoded {
+		match self {
+			Self::Utf8 => {
+				let text = String::from_utf8_lossy(payload).into_owned();
+				let fields = text
+					.trim()
+					.parse::<f64>()
+					.map(|value| vec![(String::from("value"), value)])
+					.unwrap_or_default();
+				Decoded { text, fields }
+			}
+			Self::Json => match serde_json::from_slice::<serde_json::Value>(payload) {
+				Ok(value) => {
+					let mut fields = Vec::new();
+					flatten("", &value, &mut fields);
+					Decoded {
+						text: value.to_string(),
+						fields,
+					}
+				}
+				Err(error) => Decoded::invalid(&error),
+			},
+			Self::Hex => {
+				let mut text = String::new();
+				for byte in payload.iter().take(MAX_HEX_BYTES) {
+					let _ = write!(text, "{byte:02x} ");
+				}
+				if payload.len() > MAX_HEX_BYTES {
+					text.push_str("...");
+				}
+				Decoded {
+					text: text.trim_end().to_string(),
+					fields: Vec::new(),
+				}
+			}
+			Self::BitcodeString => match Message::new(payload.to_vec()).decode::<String>() {
+				Ok(text) => Decoded {
+					text,
+					fields: Vec::new(),
+				},
+				Err(error) => Decoded::invalid(&error),
+			},
+			Self::BitcodeF64 => Decoded::number(Message::new(payload.to_vec()).decode::<f64>()),
+			Self::BitcodeI64 => Decoded::number(
+				Message::new(payload.to_vec())
+					.decode::<i64>()
+					.map(|value| value as f64),
+			),
+			Self::BitcodeU64 => Decoded::number(
+				Message::new(payload.to_vec())
+					.decode::<u64>()
+					.map(|value| value as f64),
+			),
+		}
+	}
+}
+
+/// Collects all numbers within a JSON value with their path
+fn flatten(path: &str, value: &serde_json::Value, fields: &mut Vec<(String, f64)>) {
+	match value {
+		serde_json::Value::Number(number) => {
+			if let Some(number) = number.as_f64() {
+				let name = if path.is_empty() { "value" } else { path };
+				fields.push((name.to_string(), number));
+			}
+		}
+		serde_json::Value::Array(values) => {
+			for (index, value) in values.iter().enumerate() {
+				flatten(&format!("{path}[{index}]"), value, fields);
+			}
+		}
+		serde_json::Value::Object(map) => {
+			for (key, value) in map {
+				let path = if path.is_empty() {
+					key.clone()
+				} else {
+					format!("{path}.{key}")
+				};
+				flatten(&path, value, fields);
+			}
+		}
+		_ => {}
+	}
+}
+// endregion:	--- Codec
+
+// region:		--- Decoded
+/// A decoded sample
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoded {
+	/// Textual representation
+	pub text: String,
+	/// The numeric fields with their name
+	pub fields: Vec<(String, f64)>,
+}
+
+impl Decoded {
+	fn invalid(error: &impl core::fmt::Display) -> Self {
+		Self {
+			text: format!("<undecodable: {error}>"),
+			fields: Vec::new(),
+		}
+	}
+
+	fn number(value: Result<f64>) -> Self {
+		match value {
+			Ok(value) => Self {
+				text: value.to_string(),
+				fields: vec![(String::from("value"), value)],
+			},
+			Err(error) => Self::invalid(&error),
+		}
+	}
+}
+// endregion:	--- Decoded
+
+// region:		--- RateMeter
+/// Measures sample rate and bandwidth within a sliding time window
+#[derive(Debug, Clone, Default)]
+pub struct RateMeter {
+	/// Arrival time and size of the samples within the window
+	arrivals: VecDeque<(Instant, usize)>,
+}
+
+impl RateMeter {
+	/// Register a sample of `size` bytes arriving at `now`
+	pub fn add(&mut self, now: Instant, size: usize) {
+		self.arrivals.push_back((now, size));
+		self.prune(now);
+	}
+
+	/// Remove the samples outside the window
+	fn prune(&mut self, now: Instant) {
+		while let Some((time, _)) = self.arrivals.front() {
+			if now.duration_since(*time) > RATE_WINDOW {
+				self.arrivals.pop_front();
+			} else {
+				break;
+			}
+		}
+	}
+
+	/// Get the rate in samples per second at `now`
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn rate(&mut self, now: Instant) -> f64 {
+		self.prune(now);
+		self.arrivals.len() as f64 / RATE_WINDOW.as_secs_f64()
+	}
+
+	/// Get the bandwidth in bytes per second at `now`
+	#[must_use]
+	#[allow(clippy::cast_precision_loss)]
+	pub fn bandwidth(&mut self, now: Instant) -> f64 {
+		self.prune(now);
+		let bytes: usize = self.arrivals.iter().map(|(_, size)| size).sum();
+		bytes as f64 / RATE_WINDOW.as_secs_f64()
+	}
+}
+// endregion:	--- RateMeter
+
+// region:		--- views
+/// Information about a discovered topic
+#[derive(Debug, Clone)]
+pub struct TopicView {
+	/// The key expression
+	pub key: String,
+	/// Total number of received samples
+	pub samples: u64,
+	/// Samples per second
+	pub rate: f64,
+	/// Bytes per second
+	pub bandwidth: f64,
+}
+
+/// The data of the subscribed topic
+#[derive(Debug, Clone)]
+pub struct SubscriptionView {
+	/// The key expression
+	pub key: String,
+	/// The used codec
+	pub codec: Codec,
+	/// Samples per second
+	pub rate: f64,
+	/// Bytes per second
+	pub bandwidth: f64,
+	/// The latest decoded samples, newest first
+	pub samples: Vec<String>,
+	/// The numeric fields with their values within the [`PLOT_WINDOW`],
+	/// given as (age, value) with the age in seconds
+	pub series: Vec<(String, Vec<(f64, f64)>)>,
+}
+
+/// The state of the [`Inspector`] at a certain time
+#[derive(Debug, Clone, Default)]
+pub struct InspectorView {
+	/// The discovered topics, sorted by key
+	pub topics: Vec<TopicView>,
+	/// The subscribed topic
+	pub subscription: Option<SubscriptionView>,
+}
+// endregion:	--- views
+
+// region:		--- Inspector
+/// Statistics of a topic
+#[derive(Debug, Default)]
+struct TopicStats {
+	samples: u64,
+	meter: RateMeter,
+}
+
+/// The subscribed topic
+#[derive(Debug)]
+struct Subscription {
+	key: String,
+	codec: Codec,
+	meter: RateMeter,
+	/// latest decoded samples, newest first
+	samples: VecDeque<String>,
+	/// values of the numeric fields with their arrival time
+	series: BTreeMap<String, VecDeque<(Instant, f64)>>,
+}
+
+impl Subscription {
+	fn new(key: String, codec: Codec) -> Self {
+		Self {
+			key,
+			codec,
+			meter: RateMeter::default(),
+			samples: VecDeque::new(),
+			series: BTreeMap::new(),
+		}
+	}
+
+	fn add(&mut self, now: Instant, payload: &[u8]) {
+		self.meter.add(now, payload.len());
+		let decoded = self.codec.decode(payload);
+		self.samples.push_front(decoded.text);
+		self.samples.truncate(MAX_SAMPLES);
+		for (name, value) in decoded.fields {
+			let values = self.series.entry(name).or_default();
+			values.push_back((now, value));
+			while values.len() > MAX_POINTS
+				|| values
+					.front()
+					.is_some_and(|(time, _)| now.duration_since(*time) > PLOT_WINDOW)
+			{
+				values.pop_front();
+			}
+		}
+	}
+
+	fn view(&mut self, now: Instant) -> SubscriptionView {
+		SubscriptionView {
+			key: self.key.clone(),
+			codec: self.codec,
+			rate: self.meter.rate(now),
+			bandwidth: self.meter.bandwidth(now),
+			samples: self.samples.iter().cloned().collect(),
+			series: self
+				.series
+				.iter()
+				.map(|(name, values)| {
+					let points = values
+						.iter()
+						.filter(|(time, _)| now.duration_since(*time) <= PLOT_WINDOW)
+						.map(|(time, value)| (now.duration_since(*time).as_secs_f64(), *value))
+						.collect();
+					(name.clone(), points)
+				})
+				.collect(),
+		}
+	}
+}
+
+/// The mutable state of the [`Inspector`]
+#[derive(Debug, Default)]
+struct State {
+	topics: BTreeMap<String, TopicStats>,
+	subscription: Option<Subscription>,
+}
+
+/// Discovers topics and inspects the samples of a subscribed topic
+#[derive(Debug)]
+pub struct Inspector {
+	/// The shared state
+	state: Arc<Mutex<State>>,
+	/// The subscriber for all topics within the scope
+	_subscriber: Subscriber<()>,
+}
+
+impl Inspector {
+	/// Constructor
+	/// # Errors
+	pub fn new(session: &Arc<Session>, base_selector: &str) -> Result<Self> {
+		let state = Arc::new(Mutex::new(State::default()));
+		let clone = state.clone();
+		let subscriber = session
+			.declare_subscriber(base_selector.to_string())
+			.callback(move |sample| {
+				let payload = sample.payload().to_bytes();
+				let now = Instant::now();
+				if let Ok(mut state) = clone.lock() {
+					let key = sample.key_expr().as_str();
+					let topic = state.topics.entry(key.to_string()).or_default();
+					topic.samples += 1;
+					topic.meter.add(now, payload.len());
+					if let Some(subscription) = state.subscription.as_mut() {
+						if subscription.key == key {
+							subscription.add(now, &payload);
+						}
+					}
+				}
+			})
+			.wait()?;
+		Ok(Self {
+			state,
+			_subscriber: subscriber,
+		})
+	}
+
+	/// Subscribe to the topic with `key`, decoding the samples with `codec`.
+	/// Replaces a former subscription.
+	pub fn subscribe(&self, key: &str, codec: Codec) {
+		if let Ok(mut state) = self.state.lock() {
+			state.subscription = Some(Subscription::new(key.to_string(), codec));
+		}
+	}
+
+	/// Change the codec of the subscription, which resets the decoded data
+	pub fn set_codec(&self, codec: Codec) {
+		if let Ok(mut state) = self.state.lock() {
+			if let Some(subscription) = state
+				.subscription
+				.as_mut()
+				.filter(|subscription| subscription.codec != codec)
+			{
+				*subscription = Subscription::new(subscription.key.clone(), codec);
+			}
+		}
+	}
+
+	/// Stop the subscription
+	pub fn unsubscribe(&self) {
+		if let Ok(mut state) = self.state.lock() {
+			state.subscription = None;
+		}
+	}
+
+	/// Get the current [`InspectorView`]
+	#[must_use]
+	pub fn view(&self) -> InspectorView {
+		let now = Instant::now();
+		self.state
+			.lock()
+			.map(|mut state| {
+				let state = &mut *state;
+				InspectorView {
+					topics: state
+						.topics
+						.iter_mut()
+						.map(|(key, topic)| TopicView {
+							key: key.clone(),
+							samples: topic.samples,
+							rate: topic.meter.rate(now),
+							bandwidth: topic.meter.bandwidth(now),
+						})
+						.collect(),
+					subscription: state
+						.subscription
+						.as_mut()
+						.map(|subscription| subscription.view(now)),
+				}
+			})
+			.unwrap_or_default()
+	}
+}
+// endregion:	--- Inspector
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Codec>();
+		is_normal::<Decoded>();
+		is_normal::<RateMeter>();
+		is_normal::<Inspector>();
+	}
+
+	/// Check that `value` is `expected` within the float precision
+	fn near(value: f64, expected: f64) -> bool {
+		(value - expected).abs() < 1e-9
+	}
+
+	#[test]
+	fn codec_names() -> Result<()> {
+		for codec in Codec::ALL {
+			assert_eq!(Codec::try_from(codec.name())?, codec);
+		}
+		assert_eq!(Codec::try_from("json")?, Codec::Json);
+		assert!(Codec::try_from("xml").is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn decode_utf8() {
+		let decoded = Codec::Utf8.decode(b" 42.5\n");
+		assert_eq!(decoded.text, " 42.5\n");
+		assert_eq!(decoded.fields, [(String::from("value"), 42.5)]);
+
+		let decoded = Codec::Utf8.decode(b"hello");
+		assert_eq!(decoded.text, "hello");
+		assert!(decoded.fields.is_empty());
+	}
+
+	#[test]
+	fn decode_json() {
+		let decoded = Codec::Json.decode(br#"{"a": 1, "b": [2, {"c": 3.5}], "d": "text"}"#);
+		assert_eq!(decoded.text, r#"{"a":1,"b":[2,{"c":3.5}],"d":"text"}"#);
+		assert_eq!(
+			decoded.fields,
+			[
+				(String::from("a"), 1.0),
+				(String::from("b[0]"), 2.0),
+				(String::from("b[1].c"), 3.5)
+			]
+		);
+
+		let decoded = Codec::Json.decode(b"7");
+		assert_eq!(decoded.fields, [(String::from("value"), 7.0)]);
+
+		let decoded = Codec::Json.decode(b"{invalid");
+		assert!(decoded.text.starts_with("<undecodable: "));
+		assert!(decoded.fields.is_empty());
+	}
+
+	#[test]
+	fn decode_hex() {
+		let decoded = Codec::Hex.decode(&[0x00, 0x1f, 0xab]);
+		assert_eq!(decoded.text, "00 1f ab");
+		assert!(decoded.fields.is_empty());
+
+		let decoded = Codec::Hex.decode(&[0xff; MAX_HEX_BYTES + 1]);
+		assert!(decoded.text.ends_with("ff ..."));
+		assert_eq!(decoded.text.matches("ff").count(), MAX_HEX_BYTES);
+	}
+
+	#[test]
+	fn decode_bitcode() {
+		let decoded =
+			Codec::BitcodeString.decode(&Message::encode(&String::from("text")).to_bytes());
+		assert_eq!(decoded.text, "text");
+		assert!(decoded.fields.is_empty());
+
+		let decoded = Codec::BitcodeF64.decode(&Message::encode(&-1.5_f64).to_bytes());
+		assert_eq!(decoded.text, "-1.5");
+		assert_eq!(decoded.fields, [(String::from("value"), -1.5)]);
+
+		let decoded = Codec::BitcodeI64.decode(&Message::encode(&-7_i64).to_bytes());
+		assert_eq!(decoded.text, "-7");
+		assert_eq!(decoded.fields, [(String::from("value"), -7.0)]);
+
+		let decoded = Codec::BitcodeU64.decode(&Message::encode(&7_u64).to_bytes());
+		assert_eq!(decoded.text, "7");
+		assert_eq!(decoded.fields, [(String::from("value"), 7.0)]);
+
+		for codec in [
+			Codec::BitcodeString,
+			Codec::BitcodeF64,
+			Codec::BitcodeI64,
+			Codec::BitcodeU64,
+		] {
+			let decoded = codec.decode(&[]);
+			assert!(
+				decoded.text.starts_with("<undecodable: "),
+				"{}",
+				codec.name()
+			);
+			assert!(decoded.fields.is_empty());
+		}
+	}
+
+	#[test]
+	fn rate_meter() {
+		let start = Instant::now();
+		let mut meter = RateMeter::default();
+		assert!(near(meter.rate(start), 0.0));
+		for second in 0..5 {
+			meter.add(start + Duration::from_secs(second), 100);
+		}
+		let now = start + Duration::from_secs(4);
+		assert!(near(meter.rate(now), 1.0));
+		assert!(near(meter.bandwidth(now), 100.0));
+
+		// the samples older than the window are dropped, the one at its border is kept
+		let now = start + Duration::from_secs(7);
+		assert!(near(meter.rate(now), 0.6));
+		assert!(near(meter.bandwidth(now), 60.0));
+
+		let now = start + Duration::from_secs(10);
+		assert!(near(meter.rate(now), 0.0));
+		assert!(near(meter.bandwidth(now), 0.0));
+	}
+}
@@ -4,8 +4,24 @@
 
 // region:		--- modules
 slint::include_modules!();
+mod collector;
+mod inspector;
+mod tui;
+
 use clap::Parser;
-use std::path::PathBuf;
+use collector::{Collector, Snapshot};
+use core::time::Duration;
+use dimas_config::Config;
+use dimas_core::{Result, enums::OperationState};
+use inspector::{Codec, Inspector, InspectorView, PLOT_WINDOW};
+use slint::{
+	Color, ComponentHandle, Model, ModelRc, SharedString, StandardListViewItem, Timer, TimerMode,
+	VecModel,
+};
+use std::{
+	path::PathBuf,
+	sync::{Arc, Mutex},
+};
 // endregion:	--- modules
 
 // region:		--- Cli
@@ -18,19 +34,361 @@ struct Cli {
 	/// Use config file
 	#[arg(short, long, value_name = "FILE")]
 	config: Option<PathBuf>,
-}
 
+	/// Refresh interval in milliseconds
+	#[arg(short, long, default_value = "2000")]
+	interval: u64,
+
+	/// Refresh interval of the topic inspector in milliseconds
+	#[arg(short, long, default_value = "500")]
+	topic_interval: u64,
+
+	/// Run with a terminal user interface instead of a window
+	#[arg(long)]
+	tui: bool,
+}
 // endregion:	--- Cli
 
-fn main() -> Result<(), slint::PlatformError> {
+// region:		--- helper
+/// Creates a table model from rows of strings
+fn table(rows: Vec<Vec<String>>) -> ModelRc<ModelRc<StandardListViewItem>> {
+	let rows: Vec<ModelRc<StandardListViewItem>> = rows
+		.into_iter()
+		.map(|row| {
+			let items: Vec<StandardListViewItem> = row
+				.iter()
+				.map(|item| StandardListViewItem::from(item.as_str()))
+				.collect();
+			ModelRc::new(VecModel::from(items))
+		})
+		.collect();
+	ModelRc::new(VecModel::from(rows))
+}
+
+/// Creates the status line text for a [`Snapshot`]
+fn status_text(snapshot: &Snapshot) -> String {
+	snapshot.errors.last().map_or_else(
+		|| {
+			format!(
+				"{} agents, {} zenoh nodes",
+				snapshot.agents.len(),
+				snapshot.nodes.len()
+			)
+		},
+		Clone::clone,
+	)
+}
+
+/// Shows a [`Snapshot`] in the main window
+fn show_snapshot(ui: &MainWindow, snapshot: &Snapshot) {
+	let agents = snapshot
+		.agents
+		.iter()
+		.map(|agent| {
+			vec![
+				agent.name.clone(),
+				agent.kind.clone(),
+				agent.state.to_string(),
+				agent.latency_text(),
+				agent.zid.clone(),
+			]
+		})
+		.collect();
+	let nodes = snapshot
+		.nodes
+		.iter()
+		.map(|node| {
+			vec![
+				node.zid().to_string(),
+				node.kind().to_string(),
+				node.locators().join(", "),
+			]
+		})
+		.collect();
+	let metrics = snapshot
+		.metrics
+		.iter()
+		.map(|metric| {
+			vec![
+				metric.agent.clone(),
+				metric.name.clone(),
+				metric.labels.clone(),
+				metric.value.clone(),
+			]
+		})
+		.collect();
+	// keep the selection only as long as the row still exists
+	if usize::try_from(ui.get_selected_agent())
+		.ok()
+		.is_none_or(|row| row >= snapshot.agents.len())
+	{
+		ui.set_selected_agent(-1);
+	}
+	ui.set_agents(table(agents));
+	ui.set_nodes(table(nodes));
+	ui.set_metrics(table(metrics));
+	ui.set_status(SharedString::from(status_text(snapshot)));
+}
+
+/// Colors for the plot lines
+const PLOT_COLORS: [(u8, u8, u8); 6] = [
+	(31, 119, 180),
+	(255, 127, 14),
+	(44, 160, 44),
+	(214, 39, 40),
+	(148, 103, 189),
+	(140, 86, 75),
+];
+
+/// Creates the svg path commands for a series of (age, value) points
+/// within a 1000x1000 viewbox, scaled to the values `min` and `max`
+fn plot_commands(points: &[(f64, f64)], min: f64, max: f64) -> String {
+	let window = PLOT_WINDOW.as_secs_f64();
+	let range = max - min;
+	points
+		.iter()
+		.rev()
+		.enumerate()
+		.map(|(index, (age, value))| {
+			let x = (window - age) / window * 1000.0;
+			let y = if range > f64::EPSILON {
+				((value - min) / range).mul_add(-1000.0, 1000.0)
+			} else {
+				500.0
+			};
+			let command = if index == 0 { 'M' } else { 'L' };
+			format!("{command} {x:.1} {y:.1}")
+		})
+		.collect::<Vec<String>>()
+		.join(" ")
+}
+
+/// Shows an [`InspectorView`] in the main window
+fn show_inspector_view(ui: &MainWindow, view: &InspectorView) {
+	let topics = view
+		.topics
+		.iter()
+		.map(|topic| {
+			vec![
+				topic.key.clone(),
+				topic.samples.to_string(),
+				format!("{:.1}", topic.rate),
+				format!("{:.0}", topic.bandwidth),
+			]
+		})
+		.collect();
+	ui.set_topics(table(topics));
+
+	let Some(subscription) = &view.subscription else {
+		ui.set_subscribed_topic(SharedString::new());
+		ui.set_samples(ModelRc::default());
+		ui.set_plot_lines(ModelRc::default());
+		return;
+	};
+	ui.set_subscribed_topic(SharedString::from(subscription.key.as_str()));
+	ui.set_subscription_info(SharedString::from(format!(
+		"{:.1} Hz, {:.0} B/s, decoded as {}",
+		subscription.rate,
+		subscription.bandwidth,
+		subscription.codec.name()
+	)));
+	let samples: Vec<StandardListViewItem> = subscription
+		.samples
+		.iter()
+		.map(|sample| StandardListViewItem::from(sample.as_str()))
+		.collect();
+	ui.set_samples(ModelRc::new(VecModel::from(samples)));
+	let lines: Vec<PlotLine> = subscription
+		.series
+		.iter()
+		.filter(|(_, points)| points.len() > 1)
+		.zip(PLOT_COLORS.iter().cycle())
+		.map(|((name, points), (red, green, blue))| {
+			let min = points
+				.iter()
+				.map(|(_, value)| *value)
+				.fold(f64::INFINITY, f64::min);
+			let max = points
+				.iter()
+				.map(|(_, value)| *value)
+				.fold(f64::NEG_INFINITY, f64::max);
+			PlotLine {
+				name: SharedString::from(name.as_str()),
+				commands: SharedString::from(plot_commands(points, min, max)),
+				color: Color::from_rgb_u8(*red, *green, *blue),
+				min: SharedString::from(format!("{min}")),
+				max: SharedString::from(format!("{max}")),
+			}
+		})
+		.collect();
+	ui.set_plot_lines(ModelRc::new(VecModel::from(lines)));
+}
+
+/// Connects the topic inspection callbacks of the main window with the [`Inspector`]
+/// and returns the timer refreshing the view every `interval`
+fn connect_inspector(ui: &MainWindow, inspector: &Arc<Inspector>, interval: Duration) -> Timer {
+	{
+		let ui_weak = ui.as_weak();
+		let inspector = inspector.clone();
+		ui.on_subscribe(move |row, codec| {
+			let Some(ui) = ui_weak.upgrade() else {
+				return;
+			};
+			// the key is the first column of the displayed row
+			let key = usize::try_from(row)
+				.ok()
+				.and_then(|row| ui.get_topics().row_data(row))
+				.and_then(|columns| columns.row_data(0));
+			match (key, Codec::try_from(codec.as_str())) {
+				(Some(key), Ok(codec)) => inspector.subscribe(&key.text, codec),
+				(None, _) => ui.set_status(SharedString::from("no topic selected")),
+				(_, Err(error)) => ui.set_status(SharedString::from(error.to_string())),
+			}
+		});
+	}
+	{
+		let inspector = inspector.clone();
+		ui.on_codec_changed(move |codec| {
+			if let Ok(codec) = Codec::try_from(codec.as_str()) {
+				inspector.set_codec(codec);
+			}
+		});
+	}
+	{
+		let inspector = inspector.clone();
+		ui.on_unsubscribe(move || inspector.unsubscribe());
+	}
+	let timer = Timer::default();
+	{
+		let ui_weak = ui.as_weak();
+		let inspector = inspector.clone();
+		timer.start(TimerMode::Repeated, interval, move || {
+			if let Some(ui) = ui_weak.upgrade() {
+				show_inspector_view(&ui, &inspector.view());
+			}
+		});
+	}
+	timer
+}
+
+/// Looks up the zid of the agent shown in `row`
+fn zid_of(zids: &Mutex<Vec<String>>, row: i32) -> Option<String> {
+	let index = usize::try_from(row).ok()?;
+	zids.lock()
+		.ok()
+		.and_then(|zids| zids.get(index).cloned())
+}
+
+/// Runs a command in the background and reports its result in the status line
+fn run_command<F>(ui: slint::Weak<MainWindow>, description: String, command: F)
+where
+	F: FnOnce() -> Result<usize> + Send + 'static,
+{
+	std::thread::spawn(move || {
+		let status = match command() {
+			Ok(count) => format!("{description}: {count} agent(s) replied"),
+			Err(error) => format!("{description} failed: {error}"),
+		};
+		let _ = ui.upgrade_in_event_loop(move |ui| ui.set_status(SharedString::from(status)));
+	});
+}
+// endregion:	--- helper
+
+fn main() -> Result<()> {
 	// parse args
-	let _cli = Cli::parse();
+	let cli = Cli::parse();
+	let config = match &cli.config {
+		Some(path) => Config::from_file(&path.to_string_lossy())?,
+		None => Config::default(),
+	};
+	let collector = Arc::new(Collector::new(config, cli.prefix.as_ref())?);
+	let inspector = collector.inspector()?;
+	let interval = Duration::from_millis(cli.interval);
+	let topic_interval = Duration::from_millis(cli.topic_interval);
+
+	if cli.tui {
+		tui::run(collector, inspector, interval, topic_interval)
+	} else {
+		run_gui(&collector, inspector, interval, topic_interval)
+	}
+}
+
+/// Runs the graphical user interface until the window is closed
+fn run_gui(
+	collector: &Arc<Collector>,
+	inspector: Inspector,
+	interval: Duration,
+	topic_interval: Duration,
+) -> Result<()> {
+	// zids of the agents in the order they are displayed
+	let zids = Arc::new(Mutex::new(Vec::<String>::new()));
 
 	// create window
-	let ui = MainWindow::new()?;
+	let ui = MainWindow::new().map_err(|error| error.to_string())?;
+	ui.set_status(SharedString::from(format!(
+		"collecting information for '{}'",
+		collector.base_selector()
+	)));
 
 	// implement handlers/callbacks
+	{
+		let ui_weak = ui.as_weak();
+		let collector = collector.clone();
+		let zids = zids.clone();
+		ui.on_set_state(move |row, state| {
+			let Some(zid) = zid_of(&zids, row) else {
+				return;
+			};
+			match OperationState::try_from(state.as_str()) {
+				Ok(state) => {
+					let collector = collector.clone();
+					run_command(ui_weak.clone(), format!("set state of {zid}"), move || {
+						collector
+							.set_state(&zid, state)
+							.map(|list| list.len())
+					});
+				}
+				Err(error) => ui_weak
+					.upgrade()
+					.iter()
+					.for_each(|ui| ui.set_status(SharedString::from(error.to_string()))),
+			}
+		});
+	}
+	{
+		let ui_weak = ui.as_weak();
+		let collector = collector.clone();
+		let zids = zids.clone();
+		ui.on_shutdown(move |row| {
+			let Some(zid) = zid_of(&zids, row) else {
+				return;
+			};
+			let collector = collector.clone();
+			run_command(ui_weak.clone(), format!("shutdown of {zid}"), move || {
+				collector.shutdown(&zid).map(|list| list.len())
+			});
+		});
+	}
+
+	// inspect topics
+	let _inspector_timer = connect_inspector(&ui, &Arc::new(inspector), topic_interval);
+
+	// collect data in the background
+	let ui_weak = ui.as_weak();
+	collector.spawn(interval, move |snapshot| {
+		if let Ok(mut zids) = zids.lock() {
+			*zids = snapshot
+				.agents
+				.iter()
+				.map(|agent| agent.zid.clone())
+				.collect();
+		}
+		ui_weak
+			.upgrade_in_event_loop(move |ui| show_snapshot(&ui, &snapshot))
+			.is_ok()
+	});
 
 	// show & run window
-	ui.run()
+	ui.run().map_err(|error| error.to_string())?;
+	Ok(())
 }
@@ -0,0 +1,694 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Terminal user interface of `dimasmon`
+//!
+//! Shows the same data as the graphical user interface,
+//! but runs within a terminal, e.g. via ssh.
+//! The log view additionally shows the log records streamed by the agents.
+
+// region:		--- modules
+use crate::{
+	collector::{Collector, Snapshot},
+	inspector::{Codec, Inspector, InspectorView, PLOT_WINDOW},
+};
+use core::time::Duration;
+use dimas_commands::messages::LogEntity;
+use dimas_core::{
+	Result,
+	enums::{LogLevel, OperationState},
+};
+use ratatui::{
+	DefaultTerminal, Frame,
+	crossterm::event::{self, Event, KeyCode, KeyEventKind},
+	layout::{Constraint, Layout, Rect},
+	style::{Color, Modifier, Style},
+	symbols,
+	widgets::{
+		Axis, Block, Chart, Dataset, GraphType, List, ListItem, Paragraph, Row, Table, TableState,
+		Tabs,
+	},
+};
+use std::{
+	collections::VecDeque,
+	sync::{
+		Arc, Mutex,
+		atomic::{AtomicBool, Ordering},
+	},
+};
+// endregion:	--- modules
+
+// region:		--- constants
+/// Maximum number of kept log entries
+const MAX_LOG_ENTRIES: usize = 500;
+/// Colors for the plot lines
+const PLOT_COLORS: [Color; 6] = [
+	Color::Cyan,
+	Color::Yellow,
+	Color::Green,
+	Color::Red,
+	Color::Magenta,
+	Color::Blue,
+];
+// endregion:	--- constants
+
+// region:		--- EventLog
+/// An entry of the [`EventLog`]
+#[derive(Debug, Clone)]
+struct LogLine {
+	text: String,
+	/// the level of a log record streamed by an agent, `None` for the monitors own events
+	level: Option<LogLevel>,
+}
+
+impl LogLine {
+	/// The style highlighting errors and warnings and dimming verbose records
+	fn style(&self) -> Style {
+		match self.level {
+			Some(LogLevel::Error) => Style::default().fg(Color::Red),
+			Some(LogLevel::Warn) => Style::default().fg(Color::Yellow),
+			Some(LogLevel::Debug | LogLevel::Trace) => Style::default().fg(Color::DarkGray),
+			Some(LogLevel::Off | LogLevel::Info) | None => Style::default(),
+		}
+	}
+}
+
+/// The log of the events within the monitor and of the records streamed by the agents,
+/// newest first
+#[derive(Debug, Clone, Default)]
+struct EventLog {
+	entries: Arc<Mutex<VecDeque<LogLine>>>,
+}
+
+impl EventLog {
+	/// Add an entry with the current time
+	fn add(&self, text: &str) {
+		let now = chrono::Local::now().format("%H:%M:%S");
+		self.push(LogLine {
+			text: format!("{now}  {text}"),
+			level: None,
+		});
+	}
+
+	/// Add a log record streamed by an agent with its own time
+	fn add_record(&self, entity: &LogEntity) {
+		let time = chrono::DateTime::from_timestamp_nanos(entity.timestamp())
+			.with_timezone(&chrono::Local)
+			.format("%H:%M:%S");
+		self.push(LogLine {
+			text: format!("{time}  {entity}"),
+			level: Some(entity.level()),
+		});
+	}
+
+	fn push(&self, line: LogLine) {
+		if let Ok(mut entries) = self.entries.lock() {
+			entries.push_front(line);
+			entries.truncate(MAX_LOG_ENTRIES);
+		}
+	}
+
+	/// Get the entries, newest first
+	fn entries(&self) -> Vec<LogLine> {
+		self.entries
+			.lock()
+			.map(|entries| entries.iter().cloned().collect())
+			.unwrap_or_default()
+	}
+}
+// endregion:	--- EventLog
+
+// region:		--- View
+/// The views of the terminal user interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+	Agents,
+	Nodes,
+	Topics,
+	Metrics,
+	Log,
+}
+
+impl View {
+	const ALL: [Self; 5] = [
+		Self::Agents,
+		Self::Nodes,
+		Self::Topics,
+		Self::Metrics,
+		Self::Log,
+	];
+
+	const fn title(self) -> &'static str {
+		match self {
+			Self::Agents => "Agents",
+			Self::Nodes => "Zenoh nodes",
+			Self::Topics => "Topics",
+			Self::Metrics => "Metrics",
+			Self::Log => "Log",
+		}
+	}
+
+	const fn index(self) -> usize {
+		match self {
+			Self::Agents => 0,
+			Self::Nodes => 1,
+			Self::Topics => 2,
+			Self::Metrics => 3,
+			Self::Log => 4,
+		}
+	}
+
+	const fn next(self) -> Self {
+		Self::ALL[(self.index() + 1) % Self::ALL.len()]
+	}
+
+	const fn previous(self) -> Self {
+		Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+	}
+
+	const fn help(self) -> &'static str {
+		match self {
+			Self::Agents => {
+				"q: quit  tab: next view  ↑↓: select  1: active  2: standby  3: inactive  4: configured  5: created  x: shutdown"
+			}
+			Self::Topics => {
+				"q: quit  tab: next view  ↑↓: select  enter: subscribe  c: change codec  u: unsubscribe"
+			}
+			Self::Nodes | Self::Metrics | Self::Log => "q: quit  tab: next view",
+		}
+	}
+}
+// endregion:	--- View
+
+// region:		--- Tui
+/// The terminal user interface
+struct Tui {
+	collector: Arc<Collector>,
+	inspector: Inspector,
+	/// the latest snapshot, `None` until the first collection is done
+	snapshot: Arc<Mutex<Option<Snapshot>>>,
+	log: EventLog,
+	view: View,
+	agents: TableState,
+	topics: TableState,
+	codec: Codec,
+	inspection: InspectorView,
+	quit: bool,
+}
+
+impl Tui {
+	fn new(collector: Arc<Collector>, inspector: Inspector) -> Self {
+		Self {
+			collector,
+			inspector,
+			snapshot: Arc::new(Mutex::new(None)),
+			log: EventLog::default(),
+			view: View::Agents,
+			agents: TableState::default(),
+			topics: TableState::default(),
+			codec: Codec::Utf8,
+			inspection: InspectorView::default(),
+			quit: false,
+		}
+	}
+
+	/// Number of agents in the latest snapshot
+	fn agent_count(&self) -> usize {
+		self.snapshot
+			.lock()
+			.ok()
+			.and_then(|snapshot| {
+				snapshot
+					.as_ref()
+					.map(|snapshot| snapshot.agents.len())
+			})
+			.unwrap_or(0)
+	}
+
+	/// Zid of the selected agent
+	fn selected_zid(&self) -> Option<String> {
+		let index = self.agents.selected()?;
+		self.snapshot
+			.lock()
+			.ok()?
+			.as_ref()?
+			.agents
+			.get(index)
+			.map(|agent| agent.zid.clone())
+	}
+
+	/// Runs a command for the selected agent in the background and logs its result
+	fn run_command<F>(&self, description: &str, command: F)
+	where
+		F: FnOnce(&Collector, &String) -> Result<usize> + Send + 'static,
+	{
+		let Some(zid) = self.selected_zid() else {
+			self.log.add("no agent selected");
+			return;
+		};
+		let collector = self.collector.clone();
+		let log = self.log.clone();
+		let description = format!("{description} {zid}");
+		log.add(&description);
+		std::thread::spawn(move || match command(&collector, &zid) {
+			Ok(count) => log.add(&format!("{description}: {count} agent(s) replied")),
+			Err(error) => log.add(&format!("{description} failed: {error}")),
+		});
+	}
+
+	fn set_state(&self, state: OperationState) {
+		self.run_command(&format!("set state {state} of"), move |collector, zid| {
+			collector
+				.set_state(zid, state)
+				.map(|list| list.len())
+		});
+	}
+
+	fn handle_key(&mut self, code: KeyCode) {
+		match code {
+			KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+			KeyCode::Tab | KeyCode::Right => self.view = self.view.next(),
+			KeyCode::BackTab | KeyCode::Left => self.view = self.view.previous(),
+			_ => match self.view {
+				View::Agents => self.handle_agents_key(code),
+				View::Topics => self.handle_topics_key(code),
+				View::Nodes | View::Metrics | View::Log => {}
+			},
+		}
+	}
+
+	fn handle_agents_key(&mut self, code: KeyCode) {
+		match code {
+			KeyCode::Up => self.agents.select_previous(),
+			KeyCode::Down
+				if self
+					.agents
+					.selected()
+					.is_none_or(|index| index + 1 < self.agent_count()) =>
+			{
+				self.agents.select_next();
+			}
+			KeyCode::Char('1') => self.set_state(OperationState::Active),
+			KeyCode::Char('2') => self.set_state(OperationState::Standby),
+			KeyCode::Char('3') => self.set_state(OperationState::Inactive),
+			KeyCode::Char('4') => self.set_state(OperationState::Configured),
+			KeyCode::Char('5') => self.set_state(OperationState::Created),
+			KeyCode::Char('x') => self.run_command("shutdown of", |collector, zid| {
+				collector.shutdown(zid).map(|list| list.len())
+			}),
+			_ => {}
+		}
+	}
+
+	fn handle_topics_key(&mut self, code: KeyCode) {
+		match code {
+			KeyCode::Up => self.topics.select_previous(),
+			KeyCode::Down
+				if self
+					.topics
+					.selected()
+					.is_none_or(|index| index + 1 < self.inspection.topics.len()) =>
+			{
+				self.topics.select_next();
+			}
+			KeyCode::Enter => {
+				if let Some(topic) = self
+					.topics
+					.selected()
+					.and_then(|index| self.inspection.topics.get(index))
+				{
+					self.inspector.subscribe(&topic.key, self.codec);
+					self.log.add(&format!(
+						"subscribed to {} as {}",
+						topic.key,
+						self.codec.name()
+					));
+				}
+			}
+			KeyCode::Char('c') => {
+				let index = Codec::ALL
+					.iter()
+					.position(|codec| *codec == self.codec)
+					.unwrap_or(0);
+				self.codec = Codec::ALL[(index + 1) % Codec::ALL.len()];
+				self.inspector.set_codec(self.codec);
+			}
+			KeyCode::Char('u') => self.inspector.unsubscribe(),
+			_ => {}
+		}
+	}
+
+	fn draw(&mut self, frame: &mut Frame) {
+		let [tabs, body, help] = Layout::vertical([
+			Constraint::Length(3),
+			Constraint::Min(0),
+			Constraint::Length(1),
+		])
+		.areas(frame.area());
+
+		let titles = View::ALL.iter().map(|view| view.title());
+		frame.render_widget(
+			Tabs::new(titles)
+				.block(Block::bordered().title(format!(
+					" DiMAS Monitor: {} ",
+					self.collector.base_selector()
+				)))
+				.select(self.view.index())
+				.highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+			tabs,
+		);
+
+		match self.view {
+			View::Agents => self.draw_agents(frame, body),
+			View::Nodes => self.draw_nodes(frame, body),
+			View::Topics => self.draw_topics(frame, body),
+			View::Metrics => self.draw_metrics(frame, body),
+			View::Log => self.draw_log(frame, body),
+		}
+
+		frame.render_widget(Paragraph::new(self.view.help()), help);
+	}
+
+	fn draw_agents(&mut self, frame: &mut Frame, area: Rect) {
+		let rows: Vec<Row> = self
+			.snapshot
+			.lock()
+			.ok()
+			.and_then(|snapshot| {
+				snapshot.as_ref().map(|snapshot| {
+					snapshot
+						.agents
+						.iter()
+						.map(|agent| {
+							Row::new([
+								agent.name.clone(),
+								agent.kind.clone(),
+								agent.state.to_string(),
+								agent.latency_text(),
+								agent.zid.clone(),
+							])
+						})
+						.collect()
+				})
+			})
+			.unwrap_or_default();
+		let table = Table::new(
+			rows,
+			[
+				Constraint::Fill(2),
+				Constraint::Length(6),
+				Constraint::Length(10),
+				Constraint::Length(12),
+				Constraint::Length(32),
+			],
+		)
+		.header(header(&[
+			"Prefix/Name",
+			"Kind",
+			"State",
+			"Latency [ms]",
+			"ZenohId",
+		]))
+		.block(Block::bordered())
+		.row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+		frame.render_stateful_widget(table, area, &mut self.agents);
+	}
+
+	fn draw_nodes(&self, frame: &mut Frame, area: Rect) {
+		let rows: Vec<Row> = self
+			.snapshot
+			.lock()
+			.ok()
+			.and_then(|snapshot| {
+				snapshot.as_ref().map(|snapshot| {
+					snapshot
+						.nodes
+						.iter()
+						.map(|node| {
+							Row::new([
+								node.zid().to_string(),
+								node.kind().to_string(),
+								node.locators().join(", "),
+							])
+						})
+						.collect()
+				})
+			})
+			.unwrap_or_default();
+		let table = Table::new(
+			rows,
+			[
+				Constraint::Length(32),
+				Constraint::Length(6),
+				Constraint::Fill(1),
+			],
+		)
+		.header(header(&["ZenohId", "Kind", "Locators"]))
+		.block(Block::bordered());
+		frame.render_widget(table, area);
+	}
+
+	fn draw_metrics(&self, frame: &mut Frame, area: Rect) {
+		let rows: Vec<Row> = self
+			.snapshot
+			.lock()
+			.ok()
+			.and_then(|snapshot| {
+				snapshot.as_ref().map(|snapshot| {
+					snapshot
+						.metrics
+						.iter()
+						.map(|metric| {
+							Row::new([
+								metric.agent.clone(),
+								metric.name.clone(),
+								metric.labels.clone(),
+								metric.value.clone(),
+							])
+						})
+						.collect()
+				})
+			})
+			.unwrap_or_default();
+		let table = Table::new(
+			rows,
+			[
+				Constraint::Fill(1),
+				Constraint::Length(36),
+				Constraint::Fill(2),
+				Constraint::Length(24),
+			],
+		)
+		.header(header(&["Prefix/Name", "Metric", "Labels", "Value"]))
+		.block(Block::bordered());
+		frame.render_widget(table, area);
+	}
+
+	fn draw_topics(&mut self, frame: &mut Frame, area: Rect) {
+		let [list, detail] = Layout::horizontal([
+			Constraint::Percentage(40),
+			Constraint::Percentage(60),
+		])
+		.areas(area);
+
+		let rows: Vec<Row> = self
+			.inspection
+			.topics
+			.iter()
+			.map(|topic| {
+				Row::new([
+					topic.key.clone(),
+					topic.samples.to_string(),
+					format!("{:.1}", topic.rate),
+					format!("{:.0}", topic.bandwidth),
+				])
+			})
+			.collect();
+		let table = Table::new(
+			rows,
+			[
+				Constraint::Fill(1),
+				Constraint::Length(8),
+				Constraint::Length(9),
+				Constraint::Length(9),
+			],
+		)
+		.header(header(&["Key", "Samples", "Rate [Hz]", "BW [B/s]"]))
+		.block(Block::bordered())
+		.row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+		frame.render_stateful_widget(table, list, &mut self.topics);
+
+		let Some(subscription) = &self.inspection.subscription else {
+			frame.render_widget(
+				Paragraph::new(format!("Not subscribed, codec: {}", self.codec.name()))
+					.block(Block::bordered()),
+				detail,
+			);
+			return;
+		};
+
+		let [samples, plot] = Layout::vertical([
+			Constraint::Percentage(40),
+			Constraint::Percentage(60),
+		])
+		.areas(detail);
+		let items: Vec<ListItem> = subscription
+			.samples
+			.iter()
+			.map(|sample| ListItem::new(sample.as_str()))
+			.collect();
+		frame.render_widget(
+			List::new(items).block(Block::bordered().title(format!(
+				" {}: {:.1} Hz, {:.0} B/s, decoded as {} ",
+				subscription.key,
+				subscription.rate,
+				subscription.bandwidth,
+				subscription.codec.name()
+			))),
+			samples,
+		);
+
+		draw_plot(frame, plot, &subscription.series);
+	}
+
+	fn draw_log(&self, frame: &mut Frame, area: Rect) {
+		let items: Vec<ListItem> = self
+			.log
+			.entries()
+			.into_iter()
+			.map(|line| {
+				let style = line.style();
+				ListItem::new(line.text).style(style)
+			})
+			.collect();
+		frame.render_widget(List::new(items).block(Block::bordered()), area);
+	}
+
+	fn run(&mut self, terminal: &mut DefaultTerminal, refresh: Duration) -> Result<()> {
+		while !self.quit {
+			self.inspection = self.inspector.view();
+			terminal.draw(|frame| self.draw(frame))?;
+			if event::poll(refresh)? {
+				if let Event::Key(key) = event::read()? {
+					if key.kind == KeyEventKind::Press {
+						self.handle_key(key.code);
+					}
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Draws the numeric fields given as (age, value) points
+fn draw_plot(frame: &mut Frame, area: Rect, series: &[(String, Vec<(f64, f64)>)]) {
+	// the x axis shows the age of the values as negative seconds
+	let series: Vec<(&String, Vec<(f64, f64)>)> = series
+		.iter()
+		.map(|(name, points)| {
+			(
+				name,
+				points
+					.iter()
+					.map(|(age, value)| (-age, *value))
+					.collect(),
+			)
+		})
+		.collect();
+	let (min, max) = series
+		.iter()
+		.flat_map(|(_, points)| points.iter().map(|(_, value)| *value))
+		.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+			(min.min(value), max.max(value))
+		});
+	let (min, max) = if min.is_finite() && max - min > f64::EPSILON {
+		(min, max)
+	} else if min.is_finite() {
+		(min - 1.0, max + 1.0)
+	} else {
+		(0.0, 1.0)
+	};
+	let datasets = series
+		.iter()
+		.zip(PLOT_COLORS.iter().cycle())
+		.map(|((name, points), color)| {
+			Dataset::default()
+				.name(name.as_str())
+				.marker(symbols::Marker::Braille)
+				.graph_type(GraphType::Line)
+				.style(Style::default().fg(*color))
+				.data(points)
+		})
+		.collect();
+	let window = PLOT_WINDOW.as_secs_f64();
+	let chart = Chart::new(datasets)
+		.block(Block::bordered())
+		.x_axis(
+			Axis::default()
+				.title("age [s]")
+				.bounds([-window, 0.0])
+				.labels([format!("{:.0}", -window), String::from("0")]),
+		)
+		.y_axis(
+			Axis::default()
+				.bounds([min, max])
+				.labels([format!("{min:.2}"), format!("{max:.2}")]),
+		);
+	frame.render_widget(chart, area);
+}
+
+/// Creates a bold table header
+fn header<'a>(titles: &[&'a str]) -> Row<'a> {
+	Row::new(titles.iter().copied()).style(Style::default().add_modifier(Modifier::BOLD))
+}
+// endregion:	--- Tui
+
+/// Runs the terminal user interface until the user quits.
+/// The system is queried every `interval`, the topic views are refreshed every `refresh`.
+/// # Errors
+pub fn run(
+	collector: Arc<Collector>,
+	inspector: Inspector,
+	interval: Duration,
+	refresh: Duration,
+) -> Result<()> {
+	let mut tui = Tui::new(collector, inspector);
+	tui.log.add(&format!(
+		"collecting information for '{}'",
+		tui.collector.base_selector()
+	));
+
+	// the subscription ends when dropped at the end of the run
+	let _logs = {
+		let log = tui.log.clone();
+		tui.collector
+			.log_subscriber(move |entity| log.add_record(&entity))
+			.inspect_err(|error| {
+				tui.log
+					.add(&format!("log subscription failed: {error}"));
+			})
+			.ok()
+	};
+
+	// collect data in the background
+	let stop = Arc::new(AtomicBool::new(false));
+	{
+		let stop = stop.clone();
+		let snapshot = tui.snapshot.clone();
+		let log = tui.log.clone();
+		tui.collector.spawn(interval, move |new| {
+			for error in &new.errors {
+				log.add(error);
+			}
+			if let Ok(mut snapshot) = snapshot.lock() {
+				*snapshot = Some(new);
+			}
+			!stop.load(Ordering::Relaxed)
+		});
+	}
+
+	let mut terminal = ratatui::try_init()?;
+	let result = tui.run(&mut terminal, refresh);
+	ratatui::restore();
+	stop.store(true, Ordering::Relaxed);
+	result
+}